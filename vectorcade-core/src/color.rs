@@ -2,6 +2,7 @@
 
 /// RGBA color with components in 0.0..1.0 range.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rgba(pub f32, pub f32, pub f32, pub f32);
 
 impl Rgba {
@@ -5,7 +5,15 @@ use super::GameRng;
 /// Simple xorshift64 RNG - fast and good enough for games.
 ///
 /// Not cryptographically secure, but deterministic and portable.
+///
+/// With the `serde` feature, serializes as its bare `u64` state so a
+/// saved RNG resumes exactly where it left off.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "u64", into = "u64")
+)]
 pub struct Xorshift64 {
     state: u64,
 }
@@ -46,6 +54,21 @@ impl GameRng for Xorshift64 {
     }
 }
 
+/// Restore an RNG from its state (equivalent to [`Xorshift64::new`]).
+impl From<u64> for Xorshift64 {
+    fn from(state: u64) -> Self {
+        Self::new(state)
+    }
+}
+
+/// Extract the current state; feeding it back through `From<u64>`
+/// resumes the same sequence.
+impl From<Xorshift64> for u64 {
+    fn from(rng: Xorshift64) -> Self {
+        rng.state
+    }
+}
+
 impl Default for Xorshift64 {
     fn default() -> Self {
         Self::default_seed()
@@ -0,0 +1,29 @@
+#![cfg(feature = "serde")]
+
+use vectorcade_core::{GameRng, Rgba, Xorshift64};
+
+#[test]
+fn rgba_round_trips() {
+    let json = serde_json::to_string(&Rgba::ORANGE).unwrap();
+    assert_eq!(json, "[1.0,0.5,0.0,1.0]");
+    assert_eq!(serde_json::from_str::<Rgba>(&json).unwrap(), Rgba::ORANGE);
+}
+
+#[test]
+fn rng_resumes_from_saved_state() {
+    let mut rng = Xorshift64::new(42);
+    for _ in 0..10 {
+        rng.next_u64();
+    }
+    let json = serde_json::to_string(&rng).unwrap();
+    let mut restored: Xorshift64 = serde_json::from_str(&json).unwrap();
+    for _ in 0..10 {
+        assert_eq!(rng.next_u64(), restored.next_u64());
+    }
+}
+
+#[test]
+fn zero_rng_state_is_replaced() {
+    let mut rng: Xorshift64 = serde_json::from_str("0").unwrap();
+    assert_ne!(rng.next_u64(), 0);
+}
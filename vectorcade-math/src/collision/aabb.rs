@@ -7,6 +7,7 @@ use glam::Vec2;
 /// Defined by minimum and maximum corner points. Used for fast
 /// broadphase collision detection and spatial queries.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aabb {
     /// Minimum corner (bottom-left in screen coordinates).
     pub min: Vec2,
@@ -58,4 +59,13 @@ impl Aabb {
             && self.min.y <= other.max.y
             && self.max.y >= other.min.y
     }
+
+    /// Return the smallest AABB containing both this one and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
 }
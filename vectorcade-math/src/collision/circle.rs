@@ -9,6 +9,7 @@ use super::Aabb;
 /// Defined by center point and radius. Common for player ships,
 /// bullets, and other circular game objects.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Circle {
     /// Center point of the circle.
     pub center: Vec2,
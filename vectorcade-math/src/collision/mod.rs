@@ -1,7 +1,8 @@
 //! Collision detection primitives for 2D games.
 //!
 //! Provides axis-aligned bounding boxes ([`Aabb`]), circles ([`Circle`]),
-//! and line intersection tests for basic collision detection.
+//! and line intersection tests for basic collision detection, plus
+//! segment clipping to either shape.
 
 mod aabb;
 mod circle;
@@ -22,37 +23,23 @@ use glam::Vec2;
 /// * `aabb` - The axis-aligned bounding box to test against
 #[must_use]
 pub fn line_aabb_intersect(a: Vec2, b: Vec2, aabb: &Aabb) -> bool {
-    if aabb.contains_point(a) || aabb.contains_point(b) {
-        return true;
-    }
-
-    let d = b - a;
-    let mut t_min = 0.0_f32;
-    let mut t_max = 1.0_f32;
-
-    // Check X slab
-    if d.x.abs() > 1e-9 {
-        let t1 = (aabb.min.x - a.x) / d.x;
-        let t2 = (aabb.max.x - a.x) / d.x;
-        let (t_near, t_far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
-        t_min = t_min.max(t_near);
-        t_max = t_max.min(t_far);
-    } else if a.x < aabb.min.x || a.x > aabb.max.x {
-        return false;
-    }
-
-    // Check Y slab
-    if d.y.abs() > 1e-9 {
-        let t1 = (aabb.min.y - a.y) / d.y;
-        let t2 = (aabb.max.y - a.y) / d.y;
-        let (t_near, t_far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
-        t_min = t_min.max(t_near);
-        t_max = t_max.min(t_far);
-    } else if a.y < aabb.min.y || a.y > aabb.max.y {
-        return false;
-    }
+    aabb_range(a, b, aabb).is_some()
+}
 
-    t_min <= t_max
+/// Clip a line segment to an AABB.
+///
+/// Returns the part of the segment inside the box (boundary included),
+/// in the segment's direction, or `None` if it misses the box. Uses
+/// the same slab test as [`line_aabb_intersect`] (Liang-Barsky).
+///
+/// # Arguments
+///
+/// * `a` - Start point of the line segment
+/// * `b` - End point of the line segment
+/// * `aabb` - The axis-aligned bounding box to clip against
+#[must_use]
+pub fn clip_line_aabb(a: Vec2, b: Vec2, aabb: &Aabb) -> Option<(Vec2, Vec2)> {
+    aabb_range(a, b, aabb).map(|range| segment_part(a, b, range))
 }
 
 /// Check if a line segment intersects a circle.
@@ -66,21 +53,79 @@ pub fn line_aabb_intersect(a: Vec2, b: Vec2, aabb: &Aabb) -> bool {
 /// * `circle` - The circle to test against
 #[must_use]
 pub fn line_circle_intersect(a: Vec2, b: Vec2, circle: &Circle) -> bool {
+    circle_range(a, b, circle).is_some()
+}
+
+/// Clip a line segment to a circle.
+///
+/// Returns the part of the segment inside the circle (boundary
+/// included), in the segment's direction, or `None` if it misses the
+/// circle.
+///
+/// # Arguments
+///
+/// * `a` - Start point of the line segment
+/// * `b` - End point of the line segment
+/// * `circle` - The circle to clip against
+#[must_use]
+pub fn clip_line_circle(a: Vec2, b: Vec2, circle: &Circle) -> Option<(Vec2, Vec2)> {
+    circle_range(a, b, circle).map(|range| segment_part(a, b, range))
+}
+
+/// Return the range of `t` in `0..=1` for which `a + (b - a) * t` lies
+/// inside `aabb`.
+fn aabb_range(a: Vec2, b: Vec2, aabb: &Aabb) -> Option<(f32, f32)> {
+    let d = b - a;
+    let mut t_min = 0.0_f32;
+    let mut t_max = 1.0_f32;
+
+    for axis in 0..2 {
+        let (p, dp) = (a[axis], d[axis]);
+        let (lo, hi) = (aabb.min[axis], aabb.max[axis]);
+        if dp.abs() > 1e-9 {
+            let t1 = (lo - p) / dp;
+            let t2 = (hi - p) / dp;
+            let (t_near, t_far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+            t_min = t_min.max(t_near);
+            t_max = t_max.min(t_far);
+        } else if p < lo || p > hi {
+            return None;
+        }
+    }
+
+    (t_min <= t_max).then_some((t_min, t_max))
+}
+
+/// Return the range of `t` in `0..=1` for which `a + (b - a) * t` lies
+/// inside `circle`.
+fn circle_range(a: Vec2, b: Vec2, circle: &Circle) -> Option<(f32, f32)> {
     let d = b - a;
     let f = a - circle.center;
 
     let a_coef = d.dot(d);
     let b_coef = 2.0 * f.dot(d);
     let c_coef = f.dot(f) - circle.radius * circle.radius;
+    if a_coef == 0.0 {
+        return (c_coef <= 0.0).then_some((0.0, 1.0));
+    }
 
     let discriminant = b_coef * b_coef - 4.0 * a_coef * c_coef;
     if discriminant < 0.0 {
-        return false;
+        return None;
     }
 
     let sqrt_disc = discriminant.sqrt();
-    let t1 = (-b_coef - sqrt_disc) / (2.0 * a_coef);
-    let t2 = (-b_coef + sqrt_disc) / (2.0 * a_coef);
+    let t1 = ((-b_coef - sqrt_disc) / (2.0 * a_coef)).max(0.0);
+    let t2 = ((-b_coef + sqrt_disc) / (2.0 * a_coef)).min(1.0);
+    (t1 <= t2).then_some((t1, t2))
+}
 
-    (0.0..=1.0).contains(&t1) || (0.0..=1.0).contains(&t2) || (t1 < 0.0 && t2 > 1.0)
+/// Return the points at `range` along `a`-`b`, keeping exact endpoints.
+fn segment_part(a: Vec2, b: Vec2, (t0, t1): (f32, f32)) -> (Vec2, Vec2) {
+    let at = |t: f32| match t {
+        0.0 => a,
+        1.0 => b,
+        _ => a + (b - a) * t,
+    };
+    (at(t0), at(t1))
 }
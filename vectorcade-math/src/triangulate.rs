@@ -0,0 +1,251 @@
+//! Polygon triangulation by ear clipping.
+//!
+//! [`triangulate`] turns a set of contours into triangles covering the
+//! area a [`FillRule`] fills. Contours may be concave and may nest
+//! (holes, islands inside holes), but must not cross each other or
+//! themselves; crossing input still terminates but may leave gaps.
+//!
+//! Nesting is found by containment, each filled region is joined to its
+//! holes by bridge edges into a single ring, and the ring is clipped one
+//! ear at a time.
+
+use glam::Vec2;
+
+/// Rule deciding which regions enclosed by contours are filled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// Filled where the contours wind around a point a nonzero number
+    /// of times; a hole must run opposite to its outline.
+    #[default]
+    NonZero,
+    /// Filled where a ray from a point crosses an odd number of
+    /// contours; every nested contour toggles, whatever its direction.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Check if a point with winding number `winding` is filled.
+    #[must_use]
+    pub fn is_filled(self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// A cleaned contour: indices into the flattened input points.
+struct Ring {
+    idx: Vec<u32>,
+    area: f32,
+}
+
+/// Triangulate `contours` filled with `rule`.
+///
+/// Returns three indices per triangle into the contours' points,
+/// numbered in order across all contours (the first point of the second
+/// contour follows the last point of the first). Triangles are
+/// counter-clockwise in a Y-up space. Contours are implicitly closed; a
+/// repeated closing point is allowed. Contours with fewer than three
+/// distinct points, zero area or non-finite points are ignored.
+#[must_use]
+pub fn triangulate(contours: &[Vec<Vec2>], rule: FillRule) -> Vec<u32> {
+    let pts: Vec<Vec2> = contours.iter().flatten().copied().collect();
+    let mut rings = Vec::new();
+    let mut base = 0;
+    for contour in contours {
+        let mut idx: Vec<u32> = Vec::with_capacity(contour.len());
+        for i in base..base + contour.len() as u32 {
+            if idx
+                .last()
+                .is_none_or(|&j| pts[j as usize] != pts[i as usize])
+            {
+                idx.push(i);
+            }
+        }
+        base += contour.len() as u32;
+        while idx.len() > 1 && pts[idx[0] as usize] == pts[idx[idx.len() - 1] as usize] {
+            idx.pop();
+        }
+        let area = signed_area(&pts, &idx);
+        if idx.len() >= 3 && area.is_finite() && area != 0.0 {
+            rings.push(Ring { idx, area });
+        }
+    }
+
+    // Outlines come before the contours they contain.
+    rings.sort_by(|a, b| b.area.abs().total_cmp(&a.area.abs()));
+    let mut parent: Vec<Option<usize>> = Vec::with_capacity(rings.len());
+    let mut winding: Vec<i32> = Vec::with_capacity(rings.len());
+    for (i, ring) in rings.iter().enumerate() {
+        let probe = pts[ring.idx[0] as usize];
+        let p = (0..i).rev().find(|&j| contains(&pts, &rings[j].idx, probe));
+        parent.push(p);
+        winding.push(p.map_or(0, |j| winding[j]) + if ring.area > 0.0 { 1 } else { -1 });
+    }
+
+    let mut out = Vec::new();
+    for (i, ring) in rings.iter().enumerate() {
+        if !rule.is_filled(winding[i]) {
+            continue;
+        }
+        let holes: Vec<&Ring> = (i + 1..rings.len())
+            .filter(|&j| parent[j] == Some(i))
+            .map(|j| &rings[j])
+            .collect();
+        ear_clip(&pts, bridge(&pts, ring, &holes), &mut out);
+    }
+    out
+}
+
+/// Signed area of a ring, positive when counter-clockwise.
+fn signed_area(pts: &[Vec2], idx: &[u32]) -> f32 {
+    let n = idx.len();
+    (0..n)
+        .map(|i| pts[idx[i] as usize].perp_dot(pts[idx[(i + 1) % n] as usize]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Even-odd point-in-polygon test.
+fn contains(pts: &[Vec2], idx: &[u32], p: Vec2) -> bool {
+    let n = idx.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (pts[idx[i] as usize], pts[idx[(i + 1) % n] as usize]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Join `outer` and its `holes` into one counter-clockwise ring.
+///
+/// Each hole is cut open at its rightmost vertex and connected to the
+/// nearest outline vertex it can see, walking the hole clockwise and
+/// returning along the same bridge.
+fn bridge(pts: &[Vec2], outer: &Ring, holes: &[&Ring]) -> Vec<u32> {
+    let p = |i: u32| pts[i as usize];
+    let mut ring = outer.idx.clone();
+    if outer.area < 0.0 {
+        ring.reverse();
+    }
+    let mut holes: Vec<Vec<u32>> = holes
+        .iter()
+        .map(|h| {
+            let mut idx = h.idx.clone();
+            if h.area > 0.0 {
+                idx.reverse();
+            }
+            let right = (0..idx.len())
+                .max_by(|&a, &b| p(idx[a]).x.total_cmp(&p(idx[b]).x))
+                .unwrap_or(0);
+            idx.rotate_left(right);
+            idx
+        })
+        .collect();
+    holes.sort_by(|a, b| p(b[0]).x.total_cmp(&p(a[0]).x));
+
+    for h in 0..holes.len() {
+        let m = p(holes[h][0]);
+        let blocked = |v: Vec2| {
+            let mut edges = ring_edges(&ring).chain(holes[h..].iter().flat_map(|r| ring_edges(r)));
+            edges.any(|(a, b)| {
+                let (a, b) = (p(a), p(b));
+                a != m && a != v && b != m && b != v && crosses(m, v, a, b)
+            })
+        };
+        let n = ring.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| {
+            m.distance_squared(p(ring[a]))
+                .total_cmp(&m.distance_squared(p(ring[b])))
+        });
+        // Without a clean bridge (touching contours), use the nearest vertex.
+        let k = order
+            .iter()
+            .copied()
+            .find(|&k| {
+                let (prev, v, next) = (p(ring[(k + n - 1) % n]), p(ring[k]), p(ring[(k + 1) % n]));
+                in_wedge(prev, v, next, m - v) && !blocked(v)
+            })
+            .unwrap_or(order[0]);
+        let hole = &holes[h];
+        let mut spliced = Vec::with_capacity(n + hole.len() + 2);
+        spliced.extend_from_slice(&ring[..=k]);
+        spliced.extend_from_slice(hole);
+        spliced.extend([hole[0], ring[k]]);
+        spliced.extend_from_slice(&ring[k + 1..]);
+        ring = spliced;
+    }
+    ring
+}
+
+fn ring_edges(ring: &[u32]) -> impl Iterator<Item = (u32, u32)> + '_ {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Check if direction `d` from `v` points into a counter-clockwise
+/// ring's interior, given the neighbors `prev` and `next` of `v`.
+fn in_wedge(prev: Vec2, v: Vec2, next: Vec2, d: Vec2) -> bool {
+    let (e0, e1) = (prev - v, next - v);
+    if (v - prev).perp_dot(next - v) >= 0.0 {
+        e1.perp_dot(d) > 0.0 && d.perp_dot(e0) > 0.0
+    } else {
+        !(e0.perp_dot(d) >= 0.0 && d.perp_dot(e1) >= 0.0)
+    }
+}
+
+/// Check if segments `a`-`b` and `c`-`d` cross at a point inside both.
+fn crosses(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let side = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p);
+    let (d1, d2) = (side(a, b, c), side(a, b, d));
+    let (d3, d4) = (side(c, d, a), side(c, d, b));
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Clip ears off a counter-clockwise ring, appending triangles to `out`.
+fn ear_clip(pts: &[Vec2], mut ring: Vec<u32>, out: &mut Vec<u32>) {
+    let p = |i: u32| pts[i as usize];
+    let mut i = 0;
+    let mut misses = 0;
+    while ring.len() > 3 {
+        let n = ring.len();
+        let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        let convex = (p(b) - p(a)).perp_dot(p(c) - p(b)) > 0.0;
+        let ear = convex
+            && !ring.iter().any(|&q| {
+                let q = p(q);
+                q != p(a) && q != p(b) && q != p(c) && in_triangle(q, p(a), p(b), p(c))
+            });
+        if ear || misses >= n {
+            // A full pass without an ear means crossing or degenerate
+            // input; drop the vertex anyway so clipping terminates.
+            if convex {
+                out.extend([a, b, c]);
+            }
+            ring.remove(i);
+            i = i.checked_sub(1).unwrap_or(ring.len() - 1);
+            misses = 0;
+        } else {
+            i = (i + 1) % n;
+            misses += 1;
+        }
+    }
+    if let [a, b, c] = ring[..]
+        && (p(b) - p(a)).perp_dot(p(c) - p(b)) > 0.0
+    {
+        out.extend([a, b, c]);
+    }
+}
+
+/// Check if `q` lies inside or on the counter-clockwise triangle `abc`.
+fn in_triangle(q: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(q - a) >= 0.0
+        && (c - b).perp_dot(q - b) >= 0.0
+        && (a - c).perp_dot(q - c) >= 0.0
+}
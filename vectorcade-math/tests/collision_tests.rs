@@ -1,5 +1,7 @@
 use glam::Vec2;
-use vectorcade_math::{Aabb, Circle, line_aabb_intersect, line_circle_intersect};
+use vectorcade_math::{
+    Aabb, Circle, clip_line_aabb, clip_line_circle, line_aabb_intersect, line_circle_intersect,
+};
 
 #[test]
 fn aabb_overlap() {
@@ -57,3 +59,57 @@ fn line_circle() {
         &circle
     ));
 }
+
+#[test]
+fn clip_line_to_aabb() {
+    let aabb = Aabb::from_center(Vec2::ZERO, Vec2::splat(1.0));
+    assert_eq!(
+        clip_line_aabb(Vec2::new(-3.0, 0.5), Vec2::new(3.0, 0.5), &aabb),
+        Some((Vec2::new(-1.0, 0.5), Vec2::new(1.0, 0.5)))
+    );
+    // Direction is kept, and endpoints inside the box are exact.
+    let (a, b) = (Vec2::new(0.3, 0.1), Vec2::new(0.7, -2.0));
+    let (start, end) = clip_line_aabb(a, b, &aabb).unwrap();
+    assert_eq!(start, a);
+    assert!(end.distance(Vec2::new(0.3 + 0.4 * 1.1 / 2.1, -1.0)) < 1e-6);
+    let inside = (Vec2::splat(0.1), Vec2::splat(0.2));
+    assert_eq!(clip_line_aabb(inside.0, inside.1, &aabb), Some(inside));
+    assert_eq!(
+        clip_line_aabb(Vec2::new(2.0, -3.0), Vec2::new(2.0, 3.0), &aabb),
+        None
+    );
+}
+
+#[test]
+fn clip_line_to_circle() {
+    let circle = Circle::new(Vec2::new(1.0, 0.0), 1.0);
+    let (a, b) = clip_line_circle(Vec2::new(-1.0, 0.0), Vec2::new(3.0, 0.0), &circle).unwrap();
+    assert!(a.distance(Vec2::ZERO) < 1e-6 && b.distance(Vec2::new(2.0, 0.0)) < 1e-6);
+    let inside = (Vec2::new(0.5, 0.0), Vec2::new(1.5, 0.2));
+    assert_eq!(clip_line_circle(inside.0, inside.1, &circle), Some(inside));
+    assert_eq!(
+        clip_line_circle(Vec2::new(-1.0, 2.0), Vec2::new(3.0, 2.0), &circle),
+        None
+    );
+    assert_eq!(
+        clip_line_circle(Vec2::X, Vec2::X, &circle),
+        Some((Vec2::X, Vec2::X))
+    );
+    assert!(!line_circle_intersect(
+        Vec2::Y * 5.0,
+        Vec2::Y * 5.0,
+        &circle
+    ));
+}
+
+#[test]
+fn aabb_union_covers_both() {
+    let a = Aabb::from_min_max(Vec2::ZERO, Vec2::ONE);
+    let b = Aabb::from_min_max(Vec2::new(-1.0, 0.5), Vec2::new(0.5, 3.0));
+    let u = a.union(&b);
+    assert_eq!(
+        u,
+        Aabb::from_min_max(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 3.0))
+    );
+    assert_eq!(a.union(&a), a);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "serde")]
+
+use glam::Vec2;
+use vectorcade_math::{Aabb, Circle, FillRule};
+
+#[test]
+fn aabb_round_trips() {
+    let aabb = Aabb::from_min_max(Vec2::new(-1.0, -2.0), Vec2::new(3.0, 4.0));
+    let json = serde_json::to_string(&aabb).unwrap();
+    assert_eq!(serde_json::from_str::<Aabb>(&json).unwrap(), aabb);
+}
+
+#[test]
+fn circle_round_trips() {
+    let circle = Circle::new(Vec2::new(0.5, -0.5), 0.25);
+    let json = serde_json::to_string(&circle).unwrap();
+    assert_eq!(json, r#"{"center":[0.5,-0.5],"radius":0.25}"#);
+    assert_eq!(serde_json::from_str::<Circle>(&json).unwrap(), circle);
+}
+
+#[test]
+fn fill_rule_round_trips() {
+    let json = serde_json::to_string(&FillRule::EvenOdd).unwrap();
+    assert_eq!(json, r#""EvenOdd""#);
+    assert_eq!(
+        serde_json::from_str::<FillRule>(&json).unwrap(),
+        FillRule::EvenOdd
+    );
+}
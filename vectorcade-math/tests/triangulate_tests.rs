@@ -0,0 +1,123 @@
+use glam::Vec2;
+use vectorcade_math::{FillRule, triangulate};
+
+fn square(center: Vec2, half: f32) -> Vec<Vec2> {
+    [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+        .iter()
+        .map(|&(x, y)| center + Vec2::new(x, y) * half)
+        .collect()
+}
+
+fn reversed(mut pts: Vec<Vec2>) -> Vec<Vec2> {
+    pts.reverse();
+    pts
+}
+
+/// Return the summed area of the triangles, checking each is
+/// counter-clockwise and not degenerate.
+fn area(contours: &[Vec<Vec2>], rule: FillRule) -> f32 {
+    let pts: Vec<Vec2> = contours.iter().flatten().copied().collect();
+    let idx = triangulate(contours, rule);
+    assert_eq!(idx.len() % 3, 0);
+    idx.chunks_exact(3)
+        .map(|t| {
+            let [a, b, c] = [0, 1, 2].map(|i| pts[t[i] as usize]);
+            let twice = (b - a).perp_dot(c - a);
+            assert!(twice > 0.0, "triangle {a} {b} {c} is not counter-clockwise");
+            twice * 0.5
+        })
+        .sum()
+}
+
+fn assert_area(contours: &[Vec<Vec2>], rule: FillRule, expected: f32) {
+    let got = area(contours, rule);
+    assert!(
+        (got - expected).abs() < 1e-4,
+        "{rule:?}: {got} != {expected}"
+    );
+}
+
+#[test]
+fn convex_polygons_in_either_direction() {
+    let ccw = vec![square(Vec2::ZERO, 1.0)];
+    assert_eq!(triangulate(&ccw, FillRule::NonZero).len(), 6);
+    assert_area(&ccw, FillRule::NonZero, 4.0);
+    assert_area(&[reversed(square(Vec2::ZERO, 1.0))], FillRule::EvenOdd, 4.0);
+}
+
+#[test]
+fn concave_polygons_are_covered_exactly() {
+    let l_shape = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(3.0, 0.0),
+        Vec2::new(3.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 3.0),
+        Vec2::new(0.0, 3.0),
+    ];
+    assert_area(&[l_shape], FillRule::NonZero, 5.0);
+
+    let star: Vec<Vec2> = (0..10)
+        .map(|i| {
+            let r = if i % 2 == 0 { 1.0 } else { 0.4 };
+            Vec2::from_angle(i as f32 * std::f32::consts::TAU / 10.0) * r
+        })
+        .collect();
+    let expected = 10.0 * 0.5 * 0.4 * (std::f32::consts::TAU / 10.0).sin();
+    assert_area(&[star], FillRule::EvenOdd, expected);
+}
+
+#[test]
+fn holes_follow_the_fill_rule() {
+    let outer = square(Vec2::ZERO, 2.0);
+    let opposite = vec![outer.clone(), reversed(square(Vec2::new(0.5, 0.0), 1.0))];
+    assert_area(&opposite, FillRule::NonZero, 12.0);
+    assert_area(&opposite, FillRule::EvenOdd, 12.0);
+
+    // A hole wound like its outline only cuts out under even-odd.
+    let same = vec![outer, square(Vec2::new(0.5, 0.0), 1.0)];
+    assert_area(&same, FillRule::NonZero, 16.0);
+    assert_area(&same, FillRule::EvenOdd, 12.0);
+}
+
+#[test]
+fn multiple_holes_and_islands() {
+    let contours = vec![
+        square(Vec2::ZERO, 4.0),
+        reversed(square(Vec2::new(-2.0, 0.0), 1.5)),
+        reversed(square(Vec2::new(2.0, 2.0), 1.0)),
+        square(Vec2::new(-2.0, 0.0), 0.5),
+    ];
+    let expected = 64.0 - 9.0 - 4.0 + 1.0;
+    assert_area(&contours, FillRule::NonZero, expected);
+    assert_area(&contours, FillRule::EvenOdd, expected);
+}
+
+#[test]
+fn indices_span_all_input_points() {
+    // The closing point repeats the first, and the hole follows it.
+    let mut outer = square(Vec2::ZERO, 2.0);
+    outer.push(outer[0]);
+    let hole = reversed(square(Vec2::ZERO, 1.0));
+    let idx = triangulate(&[outer, hole], FillRule::NonZero);
+    assert!(idx.iter().all(|&i| i < 9 && i != 4));
+    assert!(idx.contains(&5) && idx.contains(&8));
+}
+
+#[test]
+fn degenerate_contours_are_ignored() {
+    let line = vec![Vec2::ZERO, Vec2::X, Vec2::X * 2.0];
+    let nan = vec![Vec2::ZERO, Vec2::NAN, Vec2::Y];
+    assert!(triangulate(&[vec![Vec2::ZERO, Vec2::X]], FillRule::NonZero).is_empty());
+    assert!(triangulate(&[line, nan], FillRule::NonZero).is_empty());
+    assert!(triangulate(&[], FillRule::EvenOdd).is_empty());
+}
+
+#[test]
+fn fill_rule_decides_winding() {
+    assert!(FillRule::NonZero.is_filled(2));
+    assert!(!FillRule::EvenOdd.is_filled(2));
+    assert!(FillRule::EvenOdd.is_filled(-1));
+    assert!(!FillRule::NonZero.is_filled(0));
+    assert_eq!(FillRule::default(), FillRule::NonZero);
+}
@@ -0,0 +1,59 @@
+//! Separable Gaussian blur used for phosphor bloom.
+
+use glam::Vec3;
+
+/// Blur a `width` x `height` RGB buffer with a Gaussian of `radius_px`.
+///
+/// The kernel spans `radius_px` pixels either side with sigma at half
+/// the radius and is normalized, so blurring preserves total energy
+/// away from the edges. A radius below one pixel returns a copy.
+pub(crate) fn gaussian_blur(src: &[Vec3], width: u32, height: u32, radius_px: f32) -> Vec<Vec3> {
+    let kernel = kernel(radius_px);
+    if kernel.len() <= 1 {
+        return src.to_vec();
+    }
+    let (w, h) = (width as usize, height as usize);
+    let mut tmp = vec![Vec3::ZERO; src.len()];
+    let mut out = vec![Vec3::ZERO; src.len()];
+    pass(src, &mut tmp, w, h, &kernel, 1, w);
+    pass(&tmp, &mut out, h, w, &kernel, w, 1);
+    out
+}
+
+fn kernel(radius_px: f32) -> Vec<f32> {
+    let r = radius_px.max(0.0).round() as i32;
+    let sigma = (radius_px * 0.5).max(1e-3);
+    let mut k: Vec<f32> = (-r..=r)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f32 = k.iter().sum();
+    k.iter_mut().for_each(|v| *v /= sum);
+    k
+}
+
+/// One 1D pass. `len` samples along the blur axis with `step` stride;
+/// `lines` independent lines spaced `line_step` apart.
+fn pass(
+    src: &[Vec3],
+    dst: &mut [Vec3],
+    len: usize,
+    lines: usize,
+    kernel: &[f32],
+    step: usize,
+    line_step: usize,
+) {
+    let r = (kernel.len() / 2) as isize;
+    for line in 0..lines {
+        let base = line * line_step;
+        for i in 0..len as isize {
+            let mut acc = Vec3::ZERO;
+            for (k, weight) in kernel.iter().enumerate() {
+                let j = i + k as isize - r;
+                if (0..len as isize).contains(&j) {
+                    acc += src[base + j as usize * step] * *weight;
+                }
+            }
+            dst[base + i as usize * step] = acc;
+        }
+    }
+}
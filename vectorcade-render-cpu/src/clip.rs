@@ -0,0 +1,151 @@
+//! Clip regions in pixel space.
+//!
+//! Stroked paths are cut geometrically with the shared segment clippers,
+//! so the beam starts and stops at the region's edge. Fills, stroke width
+//! and caps are then trimmed per pixel with an antialiased mask.
+
+use glam::Vec2;
+use vectorcade_shared::game::{ScreenInfo, ndc_to_px};
+use vectorcade_shared::{Aabb, Circle, clip_line_aabb, clip_line_circle};
+
+/// A clip region mapped to framebuffer pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ClipShape {
+    /// Pixel rectangle; empty if `min` exceeds `max` on either axis.
+    Rect(Aabb),
+    /// Axis-aligned ellipse: an NDC circle stretched to the screen.
+    Ellipse { center: Vec2, radii: Vec2 },
+}
+
+impl ClipShape {
+    /// Map an NDC rectangle to pixels, keeping an inverted one empty.
+    pub(crate) fn rect(r: &Aabb, screen: ScreenInfo) -> Self {
+        let min = ndc_to_px(Vec2::new(r.min.x, r.max.y), screen);
+        let max = ndc_to_px(Vec2::new(r.max.x, r.min.y), screen);
+        Self::Rect(Aabb::from_min_max(min, max))
+    }
+
+    /// Map an NDC circle to a pixel ellipse.
+    pub(crate) fn circle(c: &Circle, screen: ScreenInfo) -> Self {
+        let half = Vec2::new(screen.width_px as f32, screen.height_px as f32) * 0.5;
+        Self::Ellipse {
+            center: ndc_to_px(c.center, screen),
+            radii: half * c.radius,
+        }
+    }
+
+    /// Return the part of segment `a`-`b` inside the region.
+    fn clip_segment(&self, a: Vec2, b: Vec2) -> Option<(Vec2, Vec2)> {
+        match *self {
+            Self::Rect(r) if r.min.cmple(r.max).all() => clip_line_aabb(a, b, &r),
+            Self::Rect(_) => None,
+            Self::Ellipse { center, radii } => {
+                if radii.min_element().is_nan() || radii.min_element() <= 0.0 {
+                    return None;
+                }
+                // Clip against the unit circle in the ellipse's own scale.
+                let local = |p: Vec2| (p - center) / radii;
+                let (la, lb) = (local(a), local(b));
+                let (p, q) = clip_line_circle(la, lb, &Circle::new(Vec2::ZERO, 1.0))?;
+                let back = |p: Vec2| match p {
+                    p if p == la => a,
+                    p if p == lb => b,
+                    p => center + p * radii,
+                };
+                Some((back(p), back(q)))
+            }
+        }
+    }
+
+    /// Return how much of the pixel centered at `p` is inside.
+    fn coverage(&self, p: Vec2) -> f32 {
+        match *self {
+            Self::Rect(r) if r.min.cmple(r.max).all() => {
+                let d = (r.min - p).max(p - r.max);
+                (0.5 - d.x).clamp(0.0, 1.0) * (0.5 - d.y).clamp(0.0, 1.0)
+            }
+            Self::Rect(_) => 0.0,
+            Self::Ellipse { center, radii } => {
+                let q = (p - center) / radii;
+                let len = q.length();
+                if len == 0.0 {
+                    return if radii.min_element() > 0.0 { 1.0 } else { 0.0 };
+                }
+                // Approximate the distance to the edge in pixels.
+                let edge_px = (len - 1.0) * (q / len * radii).length();
+                (0.5 - edge_px).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Return the coverage of pixel center `p` by the intersection of `clips`.
+pub(crate) fn mask(clips: &[ClipShape], p: Vec2) -> f32 {
+    clips.iter().map(|c| c.coverage(p)).product()
+}
+
+/// Cut `path` to the intersection of `clips`.
+///
+/// `pos` gives a vertex's position and `lerp` interpolates between two
+/// vertices, so per-vertex attributes survive clipping. Returns the
+/// pieces inside, in order; a closed path (last vertex equal to the
+/// first) that is cut keeps the piece running through its start whole.
+pub(crate) fn clip_path<T: Copy + PartialEq>(
+    path: &[T],
+    clips: &[ClipShape],
+    pos: impl Fn(&T) -> Vec2,
+    lerp: impl Fn(&T, &T, f32) -> T,
+) -> Vec<Vec<T>> {
+    if clips.is_empty() {
+        return vec![path.to_vec()];
+    }
+    if let [v] = path {
+        let p = pos(v);
+        let inside = clips.iter().all(|c| c.clip_segment(p, p).is_some());
+        return if inside { vec![vec![*v]] } else { Vec::new() };
+    }
+    let mut out: Vec<Vec<T>> = Vec::new();
+    let mut cur: Vec<T> = Vec::new();
+    for w in path.windows(2) {
+        let (a, b) = (pos(&w[0]), pos(&w[1]));
+        let seg = clips
+            .iter()
+            .try_fold((a, b), |(p, q), c| c.clip_segment(p, q));
+        let Some((p, q)) = seg else {
+            if cur.len() > 1 {
+                out.push(std::mem::take(&mut cur));
+            }
+            cur.clear();
+            continue;
+        };
+        let len_sq = (b - a).length_squared();
+        let at = |p: Vec2| match p {
+            p if p == a => w[0],
+            p if p == b => w[1],
+            p => lerp(&w[0], &w[1], (p - a).dot(b - a) / len_sq),
+        };
+        let (start, end) = (at(p), at(q));
+        if cur.last() != Some(&start) {
+            if cur.len() > 1 {
+                out.push(std::mem::take(&mut cur));
+            }
+            cur = vec![start];
+        }
+        cur.push(end);
+    }
+    if cur.len() > 1 {
+        out.push(cur);
+    }
+    let closed = path.len() > 2 && path.first() == path.last();
+    if closed && out.len() > 1 && out[0][0] == path[0] {
+        let first = out.remove(0);
+        if let Some(last) = out.last_mut()
+            && last.last() == path.last()
+        {
+            last.extend_from_slice(&first[1..]);
+        } else {
+            out.insert(0, first);
+        }
+    }
+    out
+}
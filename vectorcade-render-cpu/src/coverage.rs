@@ -0,0 +1,325 @@
+//! Antialiased stroke coverage accumulation.
+//!
+//! Strokes are accumulated into a coverage mask (taking the maximum
+//! where segments overlap) and composited once per primitive, so the
+//! shared joints of a polyline are not blended twice.
+//!
+//! Round-capped segments use an analytic distance falloff. Triangle
+//! meshes (for other caps and joins) are sampled on a 4x4 grid per
+//! pixel and unioned, so shared triangle edges leave no seams.
+//!
+//! Filled polygons are scan-converted on the same sample grid under
+//! their fill rule.
+//!
+//! Gradient segments also record, per pixel, the interpolated color of
+//! the segment that covers it most.
+//!
+//! Coverage is multiplied by the active clip mask as it is drained.
+
+use crate::Framebuffer;
+use crate::clip::{ClipShape, mask};
+use glam::Vec2;
+use vectorcade_shared::draw::{BlendMode, StrokeMesh, StrokeVertex};
+use vectorcade_shared::{FillRule, Rgba};
+
+/// Sub-pixel samples per axis for triangle coverage.
+const GRID: u32 = 4;
+
+/// Scratch coverage mask the size of the target framebuffer.
+pub(crate) struct Coverage {
+    width: i64,
+    height: i64,
+    data: Vec<f32>,
+    /// Sub-pixel sample bits for the mesh being added; all zero between
+    /// calls to `add_mesh`.
+    samples: Vec<u16>,
+    /// Per-pixel color for gradient strokes; allocated on first use.
+    colors: Vec<Rgba>,
+    /// Regions every drained pixel is masked to.
+    clips: Vec<ClipShape>,
+    dirty_min: (i64, i64),
+    dirty_max: (i64, i64),
+}
+
+impl Coverage {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width: i64::from(width),
+            height: i64::from(height),
+            data: vec![0.0; width as usize * height as usize],
+            samples: vec![0; width as usize * height as usize],
+            colors: Vec::new(),
+            clips: Vec::new(),
+            dirty_min: (i64::MAX, i64::MAX),
+            dirty_max: (i64::MIN, i64::MIN),
+        }
+    }
+
+    /// Mask the primitives drained from now on to the intersection of
+    /// `clips`.
+    pub(crate) fn set_clip(&mut self, clips: Vec<ClipShape>) {
+        self.clips = clips;
+    }
+
+    /// Add an antialiased round-capped segment in pixel space.
+    ///
+    /// Widths below one pixel are drawn one pixel wide with coverage
+    /// scaled down, approximating a dimmer, thinner beam.
+    pub(crate) fn add_segment(&mut self, a: Vec2, b: Vec2, width_px: f32) {
+        if !(a.is_finite() && b.is_finite()) || width_px <= 0.0 {
+            return;
+        }
+        let half = width_px.max(1.0) * 0.5;
+        let intensity = width_px.min(1.0);
+        let pad = half + 1.0;
+        let x0 = ((a.x.min(b.x) - pad).floor() as i64).max(0);
+        let y0 = ((a.y.min(b.y) - pad).floor() as i64).max(0);
+        let x1 = ((a.x.max(b.x) + pad).ceil() as i64).min(self.width - 1);
+        let y1 = ((a.y.max(b.y) + pad).ceil() as i64).min(self.height - 1);
+        if x0 > x1 || y0 > y1 {
+            return;
+        }
+        self.dirty_min = (self.dirty_min.0.min(x0), self.dirty_min.1.min(y0));
+        self.dirty_max = (self.dirty_max.0.max(x1), self.dirty_max.1.max(y1));
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let d = segment_distance(p, a, b);
+                let c = (half + 0.5 - d).clamp(0.0, 1.0) * intensity;
+                let slot = &mut self.data[(y * self.width + x) as usize];
+                *slot = slot.max(c);
+            }
+        }
+    }
+
+    /// Add the union of a mesh's pixel-space triangles, with coverage
+    /// scaled by `intensity`.
+    pub(crate) fn add_mesh(&mut self, mesh: &StrokeMesh, intensity: f32) {
+        let (mut lo, mut hi) = ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN));
+        for tri in mesh.triangles() {
+            if !tri.iter().all(|p| p.is_finite()) {
+                continue;
+            }
+            let min = tri[0].min(tri[1]).min(tri[2]);
+            let max = tri[0].max(tri[1]).max(tri[2]);
+            let x0 = (min.x.floor() as i64).max(0);
+            let y0 = (min.y.floor() as i64).max(0);
+            let x1 = (max.x.ceil() as i64).min(self.width - 1);
+            let y1 = (max.y.ceil() as i64).min(self.height - 1);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    let mask = triangle_mask(x, y, tri);
+                    if mask != 0 {
+                        self.samples[(y * self.width + x) as usize] |= mask;
+                        lo = (lo.0.min(x), lo.1.min(y));
+                        hi = (hi.0.max(x), hi.1.max(y));
+                    }
+                }
+            }
+        }
+        self.resolve_samples(lo, hi, intensity);
+    }
+
+    /// Add the pixel-space area enclosed by `contours` under `rule`.
+    pub(crate) fn add_fill(&mut self, contours: &[Vec<Vec2>], rule: FillRule) {
+        let edges: Vec<(Vec2, Vec2)> = contours
+            .iter()
+            .flat_map(|c| c.iter().zip(c.iter().cycle().skip(1)))
+            .map(|(&a, &b)| (a, b))
+            .filter(|(a, b)| a.is_finite() && b.is_finite() && a.y != b.y)
+            .collect();
+        let Some(y_min) = edges.iter().map(|e| e.0.y.min(e.1.y)).reduce(f32::min) else {
+            return;
+        };
+        let y_max = edges.iter().map(|e| e.0.y.max(e.1.y)).fold(y_min, f32::max);
+        let y0 = (y_min.floor() as i64).max(0);
+        let y1 = (y_max.ceil() as i64).min(self.height - 1);
+        let columns = self.width * i64::from(GRID);
+        // First sample column whose center is at or right of `x`.
+        let first = |x: f32| ((x * GRID as f32 - 0.5).ceil() as i64).clamp(0, columns);
+        let (mut lo, mut hi) = ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN));
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for y in y0..=y1 {
+            for j in 0..GRID {
+                let sy = y as f32 + (j as f32 + 0.5) / GRID as f32;
+                crossings.clear();
+                for &(a, b) in &edges {
+                    if (a.y <= sy) != (b.y <= sy) {
+                        let x = a.x + (sy - a.y) / (b.y - a.y) * (b.x - a.x);
+                        crossings.push((x, if b.y > a.y { 1 } else { -1 }));
+                    }
+                }
+                crossings.sort_by(|p, q| p.0.total_cmp(&q.0));
+                let mut winding = 0;
+                for k in 0..crossings.len().saturating_sub(1) {
+                    winding += crossings[k].1;
+                    if !rule.is_filled(winding) {
+                        continue;
+                    }
+                    let (u0, u1) = (first(crossings[k].0), first(crossings[k + 1].0));
+                    for u in u0..u1 {
+                        let x = u / i64::from(GRID);
+                        let bit = j * GRID + (u % i64::from(GRID)) as u32;
+                        self.samples[(y * self.width + x) as usize] |= 1 << bit;
+                    }
+                    if u0 < u1 {
+                        lo = (lo.0.min(u0 / i64::from(GRID)), lo.1.min(y));
+                        hi = (hi.0.max((u1 - 1) / i64::from(GRID)), hi.1.max(y));
+                    }
+                }
+            }
+        }
+        self.resolve_samples(lo, hi, 1.0);
+    }
+
+    /// Turn the sample bits within `lo..=hi` into coverage and clear them.
+    fn resolve_samples(&mut self, lo: (i64, i64), hi: (i64, i64), intensity: f32) {
+        if lo.0 > hi.0 {
+            return;
+        }
+        self.dirty_min = (self.dirty_min.0.min(lo.0), self.dirty_min.1.min(lo.1));
+        self.dirty_max = (self.dirty_max.0.max(hi.0), self.dirty_max.1.max(hi.1));
+        let total = (GRID * GRID) as f32;
+        for y in lo.1..=hi.1 {
+            for x in lo.0..=hi.0 {
+                let i = (y * self.width + x) as usize;
+                let c = std::mem::take(&mut self.samples[i]).count_ones() as f32 / total;
+                self.data[i] = self.data[i].max(c * intensity);
+            }
+        }
+    }
+
+    /// Add a round-capped segment whose color and width are
+    /// interpolated from `a` to `b`.
+    ///
+    /// Each pixel takes the color at its closest point on the segment;
+    /// where segments overlap, the one with the highest coverage wins.
+    /// Thin widths are handled as in [`Self::add_segment`].
+    pub(crate) fn add_gradient_segment(&mut self, a: &StrokeVertex, b: &StrokeVertex) {
+        let widest = a.width_px.max(b.width_px);
+        if !(a.pos.is_finite() && b.pos.is_finite() && widest.is_finite()) || widest <= 0.0 {
+            return;
+        }
+        if self.colors.is_empty() {
+            self.colors = vec![Rgba::BLACK; self.data.len()];
+        }
+        let pad = widest.max(1.0) * 0.5 + 1.0;
+        let (lo, hi) = (a.pos.min(b.pos) - pad, a.pos.max(b.pos) + pad);
+        let x0 = (lo.x.floor() as i64).max(0);
+        let y0 = (lo.y.floor() as i64).max(0);
+        let x1 = (hi.x.ceil() as i64).min(self.width - 1);
+        let y1 = (hi.y.ceil() as i64).min(self.height - 1);
+        if x0 > x1 || y0 > y1 {
+            return;
+        }
+        self.dirty_min = (self.dirty_min.0.min(x0), self.dirty_min.1.min(y0));
+        self.dirty_max = (self.dirty_max.0.max(x1), self.dirty_max.1.max(y1));
+
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let t = segment_t(p, a.pos, b.pos);
+                let width = a.width_px + (b.width_px - a.width_px) * t;
+                if width <= 0.0 {
+                    continue;
+                }
+                let d = p.distance(a.pos.lerp(b.pos, t));
+                let c = (width.max(1.0) * 0.5 + 0.5 - d).clamp(0.0, 1.0) * width.min(1.0);
+                let i = (y * self.width + x) as usize;
+                if c > self.data[i] {
+                    self.data[i] = c;
+                    self.colors[i] = lerp_rgba(a.color, b.color, t);
+                }
+            }
+        }
+    }
+
+    /// Blend `color` into `fb` with `mode` using the accumulated mask,
+    /// then reset it.
+    pub(crate) fn composite(&mut self, fb: &mut Framebuffer, color: Rgba, mode: BlendMode) {
+        self.drain(|x, y, c| fb.blend_with(x, y, color, c, mode));
+    }
+
+    /// Blend the per-pixel colors recorded by
+    /// [`Self::add_gradient_segment`] into `fb`, then reset the mask.
+    pub(crate) fn composite_gradient(&mut self, fb: &mut Framebuffer, mode: BlendMode) {
+        let colors = std::mem::take(&mut self.colors);
+        let width = self.width;
+        self.drain(|x, y, c| fb.blend_with(x, y, colors[(y * width + x) as usize], c, mode));
+        self.colors = colors;
+    }
+
+    /// Pass each covered pixel and its clipped coverage to `f`, then
+    /// reset the mask.
+    pub(crate) fn drain(&mut self, mut f: impl FnMut(i64, i64, f32)) {
+        if self.dirty_min.0 > self.dirty_max.0 {
+            return;
+        }
+        for y in self.dirty_min.1..=self.dirty_max.1 {
+            for x in self.dirty_min.0..=self.dirty_max.0 {
+                let slot = &mut self.data[(y * self.width + x) as usize];
+                if *slot > 0.0 {
+                    let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                    let c = *slot * mask(&self.clips, center);
+                    if c > 0.0 {
+                        f(x, y, c);
+                    }
+                    *slot = 0.0;
+                }
+            }
+        }
+        self.dirty_min = (i64::MAX, i64::MAX);
+        self.dirty_max = (i64::MIN, i64::MIN);
+    }
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    p.distance(a.lerp(b, segment_t(p, a, b)))
+}
+
+/// Parameter in `0..=1` of the point on segment `a`-`b` closest to `p`.
+pub(crate) fn segment_t(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq > 0.0 {
+        ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+fn lerp_rgba(a: Rgba, b: Rgba, t: f32) -> Rgba {
+    Rgba(
+        a.0 + (b.0 - a.0) * t,
+        a.1 + (b.1 - a.1) * t,
+        a.2 + (b.2 - a.2) * t,
+        a.3 + (b.3 - a.3) * t,
+    )
+}
+
+/// Return a bit per sub-pixel sample of pixel (`x`, `y`) inside `tri`.
+fn triangle_mask(x: i64, y: i64, tri: [Vec2; 3]) -> u16 {
+    let [a, b, c] = tri;
+    let area = (b - a).perp_dot(c - a);
+    if area == 0.0 {
+        return 0;
+    }
+    let mut mask = 0;
+    for j in 0..GRID {
+        for i in 0..GRID {
+            let p = Vec2::new(
+                x as f32 + (i as f32 + 0.5) / GRID as f32,
+                y as f32 + (j as f32 + 0.5) / GRID as f32,
+            );
+            let inside = [(a, b), (b, c), (c, a)]
+                .iter()
+                .all(|&(e0, e1)| (e1 - e0).perp_dot(p - e0) * area >= 0.0);
+            if inside {
+                mask |= 1 << (j * GRID + i);
+            }
+        }
+    }
+    mask
+}
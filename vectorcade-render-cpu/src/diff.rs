@@ -0,0 +1,146 @@
+//! Perceptual image comparison.
+//!
+//! Pixels are compared in YIQ space, weighting brightness differences
+//! more than hue differences, after blending each pixel over white by
+//! its alpha. This follows the metric used by `pixelmatch` so that
+//! thresholds carry over from existing web visual-regression setups.
+
+use crate::Framebuffer;
+use std::fmt;
+use vectorcade_shared::Rgba;
+
+/// Largest possible YIQ delta between two colors in 0..1 range.
+const MAX_YIQ_DELTA: f32 = 0.5416;
+
+/// Options for [`diff`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffOptions {
+    /// Per-pixel tolerance from 0.0 (exact) to 1.0 (anything matches).
+    pub threshold: f32,
+    /// Whether to produce a diff image highlighting mismatches.
+    pub emit_image: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            emit_image: false,
+        }
+    }
+}
+
+/// Result of comparing two framebuffers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffReport {
+    /// Number of pixels whose difference exceeds the threshold.
+    pub mismatched: usize,
+    /// Total number of pixels compared.
+    pub total: usize,
+    /// Largest perceptual difference found (0.0..1.0), on the same scale
+    /// as [`DiffOptions::threshold`]: a pixel mismatches when its
+    /// difference exceeds the threshold.
+    pub max_delta: f32,
+    /// Diff image: mismatches in red over a faded grayscale copy of
+    /// `expected`. Present only when requested.
+    pub image: Option<Framebuffer>,
+}
+
+impl DiffReport {
+    /// Return true if no pixel exceeded the threshold.
+    #[must_use]
+    pub fn is_match(&self) -> bool {
+        self.mismatched == 0
+    }
+}
+
+/// Error returned when images cannot be compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeMismatch {
+    /// Width and height of the expected image.
+    pub expected: (u32, u32),
+    /// Width and height of the actual image.
+    pub actual: (u32, u32),
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "image size {}x{} does not match expected {}x{}",
+            self.actual.0, self.actual.1, self.expected.0, self.expected.1
+        )
+    }
+}
+
+impl std::error::Error for SizeMismatch {}
+
+/// Compare `actual` against `expected` with a perceptual tolerance.
+///
+/// # Errors
+///
+/// Returns [`SizeMismatch`] if the images have different dimensions.
+pub fn diff(
+    expected: &Framebuffer,
+    actual: &Framebuffer,
+    options: &DiffOptions,
+) -> Result<DiffReport, SizeMismatch> {
+    let dims = |fb: &Framebuffer| (fb.width(), fb.height());
+    if dims(expected) != dims(actual) {
+        return Err(SizeMismatch {
+            expected: dims(expected),
+            actual: dims(actual),
+        });
+    }
+
+    let limit = MAX_YIQ_DELTA * options.threshold * options.threshold;
+    let mut image = options
+        .emit_image
+        .then(|| Framebuffer::new(expected.width(), expected.height()));
+    let mut mismatched = 0;
+    let mut max_delta = 0.0_f32;
+    for (i, (e, a)) in expected.pixels().iter().zip(actual.pixels()).enumerate() {
+        let delta = yiq_delta(*e, *a);
+        max_delta = max_delta.max((delta / MAX_YIQ_DELTA).sqrt());
+        let bad = delta > limit;
+        mismatched += usize::from(bad);
+        if let Some(img) = image.as_mut() {
+            img.pixels_mut()[i] = if bad { Rgba::RED } else { faded(*e) };
+        }
+    }
+
+    Ok(DiffReport {
+        mismatched,
+        total: expected.pixels().len(),
+        max_delta: max_delta.min(1.0),
+        image,
+    })
+}
+
+fn over_white(c: Rgba) -> (f32, f32, f32) {
+    let a = c.3.clamp(0.0, 1.0);
+    let blend = |v: f32| 1.0 + (v - 1.0) * a;
+    (blend(c.0), blend(c.1), blend(c.2))
+}
+
+fn yiq(c: Rgba) -> (f32, f32, f32) {
+    let (r, g, b) = over_white(c);
+    (
+        r * 0.298_895_3 + g * 0.586_622_5 + b * 0.114_482_23,
+        r * 0.595_977_97 - g * 0.274_176_1 - b * 0.321_801_9,
+        r * 0.211_470_17 - g * 0.522_617_1 + b * 0.311_146_94,
+    )
+}
+
+fn yiq_delta(a: Rgba, b: Rgba) -> f32 {
+    let (y1, i1, q1) = yiq(a);
+    let (y2, i2, q2) = yiq(b);
+    let (dy, di, dq) = (y1 - y2, i1 - i2, q1 - q2);
+    0.5053 * dy * dy + 0.299 * di * di + 0.1957 * dq * dq
+}
+
+fn faded(c: Rgba) -> Rgba {
+    let y = yiq(c).0;
+    let v = 1.0 + (y - 1.0) * 0.1;
+    Rgba(v, v, v, 1.0)
+}
@@ -0,0 +1,137 @@
+//! In-memory RGBA framebuffer.
+
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::BlendMode;
+use vectorcade_shared::game::ScreenInfo;
+
+/// Floating-point RGBA image, row-major with the origin at the top-left.
+///
+/// Pixels are stored unpremultiplied in linear 0.0..1.0 range so that
+/// blending and post effects don't lose precision before export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgba>,
+}
+
+impl Framebuffer {
+    /// Create a framebuffer filled with transparent black.
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Rgba(0.0, 0.0, 0.0, 0.0); width as usize * height as usize],
+        }
+    }
+
+    /// Create a framebuffer matching a display surface.
+    #[must_use]
+    pub fn from_screen(screen: ScreenInfo) -> Self {
+        Self::new(screen.width_px, screen.height_px)
+    }
+
+    /// Width in pixels.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Screen info describing this framebuffer (for NDC mapping).
+    #[must_use]
+    pub fn screen(&self) -> ScreenInfo {
+        ScreenInfo {
+            width_px: self.width,
+            height_px: self.height,
+            dpi_scale: 1.0,
+        }
+    }
+
+    /// Return the pixel at `(x, y)`, or `None` if out of bounds.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgba> {
+        self.index(x as i64, y as i64).map(|i| self.pixels[i])
+    }
+
+    /// All pixels in row-major order.
+    #[must_use]
+    pub fn pixels(&self) -> &[Rgba] {
+        &self.pixels
+    }
+
+    /// Mutable access to all pixels in row-major order.
+    pub fn pixels_mut(&mut self) -> &mut [Rgba] {
+        &mut self.pixels
+    }
+
+    /// Fill every pixel with `color`.
+    pub fn clear(&mut self, color: Rgba) {
+        self.pixels.fill(color);
+    }
+
+    /// Composite `color` over the pixel at `(x, y)` with `coverage`.
+    ///
+    /// Out-of-bounds coordinates are ignored.
+    pub fn blend(&mut self, x: i64, y: i64, color: Rgba, coverage: f32) {
+        self.blend_with(x, y, color, coverage, BlendMode::Alpha);
+    }
+
+    /// Combine `color` with the pixel at `(x, y)` using `mode`, weighted
+    /// by the color's alpha and `coverage`.
+    ///
+    /// Additive results saturate at 1.0. Out-of-bounds coordinates are
+    /// ignored.
+    pub fn blend_with(&mut self, x: i64, y: i64, color: Rgba, coverage: f32, mode: BlendMode) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        let a = (color.3 * coverage).clamp(0.0, 1.0);
+        let dst = self.pixels[i];
+        self.pixels[i] = match mode {
+            BlendMode::Alpha => {
+                let inv = 1.0 - a;
+                Rgba(
+                    color.0 * a + dst.0 * inv,
+                    color.1 * a + dst.1 * inv,
+                    color.2 * a + dst.2 * inv,
+                    a + dst.3 * inv,
+                )
+            }
+            BlendMode::Additive => Rgba(
+                (dst.0 + color.0 * a).min(1.0),
+                (dst.1 + color.1 * a).min(1.0),
+                (dst.2 + color.2 * a).min(1.0),
+                (dst.3 + a).min(1.0),
+            ),
+            BlendMode::Max => Rgba(
+                dst.0.max(color.0 * a),
+                dst.1.max(color.1 * a),
+                dst.2.max(color.2 * a),
+                dst.3.max(a),
+            ),
+        };
+    }
+
+    /// Convert to 8-bit RGBA bytes, row-major.
+    #[must_use]
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let q = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+        self.pixels
+            .iter()
+            .flat_map(|p| [q(p.0), q(p.1), q(p.2), q(p.3)])
+            .collect()
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let in_x = (0..i64::from(self.width)).contains(&x);
+        let in_y = (0..i64::from(self.height)).contains(&y);
+        (in_x && in_y).then(|| y as usize * self.width as usize + x as usize)
+    }
+}
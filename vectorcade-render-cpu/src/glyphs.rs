@@ -0,0 +1,52 @@
+//! Glyph path layout shared by the rasterizer and exporters.
+
+use glam::Vec2;
+use vectorcade_shared::font::{GlyphPathCmd, VectorFont};
+
+/// Glyph stroke width as a fraction of the text size.
+pub(crate) const TEXT_STROKE_RATIO: f32 = 1.0 / 16.0;
+
+/// Lay out `text` with its baseline origin at `origin` (pixel space).
+///
+/// Returns one pixel-space polyline per glyph sub-path; `Close` repeats
+/// the sub-path's first point. Font units are Y-up, pixels are Y-down.
+pub(crate) fn text_polylines(
+    font: &dyn VectorFont,
+    origin: Vec2,
+    text: &str,
+    size_px: f32,
+) -> Vec<Vec<Vec2>> {
+    let mut out = Vec::new();
+    let mut pen = origin;
+    for ch in text.chars() {
+        let to_px = |p: Vec2| pen + Vec2::new(p.x, -p.y) * size_px;
+        for path in font.glyph_paths(ch) {
+            let mut current: Vec<Vec2> = Vec::new();
+            for cmd in &path.cmds {
+                match cmd {
+                    GlyphPathCmd::MoveTo(p) => {
+                        flush(&mut out, &mut current);
+                        current.push(to_px(*p));
+                    }
+                    GlyphPathCmd::LineTo(p) => current.push(to_px(*p)),
+                    GlyphPathCmd::Close => {
+                        if let Some(&first) = current.first() {
+                            current.push(first);
+                        }
+                    }
+                }
+            }
+            flush(&mut out, &mut current);
+        }
+        pen.x += font.advance(ch) * size_px;
+    }
+    out
+}
+
+fn flush(out: &mut Vec<Vec<Vec2>>, current: &mut Vec<Vec2>) {
+    if current.len() > 1 {
+        out.push(std::mem::take(current));
+    } else {
+        current.clear();
+    }
+}
@@ -0,0 +1,34 @@
+//! VectorCade reference CPU renderer.
+//!
+//! A pure-Rust, headless rasterizer for `DrawCmd` display lists. It
+//! needs no GPU or window, so it runs on CI and serves as the ground
+//! truth other render backends are compared against:
+//!
+//! - [`framebuffer`] - Floating-point RGBA image
+//! - [`raster`] - Display-list rasterizer with antialiased strokes
+//! - [`diff`](mod@diff) - Perceptual image comparison for visual regression tests
+//! - [`svg`] - SVG export of a display list
+//! - [`phosphor`] - Vector-monitor beam, bloom and persistence emulation
+//!
+//! Enable the `png` feature to save and load framebuffers as PNG files.
+
+mod bloom;
+mod clip;
+mod coverage;
+pub mod diff;
+pub mod framebuffer;
+mod glyphs;
+pub mod phosphor;
+#[cfg(feature = "png")]
+mod png;
+mod prims;
+pub mod raster;
+pub mod svg;
+
+pub use diff::{DiffOptions, DiffReport, SizeMismatch, diff};
+pub use framebuffer::Framebuffer;
+pub use phosphor::{PhosphorConfig, PhosphorScreen};
+#[cfg(feature = "png")]
+pub use png::PngError;
+pub use raster::{Rasterizer, rasterize};
+pub use svg::{SvgOptions, to_svg};
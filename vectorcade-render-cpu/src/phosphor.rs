@@ -0,0 +1,222 @@
+//! Vector-monitor phosphor emulation.
+//!
+//! Models three characteristics of an XY vector display:
+//!
+//! - **Beam intensity.** The beam deposits energy along each segment.
+//!   Long segments are swept faster and so appear dimmer, and the beam
+//!   dwells at every vertex, leaving a bright spot where strokes start,
+//!   end and turn.
+//! - **Bloom.** Energy from strokes with `Stroke::glow > 0` is also
+//!   deposited, weighted by `glow`, into a glow buffer that is blurred
+//!   with a Gaussian kernel and added on top.
+//! - **Persistence.** Both buffers carry over between frames and decay
+//!   exponentially with a configurable half-life.
+//!
+//! `Clear` sets the background the phosphor is composited over; it does
+//! not erase persisted energy.
+//!
+//! Dash patterns are applied, but caps and joins are always round: the
+//! beam has no corners. Gradient polylines deposit energy and width
+//! interpolated along each segment. Filled polygons light the phosphor
+//! evenly, like a raster overlay, with no dwell or bloom.
+//!
+//! Clip regions blank the beam where a stroke leaves them, so clipped
+//! strokes end (with a dwell spot) at the region's edge and their width
+//! may spill past it by half a beam. Fills are masked per pixel.
+//!
+//! Energy always accumulates, so every blend mode draws as
+//! `BlendMode::Additive`: that is how the phosphor itself behaves.
+
+use crate::Framebuffer;
+use crate::bloom::gaussian_blur;
+use crate::coverage::{Coverage, segment_t};
+use crate::prims::{Prim, collect};
+use glam::{Vec2, Vec3};
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{DrawCmd, Stroke, StrokeVertex};
+use vectorcade_shared::font::VectorFont;
+use vectorcade_shared::game::ScreenInfo;
+
+/// Tuning parameters for [`PhosphorScreen`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhosphorConfig {
+    /// Segment length in pixels at which beam intensity halves.
+    pub beam_falloff_px: f32,
+    /// Peak extra brightness of the dwell spot at each vertex.
+    pub dwell: f32,
+    /// Bloom blur radius in pixels.
+    pub bloom_radius_px: f32,
+    /// Multiplier applied to the blurred glow buffer.
+    pub bloom_strength: f32,
+    /// Time for persisted brightness to decay by half (0 = none).
+    pub half_life_s: f32,
+}
+
+impl Default for PhosphorConfig {
+    fn default() -> Self {
+        Self {
+            beam_falloff_px: 1000.0,
+            dwell: 0.5,
+            bloom_radius_px: 6.0,
+            bloom_strength: 1.0,
+            half_life_s: 0.05,
+        }
+    }
+}
+
+/// A simulated vector monitor with persistent phosphor.
+pub struct PhosphorScreen {
+    width: u32,
+    height: u32,
+    config: PhosphorConfig,
+    beam: Vec<Vec3>,
+    glow: Vec<Vec3>,
+}
+
+impl PhosphorScreen {
+    /// Create a dark screen matching `screen`.
+    #[must_use]
+    pub fn new(screen: ScreenInfo, config: PhosphorConfig) -> Self {
+        let len = screen.width_px as usize * screen.height_px as usize;
+        Self {
+            width: screen.width_px,
+            height: screen.height_px,
+            config,
+            beam: vec![Vec3::ZERO; len],
+            glow: vec![Vec3::ZERO; len],
+        }
+    }
+
+    /// Return the tuning parameters.
+    #[must_use]
+    pub fn config(&self) -> PhosphorConfig {
+        self.config
+    }
+
+    /// Discard all persisted brightness.
+    pub fn reset(&mut self) {
+        self.beam.fill(Vec3::ZERO);
+        self.glow.fill(Vec3::ZERO);
+    }
+
+    /// Decay the phosphor by `dt_s`, draw `cmds`, and return the image.
+    pub fn render(
+        &mut self,
+        cmds: &[DrawCmd],
+        font: Option<&dyn VectorFont>,
+        dt_s: f32,
+    ) -> Framebuffer {
+        self.decay(dt_s);
+        let mut fb = Framebuffer::new(self.width, self.height);
+        let mut background = Rgba::BLACK;
+        let mut cov = Coverage::new(self.width, self.height);
+        for prim in collect(cmds, fb.screen(), font) {
+            match prim {
+                Prim::Clear(color) => background = color,
+                Prim::Blend(_) => {}
+                Prim::Clip(clips) => cov.set_clip(clips),
+                Prim::Stroke { paths, stroke } => {
+                    for path in &paths {
+                        self.deposit_path(path, &stroke);
+                    }
+                }
+                Prim::Gradient { verts, glow } => self.deposit_gradient(&verts, glow),
+                Prim::Fill {
+                    contours,
+                    rule,
+                    color,
+                } => {
+                    cov.add_fill(&contours, rule);
+                    let energy = Vec3::new(color.0, color.1, color.2) * color.3;
+                    let width = i64::from(self.width);
+                    cov.drain(|x, y, c| self.beam[(y * width + x) as usize] += energy * c);
+                }
+            }
+        }
+
+        let radius = self.config.bloom_radius_px;
+        let bloom = gaussian_blur(&self.glow, self.width, self.height, radius);
+        let bg = Vec3::new(background.0, background.1, background.2);
+        for ((dst, beam), glow) in fb.pixels_mut().iter_mut().zip(&self.beam).zip(&bloom) {
+            let c = (bg + *beam + *glow * self.config.bloom_strength).min(Vec3::ONE);
+            *dst = Rgba(c.x, c.y, c.z, 1.0);
+        }
+        fb
+    }
+
+    fn decay(&mut self, dt_s: f32) {
+        let keep = if self.config.half_life_s > 0.0 {
+            0.5_f32.powf(dt_s.max(0.0) / self.config.half_life_s)
+        } else {
+            0.0
+        };
+        for v in self.beam.iter_mut().chain(self.glow.iter_mut()) {
+            *v *= keep;
+        }
+    }
+
+    fn deposit_path(&mut self, path: &[Vec2], stroke: &Stroke) {
+        let color = Vec3::new(stroke.color.0, stroke.color.1, stroke.color.2) * stroke.color.3;
+        let glow = stroke.glow.clamp(0.0, 1.0);
+        for w in path.windows(2) {
+            let energy = color * self.beam_intensity(w[0], w[1]);
+            let width = [stroke.width_px; 2];
+            self.deposit_segment(w[0], w[1], width, [energy; 2], glow);
+        }
+    }
+
+    fn deposit_gradient(&mut self, verts: &[StrokeVertex], glow: f32) {
+        let energy = |v: &StrokeVertex| Vec3::new(v.color.0, v.color.1, v.color.2) * v.color.3;
+        let glow = glow.clamp(0.0, 1.0);
+        for w in verts.windows(2) {
+            let (a, b) = (&w[0], &w[1]);
+            let intensity = self.beam_intensity(a.pos, b.pos);
+            let e = [energy(a) * intensity, energy(b) * intensity];
+            self.deposit_segment(a.pos, b.pos, [a.width_px, b.width_px], e, glow);
+        }
+    }
+
+    /// Beam brightness for a segment; longer sweeps are faster and dimmer.
+    fn beam_intensity(&self, a: Vec2, b: Vec2) -> f32 {
+        1.0 / (1.0 + a.distance(b) / self.config.beam_falloff_px.max(1e-3))
+    }
+
+    /// Deposit a segment whose width and energy are interpolated from
+    /// the first to the second element of each pair.
+    fn deposit_segment(
+        &mut self,
+        a: Vec2,
+        b: Vec2,
+        width_px: [f32; 2],
+        energy: [Vec3; 2],
+        glow: f32,
+    ) {
+        let widest = width_px[0].max(width_px[1]);
+        if !(a.is_finite() && b.is_finite()) || widest.is_nan() || widest <= 0.0 {
+            return;
+        }
+        let half = widest.max(1.0) * 0.5;
+        let spot = half.max(1.0);
+        let pad = half + spot * 2.0;
+        let (w, h) = (i64::from(self.width), i64::from(self.height));
+        let x0 = ((a.x.min(b.x) - pad).floor() as i64).max(0);
+        let y0 = ((a.y.min(b.y) - pad).floor() as i64).max(0);
+        let x1 = ((a.x.max(b.x) + pad).ceil() as i64).min(w - 1);
+        let y1 = ((a.y.max(b.y) + pad).ceil() as i64).min(h - 1);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let t = segment_t(p, a, b);
+                let width = width_px[0] + (width_px[1] - width_px[0]) * t;
+                let core = (width.max(1.0) * 0.5 + 0.5 - p.distance(a.lerp(b, t))).clamp(0.0, 1.0);
+                let end_d = p.distance(a).min(p.distance(b));
+                let dwell = self.config.dwell * (-(end_d * end_d) / (2.0 * spot * spot)).exp();
+                let energy = energy[0].lerp(energy[1], t);
+                let e = energy * (core * width.clamp(0.0, 1.0) + dwell);
+                let i = (y * w + x) as usize;
+                self.beam[i] += e;
+                self.glow[i] += e * glow;
+            }
+        }
+    }
+}
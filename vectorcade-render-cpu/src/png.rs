@@ -0,0 +1,109 @@
+//! PNG encoding and decoding of framebuffers (`png` feature).
+
+use crate::Framebuffer;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Seek, Write};
+use std::path::Path;
+use vectorcade_shared::Rgba;
+
+/// Error returned by PNG export and import.
+#[derive(Debug)]
+pub enum PngError {
+    /// Opening or creating the file failed.
+    Io(std::io::Error),
+    /// The encoder reported an error.
+    Encode(png::EncodingError),
+    /// The decoder reported an error.
+    Decode(png::DecodingError),
+    /// The image uses a color layout that cannot be converted to RGBA.
+    UnsupportedFormat,
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "PNG I/O error: {e}"),
+            Self::Encode(e) => write!(f, "PNG encoding failed: {e}"),
+            Self::Decode(e) => write!(f, "PNG decoding failed: {e}"),
+            Self::UnsupportedFormat => write!(f, "unsupported PNG color format"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+impl Framebuffer {
+    /// Encode as an 8-bit RGBA PNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PngError::Encode`] if writing fails.
+    pub fn write_png<W: Write>(&self, w: W) -> Result<(), PngError> {
+        let mut encoder = png::Encoder::new(w, self.width(), self.height());
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(PngError::Encode)?;
+        writer
+            .write_image_data(&self.to_rgba8())
+            .map_err(PngError::Encode)?;
+        writer.finish().map_err(PngError::Encode)
+    }
+
+    /// Decode a PNG into a framebuffer.
+    ///
+    /// Grayscale, RGB, palette and 16-bit images are converted to RGBA.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PngError::Decode`] for malformed data.
+    pub fn read_png<R: BufRead + Seek>(r: R) -> Result<Self, PngError> {
+        let mut decoder = png::Decoder::new(r);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(PngError::Decode)?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or(PngError::UnsupportedFormat)?;
+        let mut buf = vec![0; size];
+        let info = reader.next_frame(&mut buf).map_err(PngError::Decode)?;
+        let channels = info.color_type.samples();
+        let mut fb = Framebuffer::new(info.width, info.height);
+        let rows = buf.chunks(info.line_size).take(info.height as usize);
+        let src = rows.flat_map(|row| row[..info.width as usize * channels].chunks(channels));
+        for (dst, px) in fb.pixels_mut().iter_mut().zip(src) {
+            *dst = expand(px).ok_or(PngError::UnsupportedFormat)?;
+        }
+        Ok(fb)
+    }
+
+    /// Write to a PNG file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or encoded.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), PngError> {
+        let file = File::create(path).map_err(PngError::Io)?;
+        self.write_png(BufWriter::new(file))
+    }
+
+    /// Read a PNG file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or decoded.
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, PngError> {
+        let file = File::open(path).map_err(PngError::Io)?;
+        Self::read_png(BufReader::new(file))
+    }
+}
+
+fn expand(px: &[u8]) -> Option<Rgba> {
+    let f = |v: u8| f32::from(v) / 255.0;
+    match *px {
+        [g] => Some(Rgba(f(g), f(g), f(g), 1.0)),
+        [g, a] => Some(Rgba(f(g), f(g), f(g), f(a))),
+        [r, g, b] => Some(Rgba(f(r), f(g), f(b), 1.0)),
+        [r, g, b, a] => Some(Rgba(f(r), f(g), f(b), f(a))),
+        _ => None,
+    }
+}
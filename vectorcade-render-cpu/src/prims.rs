@@ -0,0 +1,188 @@
+//! Display-list walk into pixel-space primitives.
+
+use crate::clip::{ClipShape, clip_path};
+use crate::glyphs::{TEXT_STROKE_RATIO, text_polylines};
+use glam::{Mat3, Vec2};
+use vectorcade_shared::draw::{BlendMode, DrawCmd, Stroke, StrokeVertex, dash_path, tessellate};
+use vectorcade_shared::font::VectorFont;
+use vectorcade_shared::game::{ScreenInfo, ndc_to_px};
+use vectorcade_shared::{FillRule, Rgba};
+
+/// A drawable primitive with geometry already in framebuffer pixels.
+pub(crate) enum Prim {
+    /// Fill the whole target.
+    Clear(Rgba),
+    /// One or more polylines sharing a stroke. A path whose last point
+    /// repeats its first is closed. Dashes and clips are already applied,
+    /// so `stroke.dash` is always `None`.
+    Stroke {
+        paths: Vec<Vec<Vec2>>,
+        stroke: Stroke,
+    },
+    /// A polyline with per-vertex color and width, already clipped. A
+    /// closed path repeats its first vertex.
+    Gradient { verts: Vec<StrokeVertex>, glow: f32 },
+    /// Switch the blend mode for the primitives that follow.
+    Blend(BlendMode),
+    /// Replace the clip regions for the primitives that follow; each
+    /// primitive is masked to their intersection.
+    Clip(Vec<ClipShape>),
+    /// Filled contours; each is implicitly closed.
+    Fill {
+        contours: Vec<Vec<Vec2>>,
+        rule: FillRule,
+        color: Rgba,
+    },
+}
+
+/// Walk `cmds`, composing the transform stack, and emit primitives.
+///
+/// Each `PushTransform` composes with its parent, blend pushes and pops
+/// become `Prim::Blend` switches, clip pushes and pops become `Prim::Clip`
+/// switches, and unbalanced pops are ignored. Closed polylines (plain or
+/// gradient) repeat their first point, dashed strokes are split into
+/// their dashes, and stroked paths are cut to the active clips. Curves are
+/// tessellated with [`tessellate`]. Transforms move text origins; glyphs
+/// are laid out in screen pixels from there.
+pub(crate) fn collect(
+    cmds: &[DrawCmd],
+    screen: ScreenInfo,
+    font: Option<&dyn VectorFont>,
+) -> Vec<Prim> {
+    let mut out = Vec::new();
+    let mut stack = vec![Mat3::IDENTITY];
+    let mut blends = vec![BlendMode::Alpha];
+    let mut clips: Vec<ClipShape> = Vec::new();
+    for cmd in cmds {
+        let m = *stack.last().unwrap_or(&Mat3::IDENTITY);
+        let to_px = |p: Vec2| ndc_to_px(m.transform_point2(p), screen);
+        let curve = tessellate(cmd, m, screen);
+        match curve.as_ref().unwrap_or(cmd) {
+            DrawCmd::Clear { color } => out.push(Prim::Clear(*color)),
+            DrawCmd::Line(l) => {
+                let path = [to_px(l.a), to_px(l.b)];
+                out.extend(styled(&path, false, l.stroke, &clips));
+            }
+            DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke,
+            } => {
+                let path: Vec<Vec2> = pts.iter().map(|&p| to_px(p)).collect();
+                out.extend(styled(&path, *closed, *stroke, &clips));
+            }
+            DrawCmd::GradientPolyline {
+                verts,
+                closed,
+                glow,
+            } => {
+                let mut verts: Vec<StrokeVertex> = verts
+                    .iter()
+                    .map(|v| StrokeVertex {
+                        pos: to_px(v.pos),
+                        ..*v
+                    })
+                    .collect();
+                if *closed && verts.len() > 2 {
+                    verts.push(verts[0]);
+                }
+                let lerp = |a: &StrokeVertex, b: &StrokeVertex, t: f32| StrokeVertex {
+                    pos: a.pos.lerp(b.pos, t),
+                    color: a.color.lerp(b.color, t),
+                    width_px: a.width_px + (b.width_px - a.width_px) * t,
+                };
+                for verts in clip_path(&verts, &clips, |v| v.pos, lerp) {
+                    out.push(Prim::Gradient { verts, glow: *glow });
+                }
+            }
+            DrawCmd::FillPolygon {
+                contours,
+                rule,
+                color,
+            } => out.push(Prim::Fill {
+                contours: contours
+                    .iter()
+                    .map(|c| c.iter().map(|&p| to_px(p)).collect())
+                    .collect(),
+                rule: *rule,
+                color: *color,
+            }),
+            DrawCmd::Text {
+                pos,
+                text,
+                size_px,
+                color,
+                ..
+            } => {
+                if let Some(font) = font {
+                    let paths = text_polylines(font, to_px(*pos), text, *size_px);
+                    let stroke = Stroke::new(*color, (size_px * TEXT_STROKE_RATIO).max(1.0));
+                    out.extend(clipped(paths, stroke, &clips));
+                }
+            }
+            DrawCmd::PushTransform(t) => stack.push(m * *t),
+            DrawCmd::PopTransform => {
+                if stack.len() > 1 {
+                    stack.pop();
+                }
+            }
+            DrawCmd::PushBlend(mode) => {
+                blends.push(*mode);
+                out.push(Prim::Blend(*mode));
+            }
+            DrawCmd::PopBlend => {
+                if blends.len() > 1 {
+                    blends.pop();
+                    out.push(Prim::Blend(blends[blends.len() - 1]));
+                }
+            }
+            DrawCmd::PushClipRect(r) => {
+                clips.push(ClipShape::rect(r, screen));
+                out.push(Prim::Clip(clips.clone()));
+            }
+            DrawCmd::PushClipCircle(c) => {
+                clips.push(ClipShape::circle(c, screen));
+                out.push(Prim::Clip(clips.clone()));
+            }
+            DrawCmd::PopClip => {
+                if clips.pop().is_some() {
+                    out.push(Prim::Clip(clips.clone()));
+                }
+            }
+            DrawCmd::Circle { .. }
+            | DrawCmd::Arc { .. }
+            | DrawCmd::Ellipse { .. }
+            | DrawCmd::BeginLayer(_)
+            | DrawCmd::EndLayer => {}
+        }
+    }
+    out
+}
+
+/// Build a stroke primitive from a pixel-space path, applying dashes
+/// and clips. Returns `None` if nothing is left inside the clips.
+fn styled(path: &[Vec2], closed: bool, mut stroke: Stroke, clips: &[ClipShape]) -> Option<Prim> {
+    let paths = match stroke.dash.take() {
+        Some(dash) => dash_path(path, closed, &dash),
+        None => {
+            let mut path = path.to_vec();
+            if closed && path.len() > 2 {
+                path.push(path[0]);
+            }
+            vec![path]
+        }
+    };
+    clipped(paths, stroke, clips)
+}
+
+/// Cut stroke paths to `clips`, or return `None` if nothing is left.
+fn clipped(paths: Vec<Vec<Vec2>>, stroke: Stroke, clips: &[ClipShape]) -> Option<Prim> {
+    if clips.is_empty() {
+        return Some(Prim::Stroke { paths, stroke });
+    }
+    let paths: Vec<Vec<Vec2>> = paths
+        .iter()
+        .flat_map(|p| clip_path(p, clips, |&p| p, |a, b, t| a.lerp(*b, t)))
+        .collect();
+    (!paths.is_empty()).then_some(Prim::Stroke { paths, stroke })
+}
@@ -0,0 +1,128 @@
+//! Display-list rasterizer.
+
+use crate::Framebuffer;
+use crate::coverage::Coverage;
+use crate::prims::{Prim, collect};
+use glam::Vec2;
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{
+    BlendMode, DrawCmd, LineCap, LineJoin, Stroke, StrokeMesh, expand_stroke,
+};
+use vectorcade_shared::font::VectorFont;
+use vectorcade_shared::game::ScreenInfo;
+
+/// Reference CPU renderer for `DrawCmd` display lists.
+///
+/// Walks the list in order, maintaining a transform stack where each
+/// `PushTransform` composes with its parent. Geometry is in NDC (Y-up)
+/// and mapped to framebuffer pixels with
+/// [`ndc_to_px`](vectorcade_shared::game::ndc_to_px). Text is drawn
+/// through the supplied [`VectorFont`], or skipped if none is set.
+/// Transforms move the text origin; glyphs are laid out in screen
+/// pixels from there.
+///
+/// Round caps and joins are drawn as antialiased capsules; other styles
+/// fill the triangles from [`expand_stroke`]. Gradient polylines blend
+/// each pixel with the color interpolated at its closest point on the
+/// path. Filled polygons are scan-converted under their fill rule with
+/// antialiased edges.
+///
+/// Clip regions cut stroked paths at their edge and mask every primitive
+/// per pixel, so stroke width, caps and fills stop at the edge too.
+///
+/// Each primitive is blended into the framebuffer once, with the
+/// [`BlendMode`] in effect. `Clear` is not blended and glow is not drawn.
+#[derive(Default)]
+pub struct Rasterizer<'a> {
+    font: Option<&'a dyn VectorFont>,
+}
+
+impl<'a> Rasterizer<'a> {
+    /// Create a rasterizer with no font.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { font: None }
+    }
+
+    /// Use `font` for `DrawCmd::Text`.
+    #[must_use]
+    pub fn with_font(mut self, font: &'a dyn VectorFont) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Draw `cmds` into `fb`.
+    pub fn render(&self, cmds: &[DrawCmd], fb: &mut Framebuffer) {
+        let mut cov = Coverage::new(fb.width(), fb.height());
+        let mut mesh = StrokeMesh::new();
+        let mut mode = BlendMode::Alpha;
+        for prim in collect(cmds, fb.screen(), self.font) {
+            match prim {
+                Prim::Clear(color) => fb.clear(color),
+                Prim::Blend(m) => mode = m,
+                Prim::Clip(clips) => cov.set_clip(clips),
+                Prim::Stroke { paths, stroke } => {
+                    if stroke.cap == LineCap::Round && stroke.join == LineJoin::Round {
+                        for path in &paths {
+                            for w in path.windows(2) {
+                                cov.add_segment(w[0], w[1], stroke.width_px);
+                            }
+                        }
+                    } else {
+                        add_styled(&mut cov, &mut mesh, &paths, &stroke);
+                    }
+                    cov.composite(fb, stroke.color, mode);
+                }
+                Prim::Gradient { verts, .. } => {
+                    for w in verts.windows(2) {
+                        cov.add_gradient_segment(&w[0], &w[1]);
+                    }
+                    cov.composite_gradient(fb, mode);
+                }
+                Prim::Fill {
+                    contours,
+                    rule,
+                    color,
+                } => {
+                    cov.add_fill(&contours, rule);
+                    cov.composite(fb, color, mode);
+                }
+            }
+        }
+    }
+}
+
+/// Rasterize `cmds` into a new framebuffer sized from `screen`.
+///
+/// The framebuffer starts out opaque black.
+#[must_use]
+pub fn rasterize(
+    cmds: &[DrawCmd],
+    screen: ScreenInfo,
+    font: Option<&dyn VectorFont>,
+) -> Framebuffer {
+    let mut fb = Framebuffer::from_screen(screen);
+    fb.clear(Rgba::BLACK);
+    Rasterizer { font }.render(cmds, &mut fb);
+    fb
+}
+
+/// Add paths with non-round caps or joins via their triangle mesh.
+///
+/// Like capsules, strokes thinner than a pixel are drawn one pixel wide
+/// and dimmed.
+fn add_styled(cov: &mut Coverage, mesh: &mut StrokeMesh, paths: &[Vec<Vec2>], stroke: &Stroke) {
+    if stroke.width_px.is_nan() || stroke.width_px <= 0.0 {
+        return;
+    }
+    let wide = Stroke {
+        width_px: stroke.width_px.max(1.0),
+        ..*stroke
+    };
+    mesh.clear();
+    for path in paths {
+        let ring = path.len() > 2 && path.first() == path.last();
+        expand_stroke(path, ring, &wide, mesh);
+    }
+    cov.add_mesh(mesh, stroke.width_px.min(1.0));
+}
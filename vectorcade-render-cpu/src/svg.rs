@@ -0,0 +1,460 @@
+//! SVG export of a display list.
+//!
+//! Transforms are flattened: every point is mapped through the composed
+//! transform stack and [`ndc_to_px`], so the output uses plain pixel
+//! coordinates in a `viewBox` sized from [`ScreenInfo`] (origin top-left,
+//! Y-down) and stroke widths stay in screen pixels as `Stroke::width_px`
+//! intends. Curves are tessellated like in the rasterizer, so both
+//! outputs agree. Layers become `<g>` groups whose `id` is the layer
+//! name.
+//!
+//! SVG strokes have a single width, so a `GradientPolyline` becomes one
+//! `<line>` per segment, painted with a `<linearGradient>` between its
+//! end colors and drawn at the average of its end widths.
+//!
+//! Blend modes map to CSS `mix-blend-mode` on each element:
+//! `plus-lighter` for additive and `lighten` for max.
+//!
+//! Clip regions become `<clipPath>` definitions in pixel coordinates (a
+//! circle turns into an ellipse on a non-square screen). A nested clip's
+//! path is itself clipped by its parent, and each element drawn inside
+//! refers to the innermost one with a `clip-path` attribute.
+
+use crate::glyphs::{TEXT_STROKE_RATIO, text_polylines};
+use glam::{Mat3, Vec2};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use vectorcade_shared::draw::{
+    BlendMode, DrawCmd, LineCap, LineJoin, MIN_DASH_PX, Stroke, StrokeVertex, tessellate,
+};
+use vectorcade_shared::font::VectorFont;
+use vectorcade_shared::game::{ScreenInfo, ndc_to_px};
+use vectorcade_shared::{FillRule, Rgba};
+
+/// Number of distinct glow filter strengths emitted.
+const GLOW_LEVELS: f32 = 10.0;
+/// Blur radius in pixels at full glow.
+const GLOW_MAX_BLUR_PX: f32 = 4.0;
+
+/// Options for [`to_svg`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SvgOptions {
+    /// Approximate `Stroke::glow` with a Gaussian blur filter.
+    pub glow: bool,
+}
+
+/// Render a display list as a standalone SVG document.
+///
+/// Text is drawn as stroked glyph paths through `font` when one is
+/// given, otherwise as an SVG `<text>` element in a monospace font.
+#[must_use]
+pub fn to_svg(
+    cmds: &[DrawCmd],
+    screen: ScreenInfo,
+    font: Option<&dyn VectorFont>,
+    options: SvgOptions,
+) -> String {
+    let mut w = SvgWriter {
+        screen,
+        font,
+        options,
+        body: String::new(),
+        glow_levels: BTreeSet::new(),
+        gradients: String::new(),
+        gradient_count: 0,
+        layer_ids: HashSet::new(),
+        open_layers: 0,
+        blends: Vec::new(),
+        clip_paths: String::new(),
+        clip_count: 0,
+        clips: Vec::new(),
+    };
+    let mut stack = vec![Mat3::IDENTITY];
+    for cmd in cmds {
+        let m = *stack.last().unwrap_or(&Mat3::IDENTITY);
+        match cmd {
+            DrawCmd::PushTransform(t) => stack.push(m * *t),
+            DrawCmd::PopTransform => {
+                if stack.len() > 1 {
+                    stack.pop();
+                }
+            }
+            _ => match tessellate(cmd, m, screen) {
+                Some(poly) => w.element(&poly, m),
+                None => w.element(cmd, m),
+            },
+        }
+    }
+    for _ in 0..w.open_layers {
+        w.body.push_str("</g>\n");
+    }
+    w.finish()
+}
+
+struct SvgWriter<'a> {
+    screen: ScreenInfo,
+    font: Option<&'a dyn VectorFont>,
+    options: SvgOptions,
+    body: String,
+    glow_levels: BTreeSet<u32>,
+    /// `<linearGradient>` definitions for gradient polylines.
+    gradients: String,
+    gradient_count: usize,
+    layer_ids: HashSet<String>,
+    open_layers: usize,
+    blends: Vec<BlendMode>,
+    /// `<clipPath>` definitions for clip regions.
+    clip_paths: String,
+    clip_count: usize,
+    /// Numbers of the open clip paths, innermost last.
+    clips: Vec<usize>,
+}
+
+impl SvgWriter<'_> {
+    fn element(&mut self, cmd: &DrawCmd, m: Mat3) {
+        let px = |p: Vec2| ndc_to_px(m.transform_point2(p), self.screen);
+        match cmd {
+            DrawCmd::Clear { color } => {
+                let _ = writeln!(
+                    self.body,
+                    r#"<rect width="100%" height="100%" {}/>"#,
+                    paint("fill", *color)
+                );
+            }
+            DrawCmd::Line(l) => {
+                let (a, b) = (px(l.a), px(l.b));
+                let attrs = self.stroke_attrs(&l.stroke);
+                let _ = writeln!(
+                    self.body,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {attrs}/>"#,
+                    num(a.x),
+                    num(a.y),
+                    num(b.x),
+                    num(b.y)
+                );
+            }
+            DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke,
+            } => {
+                let tag = if *closed { "polygon" } else { "polyline" };
+                let points: Vec<String> = pts.iter().map(|&p| point(px(p))).collect();
+                let attrs = self.stroke_attrs(stroke);
+                let _ = writeln!(
+                    self.body,
+                    r#"<{tag} points="{}" {attrs}/>"#,
+                    points.join(" ")
+                );
+            }
+            DrawCmd::GradientPolyline {
+                verts,
+                closed,
+                glow,
+            } => {
+                let mut verts: Vec<StrokeVertex> = verts
+                    .iter()
+                    .map(|v| StrokeVertex {
+                        pos: px(v.pos),
+                        ..*v
+                    })
+                    .collect();
+                if *closed && verts.len() > 2 {
+                    verts.push(verts[0]);
+                }
+                for w in verts.windows(2) {
+                    self.gradient_segment(&w[0], &w[1], *glow);
+                }
+            }
+            DrawCmd::FillPolygon {
+                contours,
+                rule,
+                color,
+            } => {
+                let mut d = String::new();
+                for contour in contours.iter().filter(|c| !c.is_empty()) {
+                    for (i, &p) in contour.iter().enumerate() {
+                        d.push_str(if i == 0 { "M" } else { "L" });
+                        d.push_str(&point(px(p)).replace(',', " "));
+                    }
+                    d.push('Z');
+                }
+                let rule = match rule {
+                    FillRule::NonZero => "nonzero",
+                    FillRule::EvenOdd => "evenodd",
+                };
+                let _ = writeln!(
+                    self.body,
+                    r#"<path d="{d}" {} fill-rule="{rule}"{}/>"#,
+                    paint("fill", *color),
+                    self.scope_attrs()
+                );
+            }
+            DrawCmd::Text {
+                pos,
+                text,
+                size_px,
+                color,
+                ..
+            } => self.text(px(*pos), text, *size_px, *color),
+            DrawCmd::BeginLayer(layer) => {
+                let id = self.unique_id(layer.name);
+                let _ = writeln!(self.body, r#"<g id="{id}">"#);
+                self.open_layers += 1;
+            }
+            DrawCmd::EndLayer => {
+                if self.open_layers > 0 {
+                    self.body.push_str("</g>\n");
+                    self.open_layers -= 1;
+                }
+            }
+            DrawCmd::PushBlend(mode) => self.blends.push(*mode),
+            DrawCmd::PopBlend => {
+                self.blends.pop();
+            }
+            DrawCmd::PushClipRect(r) => {
+                let min = ndc_to_px(Vec2::new(r.min.x, r.max.y), self.screen);
+                let size =
+                    (ndc_to_px(Vec2::new(r.max.x, r.min.y), self.screen) - min).max(Vec2::ZERO);
+                let shape = format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#,
+                    num(min.x),
+                    num(min.y),
+                    num(size.x),
+                    num(size.y)
+                );
+                self.push_clip(&shape);
+            }
+            DrawCmd::PushClipCircle(c) => {
+                let center = ndc_to_px(c.center, self.screen);
+                let radii = Vec2::new(self.screen.width_px as f32, self.screen.height_px as f32)
+                    * 0.5
+                    * c.radius.max(0.0);
+                let shape = format!(
+                    r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}"/>"#,
+                    num(center.x),
+                    num(center.y),
+                    num(radii.x),
+                    num(radii.y)
+                );
+                self.push_clip(&shape);
+            }
+            DrawCmd::PopClip => {
+                self.clips.pop();
+            }
+            DrawCmd::Circle { .. }
+            | DrawCmd::Arc { .. }
+            | DrawCmd::Ellipse { .. }
+            | DrawCmd::PushTransform(_)
+            | DrawCmd::PopTransform => {}
+        }
+    }
+
+    fn text(&mut self, origin: Vec2, text: &str, size_px: f32, color: Rgba) {
+        let Some(font) = self.font else {
+            let _ = writeln!(
+                self.body,
+                r#"<text x="{}" y="{}" font-size="{}" font-family="monospace" {}{}>{}</text>"#,
+                num(origin.x),
+                num(origin.y),
+                num(size_px),
+                paint("fill", color),
+                self.scope_attrs(),
+                escape(text)
+            );
+            return;
+        };
+        let mut d = String::new();
+        for line in text_polylines(font, origin, text, size_px) {
+            for (i, p) in line.iter().enumerate() {
+                d.push_str(if i == 0 { "M" } else { "L" });
+                d.push_str(&point(*p).replace(',', " "));
+            }
+        }
+        let stroke = Stroke::new(color, (size_px * TEXT_STROKE_RATIO).max(1.0));
+        let attrs = self.stroke_attrs(&stroke);
+        let _ = writeln!(self.body, r#"<path d="{d}" {attrs}/>"#);
+    }
+
+    fn gradient_segment(&mut self, a: &StrokeVertex, b: &StrokeVertex, glow: f32) {
+        let id = format!("gradient-{}", self.gradient_count);
+        self.gradient_count += 1;
+        let (x1, y1, x2, y2) = (num(a.pos.x), num(a.pos.y), num(b.pos.x), num(b.pos.y));
+        let _ = writeln!(
+            self.gradients,
+            r#"<linearGradient id="{id}" gradientUnits="userSpaceOnUse" x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}">{}{}</linearGradient>"#,
+            stop(0, a.color),
+            stop(1, b.color)
+        );
+        let width = num((a.width_px + b.width_px) * 0.5);
+        let filter = self.glow_filter(glow);
+        let scope = self.scope_attrs();
+        let _ = writeln!(
+            self.body,
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" fill="none" stroke="url(#{id})" stroke-width="{width}" stroke-linecap="round"{filter}{scope}/>"#
+        );
+    }
+
+    fn stroke_attrs(&mut self, s: &Stroke) -> String {
+        let cap = match s.cap {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        };
+        let join = match s.join {
+            LineJoin::Miter => "miter",
+            LineJoin::Bevel => "bevel",
+            LineJoin::Round => "round",
+        };
+        let mut attrs = format!(
+            r#"fill="none" {} stroke-width="{}" stroke-linecap="{cap}" stroke-linejoin="{join}""#,
+            paint("stroke", s.color),
+            num(s.width_px)
+        );
+        // Sub-pixel patterns draw solid, as `dash_path` does.
+        if let Some(d) = s.dash.filter(|d| d.on_px.min(d.off_px) >= MIN_DASH_PX) {
+            let _ = write!(
+                attrs,
+                r#" stroke-dasharray="{} {}" stroke-dashoffset="{}""#,
+                num(d.on_px),
+                num(d.off_px),
+                num(d.phase_px)
+            );
+        }
+        attrs.push_str(&self.glow_filter(s.glow));
+        attrs.push_str(&self.scope_attrs());
+        attrs
+    }
+
+    /// Return the ` style` and ` clip-path` attributes for the current
+    /// blend mode and clip.
+    fn scope_attrs(&self) -> String {
+        let mut attrs = match self.blends.last() {
+            None | Some(BlendMode::Alpha) => String::new(),
+            Some(BlendMode::Additive) => r#" style="mix-blend-mode:plus-lighter""#.to_string(),
+            Some(BlendMode::Max) => r#" style="mix-blend-mode:lighten""#.to_string(),
+        };
+        if let Some(n) = self.clips.last() {
+            let _ = write!(attrs, r#" clip-path="url(#clip-{n})""#);
+        }
+        attrs
+    }
+
+    /// Define a clip path around `shape`, clipped by the current one.
+    fn push_clip(&mut self, shape: &str) {
+        let n = self.clip_count;
+        self.clip_count += 1;
+        let parent = self
+            .clips
+            .last()
+            .map(|p| format!(r#" clip-path="url(#clip-{p})""#))
+            .unwrap_or_default();
+        let _ = writeln!(
+            self.clip_paths,
+            r#"<clipPath id="clip-{n}" clipPathUnits="userSpaceOnUse"{parent}>{shape}</clipPath>"#
+        );
+        self.clips.push(n);
+    }
+
+    /// Return the ` filter` attribute for `glow`, if glow is enabled.
+    fn glow_filter(&mut self, glow: f32) -> String {
+        let level = (glow.clamp(0.0, 1.0) * GLOW_LEVELS).round() as u32;
+        if !self.options.glow || level == 0 {
+            return String::new();
+        }
+        self.glow_levels.insert(level);
+        format!(r#" filter="url(#glow-{level})""#)
+    }
+
+    fn unique_id(&mut self, name: &str) -> String {
+        let base: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let base = if base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            base
+        } else {
+            format!("layer-{base}")
+        };
+        let mut id = base.clone();
+        let mut n = 2;
+        while !self.layer_ids.insert(id.clone()) {
+            id = format!("{base}-{n}");
+            n += 1;
+        }
+        id
+    }
+
+    fn finish(self) -> String {
+        let (w, h) = (self.screen.width_px, self.screen.height_px);
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#
+        );
+        out.push('\n');
+        if !(self.glow_levels.is_empty() && self.gradients.is_empty() && self.clip_paths.is_empty())
+        {
+            out.push_str("<defs>\n");
+            out.push_str(&self.gradients);
+            out.push_str(&self.clip_paths);
+            for level in &self.glow_levels {
+                let blur = *level as f32 / GLOW_LEVELS * GLOW_MAX_BLUR_PX;
+                let _ = writeln!(
+                    out,
+                    r#"<filter id="glow-{level}" x="-50%" y="-50%" width="200%" height="200%"><feGaussianBlur stdDeviation="{}" result="blur"/><feMerge><feMergeNode in="blur"/><feMergeNode in="SourceGraphic"/></feMerge></filter>"#,
+                    num(blur)
+                );
+            }
+            out.push_str("</defs>\n");
+        }
+        out.push_str(&self.body);
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn paint(attr: &str, c: Rgba) -> String {
+    let mut s = format!(r#"{attr}="{}""#, rgb(c));
+    if c.3 < 1.0 {
+        let _ = write!(s, r#" {attr}-opacity="{}""#, num(c.3.max(0.0)));
+    }
+    s
+}
+
+fn stop(offset: u8, c: Rgba) -> String {
+    let mut s = format!(r#"<stop offset="{offset}" stop-color="{}""#, rgb(c));
+    if c.3 < 1.0 {
+        let _ = write!(s, r#" stop-opacity="{}""#, num(c.3.max(0.0)));
+    }
+    s.push_str("/>");
+    s
+}
+
+fn rgb(c: Rgba) -> String {
+    let q = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("rgb({},{},{})", q(c.0), q(c.1), q(c.2))
+}
+
+fn point(p: Vec2) -> String {
+    format!("{},{}", num(p.x), num(p.y))
+}
+
+fn num(v: f32) -> String {
+    let s = format!("{v:.3}");
+    match s.trim_end_matches('0').trim_end_matches('.') {
+        "-0" => "0".to_string(),
+        t => t.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,81 @@
+use vectorcade_render_cpu::{DiffOptions, Framebuffer, SizeMismatch, diff};
+use vectorcade_shared::Rgba;
+
+fn filled(color: Rgba) -> Framebuffer {
+    let mut fb = Framebuffer::new(8, 8);
+    fb.clear(color);
+    fb
+}
+
+#[test]
+fn identical_images_match() {
+    let a = filled(Rgba::PHOSPHOR);
+    let report = diff(&a, &a.clone(), &DiffOptions::default()).unwrap();
+    assert!(report.is_match());
+    assert_eq!(report.total, 64);
+    assert_eq!(report.max_delta, 0.0);
+    assert!(report.image.is_none());
+}
+
+#[test]
+fn small_differences_are_tolerated() {
+    let a = filled(Rgba::rgb(0.5, 0.5, 0.5));
+    let b = filled(Rgba::rgb(0.51, 0.5, 0.5));
+    assert!(diff(&a, &b, &DiffOptions::default()).unwrap().is_match());
+
+    let strict = DiffOptions {
+        threshold: 0.0,
+        emit_image: false,
+    };
+    assert_eq!(diff(&a, &b, &strict).unwrap().mismatched, 64);
+}
+
+#[test]
+fn max_delta_is_on_the_threshold_scale() {
+    let a = filled(Rgba::rgb(0.5, 0.5, 0.5));
+    let b = filled(Rgba::rgb(0.6, 0.5, 0.5));
+    let max_delta = diff(&a, &b, &DiffOptions::default()).unwrap().max_delta;
+    let mismatched = |threshold: f32| {
+        let options = DiffOptions {
+            threshold,
+            emit_image: false,
+        };
+        diff(&a, &b, &options).unwrap().mismatched
+    };
+    assert_eq!(mismatched(max_delta * 0.99), 64);
+    assert_eq!(mismatched(max_delta * 1.01), 0);
+}
+
+#[test]
+fn counts_mismatched_pixels_and_emits_image() {
+    let a = filled(Rgba::BLACK);
+    let mut b = a.clone();
+    b.blend(2, 3, Rgba::WHITE, 1.0);
+    b.blend(5, 5, Rgba::WHITE, 1.0);
+    let options = DiffOptions {
+        emit_image: true,
+        ..DiffOptions::default()
+    };
+    let report = diff(&a, &b, &options).unwrap();
+    assert_eq!(report.mismatched, 2);
+    assert!(report.max_delta > 0.9);
+    let image = report.image.unwrap();
+    assert_eq!(image.pixel(2, 3), Some(Rgba::RED));
+    assert_ne!(image.pixel(0, 0), Some(Rgba::RED));
+}
+
+#[test]
+fn rejects_size_mismatch() {
+    let err = diff(
+        &Framebuffer::new(2, 2),
+        &Framebuffer::new(3, 2),
+        &DiffOptions::default(),
+    );
+    assert_eq!(
+        err,
+        Err(SizeMismatch {
+            expected: (2, 2),
+            actual: (3, 2)
+        })
+    );
+}
@@ -0,0 +1,141 @@
+use glam::Vec2;
+use vectorcade_render_cpu::{Framebuffer, PhosphorConfig, PhosphorScreen};
+use vectorcade_shared::draw::{DrawCmd, Line2, Stroke, trail};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::{Aabb, FillRule, Rgba};
+
+const SCREEN: ScreenInfo = ScreenInfo {
+    width_px: 100,
+    height_px: 100,
+    dpi_scale: 1.0,
+};
+
+fn hline(x0: f32, x1: f32, stroke: Stroke) -> Vec<DrawCmd> {
+    vec![DrawCmd::Line(Line2 {
+        a: Vec2::new(x0, 0.0),
+        b: Vec2::new(x1, 0.0),
+        stroke,
+    })]
+}
+
+fn green(fb: &Framebuffer, x: u32, y: u32) -> f32 {
+    fb.pixel(x, y).unwrap().1
+}
+
+fn dim() -> Stroke {
+    Stroke::new(Rgba::GREEN.with_a(0.3), 1.0)
+}
+
+fn screen(config: PhosphorConfig) -> PhosphorScreen {
+    PhosphorScreen::new(SCREEN, config)
+}
+
+#[test]
+fn long_segments_are_dimmer() {
+    let config = PhosphorConfig {
+        beam_falloff_px: 50.0,
+        dwell: 0.0,
+        ..PhosphorConfig::default()
+    };
+    let short = screen(config).render(&hline(-0.1, 0.1, dim()), None, 0.0);
+    let long = screen(config).render(&hline(-0.9, 0.9, dim()), None, 0.0);
+    assert!(green(&short, 50, 50) > green(&long, 50, 50) * 1.5);
+}
+
+#[test]
+fn beam_dwells_at_endpoints() {
+    let fb = screen(PhosphorConfig::default()).render(&hline(-0.5, 0.5, dim()), None, 0.0);
+    assert!(green(&fb, 25, 50) > green(&fb, 50, 50));
+}
+
+#[test]
+fn glow_blooms_around_stroke() {
+    let config = PhosphorConfig::default();
+    let plain = screen(config).render(&hline(-0.5, 0.5, dim()), None, 0.0);
+    let glowing = Stroke::with_glow(dim().color, 1.0, 1.0);
+    let bloom = screen(config).render(&hline(-0.5, 0.5, glowing), None, 0.0);
+    assert_eq!(green(&plain, 50, 53), 0.0);
+    assert!(green(&bloom, 50, 53) > 0.01);
+}
+
+#[test]
+fn phosphor_decays_over_time() {
+    let config = PhosphorConfig {
+        half_life_s: 0.1,
+        ..PhosphorConfig::default()
+    };
+    let mut s = screen(config);
+    let lit = green(&s.render(&hline(-0.5, 0.5, dim()), None, 0.0), 50, 50);
+    let half = green(&s.render(&[], None, 0.1), 50, 50);
+    assert!((half - lit * 0.5).abs() < 1e-3);
+    s.reset();
+    assert_eq!(green(&s.render(&[], None, 0.0), 50, 50), 0.0);
+}
+
+#[test]
+fn zero_half_life_disables_persistence() {
+    let config = PhosphorConfig {
+        half_life_s: 0.0,
+        ..PhosphorConfig::default()
+    };
+    let mut s = screen(config);
+    s.render(&hline(-0.5, 0.5, dim()), None, 0.0);
+    assert_eq!(green(&s.render(&[], None, 0.016), 50, 50), 0.0);
+}
+
+#[test]
+fn clear_sets_background() {
+    let cmds = [DrawCmd::Clear {
+        color: Rgba::rgb(0.0, 0.0, 0.2),
+    }];
+    let fb = screen(PhosphorConfig::default()).render(&cmds, None, 0.0);
+    assert_eq!(fb.pixel(0, 0), Some(Rgba(0.0, 0.0, 0.2, 1.0)));
+}
+
+#[test]
+fn trails_fade_toward_their_tail() {
+    let config = PhosphorConfig {
+        dwell: 0.0,
+        ..PhosphorConfig::default()
+    };
+    let cmds = [trail(
+        &[Vec2::new(-0.8, 0.0), Vec2::new(0.8, 0.0)],
+        Stroke::new(Rgba::GREEN, 1.0),
+        1.0,
+    )];
+    let fb = screen(config).render(&cmds, None, 0.0);
+    assert!(green(&fb, 20, 50) < green(&fb, 50, 50));
+    assert!(green(&fb, 50, 50) < green(&fb, 80, 50));
+}
+
+#[test]
+fn fills_light_the_phosphor_evenly() {
+    let cmds = [DrawCmd::FillPolygon {
+        contours: vec![vec![
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(0.5, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ]],
+        rule: FillRule::NonZero,
+        color: Rgba::GREEN.with_a(0.5),
+    }];
+    let fb = screen(PhosphorConfig::default()).render(&cmds, None, 0.0);
+    assert!((green(&fb, 30, 30) - 0.5).abs() < 1e-6);
+    assert!((green(&fb, 60, 50) - 0.5).abs() < 1e-6);
+    assert_eq!(green(&fb, 10, 50), 0.0);
+}
+
+#[test]
+fn clipped_beam_stops_at_the_clip_edge() {
+    let mut cmds = vec![DrawCmd::PushClipRect(Aabb::from_min_max(
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(0.0, 1.0),
+    ))];
+    cmds.extend(hline(-0.5, 0.5, dim()));
+    cmds.push(DrawCmd::PopClip);
+    let fb = screen(PhosphorConfig::default()).render(&cmds, None, 0.0);
+    // The line spans pixels 25..75 and the clip ends at 50.
+    assert!(green(&fb, 35, 50) > 0.0);
+    assert_eq!(green(&fb, 65, 50), 0.0);
+}
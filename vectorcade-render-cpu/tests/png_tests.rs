@@ -0,0 +1,32 @@
+#![cfg(feature = "png")]
+
+use std::io::Cursor;
+use vectorcade_render_cpu::{DiffOptions, Framebuffer, PngError, diff};
+use vectorcade_shared::Rgba;
+
+#[test]
+fn png_round_trips() {
+    let mut fb = Framebuffer::new(5, 3);
+    fb.clear(Rgba::BLACK);
+    fb.blend(1, 2, Rgba::PHOSPHOR, 1.0);
+    fb.blend(4, 0, Rgba::ORANGE, 0.5);
+
+    let mut bytes = Vec::new();
+    fb.write_png(&mut bytes).unwrap();
+    assert_eq!(&bytes[1..4], b"PNG");
+
+    let back = Framebuffer::read_png(Cursor::new(bytes)).unwrap();
+    assert_eq!(back.width(), 5);
+    assert_eq!(back.height(), 3);
+    let strict = DiffOptions {
+        threshold: 0.01,
+        emit_image: false,
+    };
+    assert!(diff(&fb, &back, &strict).unwrap().is_match());
+}
+
+#[test]
+fn rejects_garbage() {
+    let err = Framebuffer::read_png(Cursor::new(b"not a png".to_vec()));
+    assert!(matches!(err, Err(PngError::Decode(_))));
+}
@@ -0,0 +1,408 @@
+use glam::Vec2;
+use vectorcade_render_cpu::{Framebuffer, Rasterizer, rasterize};
+use vectorcade_shared::draw::{
+    BlendMode, Dash, DrawCmd, Line2, LineCap, LineJoin, OptimizeOptions, Stroke, StrokeVertex,
+    flatten, optimize,
+};
+use vectorcade_shared::font::{BuiltinFont, FontStyleId, GlyphPath, GlyphPathCmd, VectorFont};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::{Aabb, Circle, FillRule, Rgba, rot2, translate2};
+
+const SCREEN: ScreenInfo = ScreenInfo {
+    width_px: 64,
+    height_px: 64,
+    dpi_scale: 1.0,
+};
+
+/// Every glyph is a closed unit square.
+struct BoxFont;
+
+impl VectorFont for BoxFont {
+    fn style_id(&self) -> FontStyleId {
+        FontStyleId::DEFAULT
+    }
+
+    fn has_glyph(&self, _ch: char) -> bool {
+        true
+    }
+
+    fn glyph_paths(&self, _ch: char) -> Vec<GlyphPath> {
+        vec![GlyphPath {
+            cmds: vec![
+                GlyphPathCmd::MoveTo(Vec2::new(0.0, 0.0)),
+                GlyphPathCmd::LineTo(Vec2::new(1.0, 0.0)),
+                GlyphPathCmd::LineTo(Vec2::new(1.0, 1.0)),
+                GlyphPathCmd::LineTo(Vec2::new(0.0, 1.0)),
+                GlyphPathCmd::Close,
+            ],
+        }]
+    }
+
+    fn advance(&self, _ch: char) -> f32 {
+        1.5
+    }
+}
+
+fn hline(y: f32, stroke: Stroke) -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a: Vec2::new(-0.5, y),
+        b: Vec2::new(0.5, y),
+        stroke,
+    })
+}
+
+fn lit(fb: &Framebuffer, x: u32, y: u32) -> f32 {
+    fb.pixel(x, y).map_or(0.0, |p| p.1)
+}
+
+#[test]
+fn clear_fills_framebuffer() {
+    let fb = rasterize(&[DrawCmd::Clear { color: Rgba::BLUE }], SCREEN, None);
+    assert!(fb.pixels().iter().all(|&p| p == Rgba::BLUE));
+}
+
+#[test]
+fn line_is_drawn_in_ndc_space() {
+    // NDC y = 0.5 maps to pixel row 16 (Y-down).
+    let fb = rasterize(&[hline(0.5, Stroke::new(Rgba::GREEN, 2.0))], SCREEN, None);
+    assert!(lit(&fb, 32, 15) > 0.9);
+    assert!(lit(&fb, 32, 16) > 0.9);
+    assert_eq!(lit(&fb, 32, 40), 0.0);
+    assert_eq!(lit(&fb, 4, 16), 0.0);
+}
+
+#[test]
+fn wider_strokes_cover_more() {
+    let count = |w: f32| {
+        let fb = rasterize(&[hline(0.0, Stroke::new(Rgba::GREEN, w))], SCREEN, None);
+        fb.pixels().iter().filter(|p| p.1 > 0.5).count()
+    };
+    assert!(count(6.0) > count(2.0));
+}
+
+#[test]
+fn edges_are_antialiased() {
+    let fb = rasterize(&[hline(0.0, Stroke::new(Rgba::GREEN, 1.5))], SCREEN, None);
+    let column: Vec<f32> = (28..36).map(|y| lit(&fb, 32, y)).collect();
+    assert!(column.iter().any(|&v| v > 0.05 && v < 0.95));
+}
+
+#[test]
+fn alpha_blends_over_background() {
+    let cmds = [
+        DrawCmd::Clear { color: Rgba::RED },
+        hline(0.0, Stroke::new(Rgba::GREEN.with_a(0.5), 4.0)),
+    ];
+    let fb = rasterize(&cmds, SCREEN, None);
+    let p = fb.pixel(32, 32).unwrap();
+    assert!((p.0 - 0.5).abs() < 0.01 && (p.1 - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn polyline_joints_are_not_double_blended() {
+    let cmds = [DrawCmd::Polyline {
+        pts: vec![Vec2::new(-0.5, 0.0), Vec2::ZERO, Vec2::new(0.0, 0.5)],
+        closed: false,
+        stroke: Stroke::new(Rgba::GREEN.with_a(0.5), 4.0),
+    }];
+    let fb = rasterize(&cmds, SCREEN, None);
+    let joint = fb.pixel(32, 32).unwrap();
+    assert!(joint.1 <= 0.51);
+}
+
+#[test]
+fn optimizing_translucent_lines_keeps_pixels() {
+    let stroke = Stroke::new(Rgba::GREEN.with_a(0.5), 4.0);
+    let seg = |a: (f32, f32), b: (f32, f32)| {
+        DrawCmd::Line(Line2 {
+            a: Vec2::from(a),
+            b: Vec2::from(b),
+            stroke,
+        })
+    };
+    let cmds = [
+        DrawCmd::Clear { color: Rgba::BLACK },
+        seg((-0.5, 0.0), (0.0, 0.0)),
+        seg((0.0, 0.0), (0.0, 0.5)),
+        seg((0.0, 0.5), (-0.25, -0.25)),
+    ];
+    let mut optimized = Vec::new();
+    optimize(&cmds, &mut optimized, &OptimizeOptions::default());
+    assert_eq!(
+        rasterize(&cmds, SCREEN, None),
+        rasterize(&optimized, SCREEN, None)
+    );
+}
+
+#[test]
+fn transforms_compose() {
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::new(0.0, 0.25))),
+        DrawCmd::PushTransform(translate2(Vec2::new(0.0, 0.25))),
+        hline(0.0, Stroke::new(Rgba::GREEN, 2.0)),
+        DrawCmd::PopTransform,
+        DrawCmd::PopTransform,
+    ];
+    let fb = rasterize(&cmds, SCREEN, None);
+    assert!(lit(&fb, 32, 16) > 0.9);
+}
+
+#[test]
+fn matches_flattened_display_list() {
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::new(0.1, 0.2))),
+        DrawCmd::PushTransform(rot2(0.5)),
+        hline(0.0, Stroke::new(Rgba::GREEN, 2.0)),
+        DrawCmd::PopTransform,
+        hline(-0.4, Stroke::new(Rgba::RED, 1.0)),
+        DrawCmd::PopTransform,
+    ];
+    let mut flat = Vec::new();
+    flatten(&cmds, &mut flat).unwrap();
+    assert_eq!(
+        rasterize(&cmds, SCREEN, None),
+        rasterize(&flat, SCREEN, None)
+    );
+}
+
+#[test]
+fn text_requires_a_font() {
+    let cmds = [DrawCmd::Text {
+        pos: Vec2::new(-0.5, 0.0),
+        text: "AB".to_string(),
+        size_px: 10.0,
+        color: Rgba::GREEN,
+        style: FontStyleId::DEFAULT,
+    }];
+    let blank = rasterize(&cmds, SCREEN, None);
+    assert!(blank.pixels().iter().all(|p| p.1 == 0.0));
+
+    let mut fb = Framebuffer::from_screen(SCREEN);
+    Rasterizer::new().with_font(&BoxFont).render(&cmds, &mut fb);
+    // First glyph spans x 16..26, second starts at 31.
+    assert!(lit(&fb, 16, 27) > 0.4);
+    assert!(lit(&fb, 31, 27) > 0.4);
+    assert_eq!(lit(&fb, 28, 27), 0.0);
+}
+
+#[test]
+fn builtin_font_draws_real_glyphs() {
+    let cmds = [DrawCmd::Text {
+        pos: Vec2::new(-0.5, 0.0),
+        text: "I".to_string(),
+        size_px: 15.0,
+        color: Rgba::GREEN,
+        style: FontStyleId::DEFAULT,
+    }];
+    let fb = rasterize(&cmds, SCREEN, Some(&BuiltinFont));
+    // The stem of the I runs from the baseline at y 32 up to y 17 along
+    // x 21, straddling pixel columns 20 and 21.
+    assert!(lit(&fb, 20, 25) > 0.4);
+    assert!(lit(&fb, 21, 25) > 0.4);
+    assert_eq!(lit(&fb, 18, 25), 0.0);
+    assert_eq!(lit(&fb, 24, 25), 0.0);
+}
+
+#[test]
+fn circles_are_tessellated_into_rings() {
+    let cmds = [DrawCmd::Circle {
+        center: Vec2::ZERO,
+        radius: 0.5,
+        stroke: Stroke::new(Rgba::GREEN, 2.0),
+    }];
+    let fb = rasterize(&cmds, SCREEN, None);
+    assert!(lit(&fb, 48, 32) > 0.9);
+    assert!(lit(&fb, 32, 16) > 0.9);
+    assert_eq!(lit(&fb, 32, 32), 0.0);
+}
+
+#[test]
+fn caps_and_dashes_are_honored() {
+    let base = Stroke::new(Rgba::GREEN, 4.0);
+    let butt = rasterize(&[hline(0.0, base.cap(LineCap::Butt))], SCREEN, None);
+    let square = rasterize(&[hline(0.0, base.cap(LineCap::Square))], SCREEN, None);
+    // The line ends at x = 48; square caps reach 2px further.
+    assert_eq!(lit(&butt, 49, 32), 0.0);
+    assert!(lit(&square, 49, 32) > 0.9);
+    assert!(lit(&butt, 40, 32) > 0.9);
+
+    let dashed = base.dashed(Dash::new(4.0, 4.0, 0.0)).cap(LineCap::Butt);
+    let fb = rasterize(&[hline(0.0, dashed)], SCREEN, None);
+    assert!(lit(&fb, 17, 32) > 0.9);
+    assert_eq!(lit(&fb, 21, 32), 0.0);
+}
+
+#[test]
+fn miter_joins_fill_corners() {
+    let corner = |join| DrawCmd::Polyline {
+        pts: vec![Vec2::new(-0.5, 0.0), Vec2::ZERO, Vec2::new(0.0, 0.5)],
+        closed: false,
+        stroke: Stroke::new(Rgba::GREEN, 6.0).join(join),
+    };
+    let miter = rasterize(&[corner(LineJoin::Miter)], SCREEN, None);
+    let bevel = rasterize(&[corner(LineJoin::Bevel)], SCREEN, None);
+    // Outer corner of the bend at pixel (32, 32) is toward +x, +y (down).
+    assert!(lit(&miter, 34, 34) > 0.9);
+    assert!(lit(&bevel, 34, 34) < 0.5);
+}
+
+#[test]
+fn gradient_color_and_width_vary_along_the_line() {
+    let cmds = [DrawCmd::GradientPolyline {
+        verts: vec![
+            StrokeVertex::new(Vec2::new(-0.5, 0.0), Rgba::RED, 1.0),
+            StrokeVertex::new(Vec2::new(0.5, 0.0), Rgba::GREEN, 7.0),
+        ],
+        closed: false,
+        glow: 0.0,
+    }];
+    let fb = rasterize(&cmds, SCREEN, None);
+    let (start, end) = (fb.pixel(17, 32).unwrap(), fb.pixel(47, 32).unwrap());
+    assert!(start.0 > 0.5 && start.1 < start.0 * 0.1, "{start:?}");
+    assert!(end.1 > 0.8 && end.0 < 0.2, "{end:?}");
+    // Only the wide end reaches two pixels off the center line.
+    assert_eq!(lit(&fb, 18, 34), 0.0);
+    assert!(lit(&fb, 46, 34) > 0.9);
+}
+
+fn square(half: f32) -> Vec<Vec2> {
+    vec![
+        Vec2::new(-half, -half),
+        Vec2::new(half, -half),
+        Vec2::new(half, half),
+        Vec2::new(-half, half),
+    ]
+}
+
+#[test]
+fn fills_honor_holes_and_fill_rule() {
+    let fill = |rule| DrawCmd::FillPolygon {
+        contours: vec![square(0.5), square(0.25)],
+        rule,
+        color: Rgba::GREEN,
+    };
+    let even_odd = rasterize(&[fill(FillRule::EvenOdd)], SCREEN, None);
+    let non_zero = rasterize(&[fill(FillRule::NonZero)], SCREEN, None);
+    // The outline spans pixels 16..48 and the hole 24..40.
+    assert_eq!(lit(&even_odd, 20, 20), 1.0);
+    assert_eq!(lit(&even_odd, 32, 32), 0.0);
+    assert_eq!(lit(&non_zero, 32, 32), 1.0);
+    assert_eq!(lit(&even_odd, 12, 32), 0.0);
+}
+
+#[test]
+fn fill_edges_are_antialiased() {
+    // A right edge at x = 32.5 px covers half of pixel column 32.
+    let cmds = [DrawCmd::FillPolygon {
+        contours: vec![vec![
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(1.0 / 64.0, -0.5),
+            Vec2::new(1.0 / 64.0, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ]],
+        rule: FillRule::NonZero,
+        color: Rgba::GREEN,
+    }];
+    let fb = rasterize(&cmds, SCREEN, None);
+    assert_eq!(lit(&fb, 31, 32), 1.0);
+    assert!((lit(&fb, 32, 32) - 0.5).abs() < 1e-6);
+    assert_eq!(lit(&fb, 33, 32), 0.0);
+}
+
+#[test]
+fn blend_modes_combine_crossing_beams() {
+    let dim = Stroke::new(Rgba::GREEN.with_a(0.4), 3.0);
+    let cross = |mode| {
+        let vline = DrawCmd::Line(Line2 {
+            a: Vec2::new(0.0, -0.5),
+            b: Vec2::new(0.0, 0.5),
+            stroke: dim,
+        });
+        let cmds = [
+            DrawCmd::PushBlend(mode),
+            hline(0.0, dim),
+            vline,
+            DrawCmd::PopBlend,
+        ];
+        rasterize(&cmds, SCREEN, None)
+    };
+    let (alpha, additive, max) = (
+        cross(BlendMode::Alpha),
+        cross(BlendMode::Additive),
+        cross(BlendMode::Max),
+    );
+    // Where the beams cross (32, 32), additive light doubles.
+    assert!((lit(&additive, 32, 32) - 0.8).abs() < 1e-5);
+    assert!((lit(&max, 32, 32) - 0.4).abs() < 1e-5);
+    assert!((lit(&alpha, 32, 32) - 0.64).abs() < 1e-5);
+    // Away from the crossing all modes agree.
+    for fb in [&additive, &max] {
+        assert!((lit(fb, 20, 32) - lit(&alpha, 20, 32)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn pop_blend_restores_previous_mode() {
+    let dim = Stroke::new(Rgba::GREEN.with_a(0.5), 3.0);
+    let cmds = [
+        DrawCmd::PushBlend(BlendMode::Additive),
+        DrawCmd::PushBlend(BlendMode::Max),
+        DrawCmd::PopBlend,
+        hline(0.0, dim),
+        hline(0.0, dim),
+        DrawCmd::PopBlend,
+        hline(0.0, dim),
+    ];
+    let fb = rasterize(&cmds, SCREEN, None);
+    // Two additive passes saturate green, then an alpha pass keeps it.
+    assert!((lit(&fb, 32, 32) - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn clip_rect_cuts_strokes_and_fills() {
+    let clip = Aabb::from_min_max(Vec2::new(-0.25, -1.0), Vec2::new(0.25, 0.5));
+    let wide = Stroke::new(Rgba::GREEN, 5.0);
+    let cmds = [
+        DrawCmd::PushClipRect(clip),
+        DrawCmd::FillPolygon {
+            contours: vec![square(0.75)],
+            rule: FillRule::NonZero,
+            color: Rgba::RED,
+        },
+        hline(0.0, wide),
+        DrawCmd::PopClip,
+        hline(-0.75, wide),
+    ];
+    let fb = rasterize(&cmds, SCREEN, None);
+    let red = |x, y| fb.pixel(x, y).map_or(0.0, |p| p.0);
+    // The clip spans pixels 24..40 horizontally and 16..64 vertically.
+    assert_eq!(lit(&fb, 30, 32), 1.0);
+    assert_eq!(lit(&fb, 20, 32), 0.0);
+    assert_eq!(red(30, 20), 1.0);
+    assert_eq!(red(30, 12), 0.0);
+    assert_eq!(red(44, 20), 0.0);
+    // The round cap at the clip edge is cut too.
+    assert_eq!(lit(&fb, 40, 32), 0.0);
+    // After the pop, drawing is unclipped again.
+    assert_eq!(lit(&fb, 20, 56), 1.0);
+}
+
+#[test]
+fn nested_clip_circle_intersects_its_parent() {
+    let cmds = [
+        DrawCmd::PushClipRect(Aabb::from_min_max(Vec2::new(0.0, -1.0), Vec2::ONE)),
+        DrawCmd::PushClipCircle(Circle::new(Vec2::ZERO, 0.5)),
+        DrawCmd::Line(Line2 {
+            a: -Vec2::X,
+            b: Vec2::X,
+            stroke: Stroke::new(Rgba::GREEN, 3.0),
+        }),
+        DrawCmd::PopClip,
+        DrawCmd::PopClip,
+    ];
+    let fb = rasterize(&cmds, SCREEN, None);
+    // The circle spans pixels 16..48; the rect keeps its right half.
+    assert_eq!(lit(&fb, 40, 32), 1.0);
+    assert_eq!(lit(&fb, 52, 32), 0.0);
+    assert_eq!(lit(&fb, 24, 32), 0.0);
+}
@@ -0,0 +1,237 @@
+use glam::Vec2;
+use vectorcade_render_cpu::{SvgOptions, to_svg};
+use vectorcade_shared::draw::{
+    BlendMode, Dash, DrawCmd, LayerId, Line2, LineCap, LineJoin, Stroke, StrokeVertex, rect_wire,
+};
+use vectorcade_shared::font::{BuiltinFont, FontStyleId};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::{Aabb, Circle, FillRule, Rgba, translate2};
+
+const SCREEN: ScreenInfo = ScreenInfo {
+    width_px: 200,
+    height_px: 100,
+    dpi_scale: 1.0,
+};
+
+fn line(a: Vec2, b: Vec2, stroke: Stroke) -> DrawCmd {
+    DrawCmd::Line(Line2 { a, b, stroke })
+}
+
+#[test]
+fn document_uses_pixel_viewbox() {
+    let svg = to_svg(&[], SCREEN, None, SvgOptions::default());
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 200 100">"#
+    ));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn line_maps_ndc_y_up_to_svg_y_down() {
+    let cmds = [line(
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(1.0, -1.0),
+        Stroke::new(Rgba::ORANGE.with_a(0.5), 2.5),
+    )];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(r#"<line x1="0" y1="0" x2="200" y2="100""#));
+    assert!(svg.contains(r#"stroke="rgb(255,128,0)" stroke-opacity="0.5" stroke-width="2.5""#));
+}
+
+#[test]
+fn transforms_are_flattened() {
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::new(0.5, 0.0))),
+        rect_wire(Vec2::splat(-0.5), Vec2::splat(0.5), Stroke::default()),
+        DrawCmd::PopTransform,
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ZERO, Vec2::X],
+            closed: false,
+            stroke: Stroke::default(),
+        },
+    ];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(r#"<polygon points="100,75 200,75 200,25 100,25""#));
+    assert!(svg.contains(r#"<polyline points="100,50 200,50""#));
+}
+
+#[test]
+fn layers_become_groups_with_unique_ids() {
+    let cmds = [
+        DrawCmd::BeginLayer(LayerId::new("hud", 0)),
+        DrawCmd::EndLayer,
+        DrawCmd::BeginLayer(LayerId::new("hud", 0)),
+        DrawCmd::BeginLayer(LayerId::new("2 debug", 0)),
+    ];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(r#"<g id="hud">"#));
+    assert!(svg.contains(r#"<g id="hud-2">"#));
+    assert!(svg.contains(r#"<g id="layer-2_debug">"#));
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+}
+
+#[test]
+fn glow_is_optional_blur_filter() {
+    let cmds = [line(
+        Vec2::ZERO,
+        Vec2::X,
+        Stroke::with_glow(Rgba::GREEN, 1.0, 0.5),
+    )];
+    let plain = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(!plain.contains("filter"));
+
+    let glowing = to_svg(&cmds, SCREEN, None, SvgOptions { glow: true });
+    assert!(glowing.contains(r#"<filter id="glow-5""#));
+    assert!(glowing.contains(r#"filter="url(#glow-5)""#));
+}
+
+#[test]
+fn text_without_font_is_escaped() {
+    let cmds = [DrawCmd::Text {
+        pos: Vec2::ZERO,
+        text: "<P1 & P2>".to_string(),
+        size_px: 12.0,
+        color: Rgba::WHITE,
+        style: FontStyleId::DEFAULT,
+    }];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(">&lt;P1 &amp; P2&gt;</text>"));
+}
+
+#[test]
+fn text_with_builtin_font_becomes_a_path() {
+    let cmds = [DrawCmd::Text {
+        pos: Vec2::ZERO,
+        text: "-".to_string(),
+        size_px: 12.0,
+        color: Rgba::WHITE,
+        style: FontStyleId::DEFAULT,
+    }];
+    let svg = to_svg(&cmds, SCREEN, Some(&BuiltinFont), SvgOptions::default());
+    assert!(!svg.contains("<text"));
+    // The dash spans grid x 1..3 at half height: 2..6 px right, 6 px up.
+    assert!(svg.contains(r#"<path d="M102 44L106 44" "#));
+}
+
+#[test]
+fn curves_become_polylines() {
+    let stroke = Stroke::default();
+    let cmds = [
+        DrawCmd::Circle {
+            center: Vec2::ZERO,
+            radius: 0.5,
+            stroke,
+        },
+        DrawCmd::Arc {
+            center: Vec2::ZERO,
+            radius: 0.5,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::PI,
+            stroke,
+        },
+    ];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(r#"<polygon points="150,50 "#));
+    assert!(svg.contains(r#"<polyline points="150,50 "#));
+    assert!(svg.contains(r#" 50,50" "#));
+}
+
+#[test]
+fn stroke_styles_map_to_svg_attributes() {
+    let stroke = Stroke::default()
+        .cap(LineCap::Butt)
+        .join(LineJoin::Bevel)
+        .dashed(Dash::new(6.0, 3.0, 1.5));
+    let svg = to_svg(
+        &[line(Vec2::ZERO, Vec2::X, stroke)],
+        SCREEN,
+        None,
+        SvgOptions::default(),
+    );
+    assert!(svg.contains(r#"stroke-linecap="butt" stroke-linejoin="bevel""#));
+    assert!(svg.contains(r#"stroke-dasharray="6 3" stroke-dashoffset="1.5""#));
+
+    let fine = stroke.dashed(Dash::new(1e-3, 1e-3, 0.0));
+    let svg = to_svg(
+        &[line(Vec2::ZERO, Vec2::X, fine)],
+        SCREEN,
+        None,
+        SvgOptions::default(),
+    );
+    assert!(!svg.contains("stroke-dasharray"));
+}
+
+#[test]
+fn gradient_segments_use_linear_gradients() {
+    let cmds = [DrawCmd::GradientPolyline {
+        verts: vec![
+            StrokeVertex::new(Vec2::new(-1.0, 0.0), Rgba::RED, 1.0),
+            StrokeVertex::new(Vec2::new(0.0, 0.0), Rgba::GREEN.with_a(0.5), 3.0),
+            StrokeVertex::new(Vec2::new(1.0, 0.0), Rgba::BLUE, 5.0),
+        ],
+        closed: false,
+        glow: 0.0,
+    }];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(
+        r#"<linearGradient id="gradient-0" gradientUnits="userSpaceOnUse" x1="0" y1="50" x2="100" y2="50"><stop offset="0" stop-color="rgb(255,0,0)"/><stop offset="1" stop-color="rgb(0,255,0)" stop-opacity="0.5"/></linearGradient>"#
+    ));
+    assert!(svg.contains(r#"stroke="url(#gradient-1)" stroke-width="4""#));
+    assert_eq!(svg.matches("<line ").count(), 2);
+}
+
+#[test]
+fn fills_become_paths_with_fill_rule() {
+    let cmds = [DrawCmd::FillPolygon {
+        contours: vec![
+            vec![
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(0.0, 1.0),
+            ],
+            Vec::new(),
+        ],
+        rule: FillRule::EvenOdd,
+        color: Rgba::RED.with_a(0.5),
+    }];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(
+        r#"<path d="M0 100L200 100L100 0Z" fill="rgb(255,0,0)" fill-opacity="0.5" fill-rule="evenodd"/>"#
+    ));
+}
+
+#[test]
+fn blend_modes_map_to_mix_blend_mode() {
+    let cmds = [
+        DrawCmd::PushBlend(BlendMode::Additive),
+        line(Vec2::ZERO, Vec2::X, Stroke::default()),
+        DrawCmd::PushBlend(BlendMode::Max),
+        line(Vec2::ZERO, Vec2::Y, Stroke::default()),
+        DrawCmd::PopBlend,
+        DrawCmd::PopBlend,
+        line(Vec2::ZERO, -Vec2::X, Stroke::default()),
+    ];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert_eq!(svg.matches("mix-blend-mode:plus-lighter").count(), 1);
+    assert_eq!(svg.matches("mix-blend-mode:lighten").count(), 1);
+    assert_eq!(svg.matches("<line ").count(), 3);
+}
+
+#[test]
+fn clips_become_nested_clip_paths() {
+    let cmds = [
+        DrawCmd::PushClipRect(Aabb::from_min_max(Vec2::splat(-0.5), Vec2::new(0.5, 1.0))),
+        line(Vec2::ZERO, Vec2::X, Stroke::default()),
+        DrawCmd::PushClipCircle(Circle::new(Vec2::ZERO, 0.25)),
+        line(Vec2::ZERO, Vec2::Y, Stroke::default()),
+        DrawCmd::PopClip,
+        DrawCmd::PopClip,
+        line(Vec2::ZERO, -Vec2::X, Stroke::default()),
+    ];
+    let svg = to_svg(&cmds, SCREEN, None, SvgOptions::default());
+    assert!(svg.contains(r#"<clipPath id="clip-0" clipPathUnits="userSpaceOnUse"><rect x="50" y="0" width="100" height="75"/></clipPath>"#));
+    assert!(svg.contains(r#"<clipPath id="clip-1" clipPathUnits="userSpaceOnUse" clip-path="url(#clip-0)"><ellipse cx="100" cy="50" rx="25" ry="12.5"/></clipPath>"#));
+    assert_eq!(svg.matches(r#"clip-path="url(#clip-0)""#).count(), 2);
+    assert_eq!(svg.matches(r#"clip-path="url(#clip-1)""#).count(), 1);
+    assert_eq!(svg.matches("<line ").count(), 3);
+}
@@ -0,0 +1,27 @@
+//! Blend modes.
+
+/// How a drawn pixel combines with what is already in the target.
+///
+/// Set with `DrawCmd::PushBlend` and restored with `DrawCmd::PopBlend`.
+/// Each primitive (a line, a whole polyline, a fill or a text run) is
+/// antialiased into a coverage mask and then blended once, so a
+/// polyline never brightens its own joints. Separate primitives that
+/// cross do combine, which is what `Additive` is for.
+///
+/// `Stroke::glow` is independent of the blend mode: glow is extra light
+/// around the beam and is always added on top by backends that draw it
+/// (the phosphor bloom, the SVG blur filter), whatever mode the stroke
+/// core uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// Paint over the target by alpha (source-over).
+    #[default]
+    Alpha,
+    /// Add the color, scaled by alpha and coverage, to the target. Beams
+    /// crossing each other get brighter, as on a vector monitor.
+    Additive,
+    /// Keep the brighter of the target and the color scaled by alpha and
+    /// coverage, per channel. Overlaps neither darken nor saturate.
+    Max,
+}
@@ -0,0 +1,117 @@
+//! Curve tessellation.
+//!
+//! `Circle`, `Arc` and `Ellipse` become polylines with just enough
+//! segments that the true curve never strays more than
+//! [`CURVE_TOLERANCE_PX`] from them on screen. The segment count is
+//! derived from the curve's largest on-screen radius, so small blips
+//! stay cheap and screen-filling rings stay smooth.
+
+use super::DrawCmd;
+use crate::game::ScreenInfo;
+use glam::{Mat2, Mat3, Vec2};
+use std::f32::consts::TAU;
+
+/// Maximum distance in pixels between a curve and its tessellation.
+pub const CURVE_TOLERANCE_PX: f32 = 0.25;
+/// Fewest segments used for a full turn.
+const MIN_SEGMENTS: usize = 8;
+/// Most segments used for a full turn.
+const MAX_SEGMENTS: usize = 512;
+
+/// Return the number of segments for a curve of `radius_px` pixels
+/// sweeping `sweep` radians.
+///
+/// Full turns use between 8 and 512 segments; partial sweeps use a
+/// proportional share, and never fewer than one.
+#[must_use]
+pub fn segments_for_radius(radius_px: f32, sweep: f32) -> usize {
+    let turn = if radius_px > CURVE_TOLERANCE_PX {
+        let step = 2.0 * (1.0 - CURVE_TOLERANCE_PX / radius_px).acos();
+        ((TAU / step).ceil() as usize).clamp(MIN_SEGMENTS, MAX_SEGMENTS)
+    } else {
+        MIN_SEGMENTS
+    };
+    let share = (sweep.abs() / TAU).min(1.0);
+    ((turn as f32 * share).ceil() as usize).max(1)
+}
+
+/// Tessellate a curve command into a `Polyline`.
+///
+/// `transform` is the composed transform the command is drawn under and
+/// `screen` the target surface; together they give the on-screen size.
+/// The returned points are in the command's own coordinates, so the
+/// polyline is drawn under the same transform. Circles and ellipses
+/// become closed polylines, arcs open ones with both end points exact.
+///
+/// Returns `None` for commands that are not curves.
+#[must_use]
+pub fn tessellate(cmd: &DrawCmd, transform: Mat3, screen: ScreenInfo) -> Option<DrawCmd> {
+    let (center, axes, start, sweep, stroke) = match *cmd {
+        DrawCmd::Circle {
+            center,
+            radius,
+            stroke,
+        } => (
+            center,
+            Mat2::from_diagonal(Vec2::splat(radius)),
+            0.0,
+            TAU,
+            stroke,
+        ),
+        DrawCmd::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            stroke,
+        } => {
+            let sweep = (end_angle - start_angle).clamp(-TAU, TAU);
+            let axes = Mat2::from_diagonal(Vec2::splat(radius));
+            (center, axes, start_angle, sweep, stroke)
+        }
+        DrawCmd::Ellipse {
+            center,
+            radii,
+            rotation,
+            stroke,
+        } => {
+            let axes = Mat2::from_angle(rotation) * Mat2::from_diagonal(radii);
+            (center, axes, 0.0, TAU, stroke)
+        }
+        _ => return None,
+    };
+    let closed = !matches!(cmd, DrawCmd::Arc { .. });
+    let half = Vec2::new(screen.width_px as f32, screen.height_px as f32) * 0.5;
+    let on_screen = Mat2::from_diagonal(half) * linear(transform) * axes;
+    let n = segments_for_radius(svd(on_screen).1.x, sweep);
+    let count = if closed { n } else { n + 1 };
+    let pts = (0..count)
+        .map(|i| {
+            let t = start + sweep * i as f32 / n as f32;
+            center + axes * Vec2::new(t.cos(), t.sin())
+        })
+        .collect();
+    Some(DrawCmd::Polyline {
+        pts,
+        closed,
+        stroke,
+    })
+}
+
+/// Return the linear (2x2) part of an affine transform.
+pub(super) fn linear(m: Mat3) -> Mat2 {
+    Mat2::from_cols(m.x_axis.truncate(), m.y_axis.truncate())
+}
+
+/// Decompose `m` as `R(theta) * diag(s) * R(phi)`.
+///
+/// Returns `(theta, s, phi)` with `s.x >= s.y.abs()`; `s.y` is negative
+/// when `m` is a reflection.
+pub(super) fn svd(m: Mat2) -> (f32, Vec2, f32) {
+    let (a, b, c, d) = (m.x_axis.x, m.y_axis.x, m.x_axis.y, m.y_axis.y);
+    let (e, f) = ((a + d) * 0.5, (a - d) * 0.5);
+    let (g, h) = ((c + b) * 0.5, (c - b) * 0.5);
+    let (q, r) = (e.hypot(h), f.hypot(g));
+    let (a1, a2) = (g.atan2(f), h.atan2(e));
+    ((a2 + a1) * 0.5, Vec2::new(q + r, q - r), (a2 - a1) * 0.5)
+}
@@ -0,0 +1,220 @@
+//! Transform-stack flattening.
+//!
+//! [`flatten`] is the reference definition of `PushTransform` and
+//! `PopTransform`:
+//!
+//! - The stack starts at `Mat3::IDENTITY`.
+//! - `PushTransform(t)` composes with its parent: the new top is
+//!   `parent * t`, so `t` applies to points first.
+//! - `PopTransform` restores the parent.
+//! - Transforms apply to `Line2` endpoints, polyline and polygon points,
+//!   curve geometry and the `Text` position. Stroke widths and text size stay
+//!   in screen pixels.
+//!
+//! A circle under a non-uniform scale or shear becomes an `Ellipse`. An
+//! `Arc` under one has no exact arc form, so it is tessellated for the
+//! default [`ScreenInfo`] and emitted as a `Polyline`.
+//!
+//! Clip regions are in NDC already and pass through unchanged.
+//!
+//! The output contains no transform commands and draws the same image.
+
+use super::curve::{linear, svd, tessellate};
+use super::{DrawCmd, Line2, Stroke, StrokeVertex};
+use crate::game::ScreenInfo;
+use glam::{Mat2, Mat3, Vec2};
+use std::fmt;
+
+/// Relative tolerance for treating a transform as a similarity.
+const SIMILARITY_EPS: f32 = 1e-5;
+
+/// Error returned by [`flatten`] for an unbalanced transform stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlattenError {
+    /// A `PopTransform` had no matching `PushTransform`.
+    UnbalancedPop {
+        /// Index of the offending command.
+        index: usize,
+    },
+    /// The list ended with pushes still open.
+    UnclosedPush {
+        /// Index of the outermost unclosed `PushTransform`.
+        index: usize,
+        /// Number of pushes left open.
+        depth: usize,
+    },
+}
+
+impl fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnbalancedPop { index } => {
+                write!(f, "PopTransform at command {index} has no matching push")
+            }
+            Self::UnclosedPush { index, depth } => write!(
+                f,
+                "{depth} PushTransform(s) left open, outermost at command {index}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FlattenError {}
+
+/// Write `cmds` into `out` with every transform applied.
+///
+/// `out` is cleared first. On error its contents are unspecified.
+///
+/// # Errors
+///
+/// Returns [`FlattenError`] if pushes and pops do not balance.
+pub fn flatten(cmds: &[DrawCmd], out: &mut Vec<DrawCmd>) -> Result<(), FlattenError> {
+    out.clear();
+    // Composed matrix and the index of the push that created it.
+    let mut stack: Vec<(Mat3, usize)> = Vec::new();
+    for (index, cmd) in cmds.iter().enumerate() {
+        let m = stack.last().map_or(Mat3::IDENTITY, |(m, _)| *m);
+        match cmd {
+            DrawCmd::PushTransform(t) => stack.push((m * *t, index)),
+            DrawCmd::PopTransform => {
+                stack.pop().ok_or(FlattenError::UnbalancedPop { index })?;
+            }
+            _ => out.push(apply(cmd, m)),
+        }
+    }
+    match stack.first() {
+        Some(&(_, index)) => Err(FlattenError::UnclosedPush {
+            index,
+            depth: stack.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Return `cmd` with its geometry mapped through `m`.
+fn apply(cmd: &DrawCmd, m: Mat3) -> DrawCmd {
+    if m == Mat3::IDENTITY {
+        return cmd.clone();
+    }
+    match cmd {
+        DrawCmd::Line(l) => DrawCmd::Line(Line2 {
+            a: m.transform_point2(l.a),
+            b: m.transform_point2(l.b),
+            stroke: l.stroke,
+        }),
+        DrawCmd::Polyline {
+            pts,
+            closed,
+            stroke,
+        } => DrawCmd::Polyline {
+            pts: pts.iter().map(|&p| m.transform_point2(p)).collect(),
+            closed: *closed,
+            stroke: *stroke,
+        },
+        DrawCmd::GradientPolyline {
+            verts,
+            closed,
+            glow,
+        } => DrawCmd::GradientPolyline {
+            verts: verts
+                .iter()
+                .map(|v| StrokeVertex {
+                    pos: m.transform_point2(v.pos),
+                    ..*v
+                })
+                .collect(),
+            closed: *closed,
+            glow: *glow,
+        },
+        DrawCmd::FillPolygon {
+            contours,
+            rule,
+            color,
+        } => DrawCmd::FillPolygon {
+            contours: contours
+                .iter()
+                .map(|c| c.iter().map(|&p| m.transform_point2(p)).collect())
+                .collect(),
+            rule: *rule,
+            color: *color,
+        },
+        DrawCmd::Text {
+            pos,
+            text,
+            size_px,
+            color,
+            style,
+        } => DrawCmd::Text {
+            pos: m.transform_point2(*pos),
+            text: text.clone(),
+            size_px: *size_px,
+            color: *color,
+            style: *style,
+        },
+        DrawCmd::Circle {
+            center,
+            radius,
+            stroke,
+        } => ellipse(m, *center, Vec2::splat(*radius), 0.0, *stroke, true),
+        DrawCmd::Ellipse {
+            center,
+            radii,
+            rotation,
+            stroke,
+        } => ellipse(m, *center, *radii, *rotation, *stroke, false),
+        DrawCmd::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            stroke,
+        } => {
+            let (theta, s, phi) = svd(linear(m));
+            if s.x - s.y.abs() > SIMILARITY_EPS * s.x {
+                let poly = tessellate(cmd, m, ScreenInfo::default());
+                return apply(&poly.unwrap_or_else(|| cmd.clone()), m);
+            }
+            // A reflection mirrors angles as well as rotating them.
+            let (start_angle, end_angle) = if s.y >= 0.0 {
+                (start_angle + theta + phi, end_angle + theta + phi)
+            } else {
+                (theta - phi - start_angle, theta - phi - end_angle)
+            };
+            DrawCmd::Arc {
+                center: m.transform_point2(*center),
+                radius: radius * s.x,
+                start_angle,
+                end_angle,
+                stroke: *stroke,
+            }
+        }
+        _ => cmd.clone(),
+    }
+}
+
+/// Map an ellipse through `m`, keeping circles as circles when possible.
+fn ellipse(
+    m: Mat3,
+    center: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    stroke: Stroke,
+    circle: bool,
+) -> DrawCmd {
+    let axes = linear(m) * Mat2::from_angle(rotation) * Mat2::from_diagonal(radii);
+    let (theta, s, _) = svd(axes);
+    let center = m.transform_point2(center);
+    if circle && s.x - s.y.abs() <= SIMILARITY_EPS * s.x {
+        return DrawCmd::Circle {
+            center,
+            radius: s.x,
+            stroke,
+        };
+    }
+    DrawCmd::Ellipse {
+        center,
+        radii: Vec2::new(s.x, s.y.abs()),
+        rotation: theta,
+        stroke,
+    }
+}
@@ -0,0 +1,55 @@
+//! Per-vertex styled polylines.
+
+use super::{DrawCmd, Stroke};
+use glam::Vec2;
+use vectorcade_core::Rgba;
+
+/// A vertex of a `DrawCmd::GradientPolyline`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrokeVertex {
+    /// Position.
+    pub pos: Vec2,
+    /// Color at this vertex.
+    pub color: Rgba,
+    /// Stroke width in screen pixels at this vertex.
+    pub width_px: f32,
+}
+
+impl StrokeVertex {
+    /// Create a vertex.
+    #[must_use]
+    pub const fn new(pos: Vec2, color: Rgba, width_px: f32) -> Self {
+        Self {
+            pos,
+            color,
+            width_px,
+        }
+    }
+}
+
+/// Build a fading trail from a history of positions, oldest first.
+///
+/// The newest point is drawn with `stroke`'s color and width. Alpha
+/// falls off towards the oldest point as `(1 - age)^falloff`, where age
+/// runs from 0 (newest) to 1 (oldest): `falloff` 1 fades linearly,
+/// larger values fade faster. Cap, join and dash settings are ignored.
+#[must_use]
+pub fn trail(history: &[Vec2], stroke: Stroke, falloff: f32) -> DrawCmd {
+    let last = history.len().saturating_sub(1);
+    let verts = history
+        .iter()
+        .enumerate()
+        .map(|(i, &pos)| {
+            // Age from the newest point, so a lone point is fresh.
+            let fresh = 1.0 - (last - i) as f32 / last.max(1) as f32;
+            let alpha = stroke.color.3 * fresh.powf(falloff.max(0.0));
+            StrokeVertex::new(pos, stroke.color.with_a(alpha), stroke.width_px)
+        })
+        .collect();
+    DrawCmd::GradientPolyline {
+        verts,
+        closed: false,
+        glow: stroke.glow,
+    }
+}
@@ -0,0 +1,283 @@
+//! Render layers.
+//!
+//! A display list groups commands with `BeginLayer(LayerId)` and
+//! `EndLayer`. Games emit layers in whatever order is convenient;
+//! [`sort_layers`] reorders them by z and applies the host's
+//! [`LayerSet`] (hidden layers, per-layer blend) before rendering.
+
+use super::{BlendMode, DrawCmd, Stroke};
+use std::collections::HashMap;
+
+/// Identifies a render layer and orders it against other layers.
+///
+/// Layers with a higher `z` are drawn later, on top. Two ids are the
+/// same layer only if both name and z match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerId {
+    /// Name for debugging, profiling and SVG group ids.
+    ///
+    /// With the `serde` feature, deserialized names are interned: the
+    /// built-in layers' names are reused, and each other distinct name
+    /// is leaked once. Leaked memory is never freed, so a peer or file
+    /// sending new names could grow it; a name longer than
+    /// [`Self::MAX_NAME_LEN`] bytes, or a new name once
+    /// [`Self::MAX_INTERNED_NAMES`] are interned, is a deserialization
+    /// error.
+    // Spelled `std::primitive::str` so serde does not treat the field
+    // as borrowed from the input (which would require `'de: 'static`).
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "layer_name::deserialize"))]
+    pub name: &'static std::primitive::str,
+    /// Draw order; higher is on top.
+    pub z: i32,
+}
+
+impl LayerId {
+    /// Starfields, terrain and other backdrops.
+    pub const BACKGROUND: LayerId = LayerId::new("background", -100);
+    /// Ships, shots and the rest of the game world.
+    pub const PLAYFIELD: LayerId = LayerId::new("playfield", 0);
+    /// Scores, lives and messages drawn over the playfield.
+    pub const HUD: LayerId = LayerId::new("hud", 100);
+    /// Developer overlays such as hitboxes; usually hidden.
+    pub const DEBUG: LayerId = LayerId::new("debug", 1000);
+
+    /// Most distinct custom names interned by deserialization.
+    pub const MAX_INTERNED_NAMES: usize = 256;
+    /// Longest name in bytes interned by deserialization.
+    pub const MAX_NAME_LEN: usize = 64;
+
+    /// Create a layer id.
+    #[must_use]
+    pub const fn new(name: &'static str, z: i32) -> Self {
+        Self { name, z }
+    }
+}
+
+/// How the host presents one layer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerStyle {
+    /// Whether the layer is drawn at all.
+    pub visible: bool,
+    /// Blend mode the layer's commands start in, instead of the mode in
+    /// effect around the layer.
+    pub blend: Option<BlendMode>,
+    /// Stroke for emitters to draw the layer with when they have no
+    /// style of their own, as [`DrawList::styled_layer`](super::DrawList::styled_layer)
+    /// does. Commands carry explicit strokes, so this does not change a
+    /// finished display list.
+    pub stroke: Option<Stroke>,
+}
+
+impl Default for LayerStyle {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            blend: None,
+            stroke: None,
+        }
+    }
+}
+
+/// Host-side settings for layers, keyed by [`LayerId`].
+///
+/// Layers without settings use [`LayerStyle::default`]: visible, with
+/// no default blend or stroke.
+#[derive(Clone, Debug, Default)]
+pub struct LayerSet {
+    styles: HashMap<LayerId, LayerStyle>,
+}
+
+impl LayerSet {
+    /// Create a set where every layer is visible and unstyled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the style of `id`.
+    #[must_use]
+    pub fn style(&self, id: LayerId) -> LayerStyle {
+        self.styles.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Replace the style of `id`.
+    pub fn set_style(&mut self, id: LayerId, style: LayerStyle) {
+        self.styles.insert(id, style);
+    }
+
+    /// Return a mutable reference to the style of `id`.
+    pub fn style_mut(&mut self, id: LayerId) -> &mut LayerStyle {
+        self.styles.entry(id).or_default()
+    }
+
+    /// Check if `id` is drawn.
+    #[must_use]
+    pub fn is_visible(&self, id: LayerId) -> bool {
+        self.style(id).visible
+    }
+
+    /// Show or hide `id`.
+    pub fn set_visible(&mut self, id: LayerId, visible: bool) {
+        self.style_mut(id).visible = visible;
+    }
+
+    /// Flip the visibility of `id` and return the new value.
+    pub fn toggle_visible(&mut self, id: LayerId) -> bool {
+        let style = self.style_mut(id);
+        style.visible = !style.visible;
+        style.visible
+    }
+}
+
+/// Write `cmds` into `out` with layers in z order.
+///
+/// Commands outside any layer come first, in their original order,
+/// followed by each layer block (from `BeginLayer` to its `EndLayer`)
+/// stably sorted by z, so layers with equal z keep their emission order.
+/// Layers nested in a layer are sorted the same way within their parent.
+///
+/// Hidden layers are dropped with everything inside them. A layer with
+/// a default blend has its contents wrapped in `PushBlend`/`PopBlend`.
+///
+/// Only layers outside any transform, blend or clip scope are moved;
+/// those opened inside one stay in place, since moving them would
+/// change their state. The result is only equivalent for lists whose
+/// scopes are balanced within each layer, as [`validate`](super::validate)
+/// checks; an unclosed layer runs to the end of the list.
+pub fn sort_layers(cmds: &[DrawCmd], out: &mut Vec<DrawCmd>, layers: &LayerSet) {
+    out.clear();
+    sort_level(cmds, out, layers);
+}
+
+/// Sort one nesting level of layers into `out`.
+fn sort_level(cmds: &[DrawCmd], out: &mut Vec<DrawCmd>, layers: &LayerSet) {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < cmds.len() {
+        let cmd = &cmds[i];
+        match cmd {
+            DrawCmd::BeginLayer(id) => {
+                let end = layer_end(cmds, i);
+                let block = (*id, &cmds[i + 1..end], end < cmds.len());
+                if depth == 0 {
+                    blocks.push(block);
+                } else {
+                    emit_layer(block, out, layers);
+                }
+                i = end + 1;
+                continue;
+            }
+            DrawCmd::PushTransform(_)
+            | DrawCmd::PushBlend(_)
+            | DrawCmd::PushClipRect(_)
+            | DrawCmd::PushClipCircle(_) => depth += 1,
+            DrawCmd::PopTransform | DrawCmd::PopBlend | DrawCmd::PopClip => {
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+        out.push(cmd.clone());
+        i += 1;
+    }
+    blocks.sort_by_key(|(id, ..)| id.z);
+    for block in blocks {
+        emit_layer(block, out, layers);
+    }
+}
+
+/// Return the index of the `EndLayer` closing the layer begun at
+/// `start`, or `cmds.len()` if it is never closed.
+fn layer_end(cmds: &[DrawCmd], start: usize) -> usize {
+    let mut open = 0usize;
+    for (i, cmd) in cmds.iter().enumerate().skip(start) {
+        match cmd {
+            DrawCmd::BeginLayer(_) => open += 1,
+            DrawCmd::EndLayer => {
+                open -= 1;
+                if open == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    cmds.len()
+}
+
+fn emit_layer(
+    (id, body, closed): (LayerId, &[DrawCmd], bool),
+    out: &mut Vec<DrawCmd>,
+    layers: &LayerSet,
+) {
+    let style = layers.style(id);
+    if !style.visible {
+        return;
+    }
+    out.push(DrawCmd::BeginLayer(id));
+    if let Some(mode) = style.blend {
+        out.push(DrawCmd::PushBlend(mode));
+    }
+    sort_level(body, out, layers);
+    if style.blend.is_some() {
+        out.push(DrawCmd::PopBlend);
+    }
+    if closed {
+        out.push(DrawCmd::EndLayer);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod layer_name {
+    use super::LayerId;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
+    use std::collections::HashSet;
+    use std::sync::{Mutex, OnceLock};
+
+    /// Deserialize a layer name into an interned `&'static str`.
+    ///
+    /// Games build layer names from literals, so the field stays
+    /// `&'static str` and deserialized names have to be leaked. The table
+    /// is capped in count and name length (see [`LayerId::name`]) so
+    /// untrusted input cannot leak memory without bound.
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<&'static str, D::Error> {
+        static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+        let name = String::deserialize(d)?;
+        let builtin = [
+            LayerId::BACKGROUND,
+            LayerId::PLAYFIELD,
+            LayerId::HUD,
+            LayerId::DEBUG,
+        ]
+        .into_iter()
+        .map(|id| id.name)
+        .find(|&n| n == name);
+        if let Some(builtin) = builtin {
+            return Ok(builtin);
+        }
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(&interned) = names.get(name.as_str()) {
+            return Ok(interned);
+        }
+        if name.len() > LayerId::MAX_NAME_LEN {
+            return Err(D::Error::custom(format_args!(
+                "layer name is longer than {} bytes",
+                LayerId::MAX_NAME_LEN
+            )));
+        }
+        if names.len() >= LayerId::MAX_INTERNED_NAMES {
+            return Err(D::Error::custom(format_args!(
+                "more than {} distinct layer names",
+                LayerId::MAX_INTERNED_NAMES
+            )));
+        }
+        let leaked: &'static str = Box::leak(name.into_boxed_str());
+        names.insert(leaked);
+        Ok(leaked)
+    }
+}
@@ -0,0 +1,304 @@
+//! Display-list builder.
+//!
+//! [`DrawList`] wraps a `Vec<DrawCmd>` with a current stroke, shape
+//! helpers, and scopes that pop themselves. Keep one per game and call
+//! [`DrawList::append_to`] (or [`DrawList::clear`]) each frame: the
+//! buffer's capacity survives, so steady-state frames do not allocate
+//! the command vector.
+//!
+//! ```
+//! use glam::Vec2;
+//! use vectorcade_shared::draw::{DrawList, Stroke};
+//! use vectorcade_shared::{Rgba, rot2, translate2};
+//!
+//! let mut list = DrawList::new();
+//! list.set_stroke(Stroke::new(Rgba::GREEN, 2.0));
+//! {
+//!     let mut ship = list.transform(translate2(Vec2::new(0.2, 0.0)) * rot2(0.3));
+//!     ship.regular_polygon(Vec2::ZERO, 0.05, 3, 0.0);
+//! } // PopTransform is pushed here.
+//! list.text(Vec2::new(-0.9, 0.9), "SCORE 100", 16.0);
+//!
+//! let mut out = Vec::new();
+//! list.append_to(&mut out);
+//! assert_eq!(out.len(), 4);
+//! ```
+
+use super::{BlendMode, DrawCmd, LayerId, LayerSet, Line2, Stroke, rect_wire};
+use crate::font::{FontStyleId, TextLayout};
+use glam::{Mat3, Vec2};
+use std::f32::consts::TAU;
+use std::ops::{Deref, DerefMut};
+use vectorcade_core::Rgba;
+use vectorcade_math::{Aabb, Circle};
+
+/// Reusable display-list builder with a current stroke and font style.
+#[derive(Clone, Debug)]
+pub struct DrawList {
+    cmds: Vec<DrawCmd>,
+    stroke: Stroke,
+    font_style: FontStyleId,
+}
+
+impl Default for DrawList {
+    fn default() -> Self {
+        Self {
+            cmds: Vec::new(),
+            stroke: Stroke::default(),
+            font_style: FontStyleId::DEFAULT,
+        }
+    }
+}
+
+impl DrawList {
+    /// Create an empty list with the default stroke and font style.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty list with room for `capacity` commands.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cmds: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Return the commands built so far.
+    #[must_use]
+    pub fn cmds(&self) -> &[DrawCmd] {
+        &self.cmds
+    }
+
+    /// Return the number of commands built so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cmds.len()
+    }
+
+    /// Check if no commands have been built.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty()
+    }
+
+    /// Remove all commands, keeping the allocation. The stroke and font
+    /// style are kept too.
+    pub fn clear(&mut self) {
+        self.cmds.clear();
+    }
+
+    /// Move all commands to the end of `out`, keeping this list's
+    /// allocation for the next frame.
+    pub fn append_to(&mut self, out: &mut Vec<DrawCmd>) {
+        out.append(&mut self.cmds);
+    }
+
+    /// Return the commands, consuming the list.
+    #[must_use]
+    pub fn into_cmds(self) -> Vec<DrawCmd> {
+        self.cmds
+    }
+
+    /// Return the stroke used by shape methods.
+    #[must_use]
+    pub fn stroke(&self) -> Stroke {
+        self.stroke
+    }
+
+    /// Set the stroke used by shape methods.
+    pub fn set_stroke(&mut self, stroke: Stroke) {
+        self.stroke = stroke;
+    }
+
+    /// Set the font style used by [`Self::text`].
+    pub fn set_font_style(&mut self, style: FontStyleId) {
+        self.font_style = style;
+    }
+
+    /// Append a raw command.
+    pub fn push(&mut self, cmd: DrawCmd) {
+        self.cmds.push(cmd);
+    }
+
+    /// Fill the whole screen with `color`.
+    pub fn fill_screen(&mut self, color: Rgba) {
+        self.push(DrawCmd::Clear { color });
+    }
+
+    /// Draw a line from `a` to `b`.
+    pub fn line(&mut self, a: Vec2, b: Vec2) {
+        let stroke = self.stroke;
+        self.push(DrawCmd::Line(Line2 { a, b, stroke }));
+    }
+
+    /// Draw connected segments through `pts`, back to the first point if
+    /// `closed`.
+    pub fn polyline(&mut self, pts: &[Vec2], closed: bool) {
+        self.push(DrawCmd::Polyline {
+            pts: pts.to_vec(),
+            closed,
+            stroke: self.stroke,
+        });
+    }
+
+    /// Draw an axis-aligned rectangle outline from `min` to `max`.
+    pub fn rect(&mut self, min: Vec2, max: Vec2) {
+        self.push(rect_wire(min, max, self.stroke));
+    }
+
+    /// Draw a circle outline.
+    pub fn circle(&mut self, center: Vec2, radius: f32) {
+        self.push(DrawCmd::Circle {
+            center,
+            radius,
+            stroke: self.stroke,
+        });
+    }
+
+    /// Draw `text` with its baseline origin at `pos`, in the stroke's
+    /// color and the current font style.
+    pub fn text(&mut self, pos: Vec2, text: &str, size_px: f32) {
+        self.push(DrawCmd::Text {
+            pos,
+            text: text.to_string(),
+            size_px,
+            color: self.stroke.color,
+            style: self.font_style,
+        });
+    }
+
+    /// Draw laid-out text as polylines in the current stroke.
+    pub fn text_layout(&mut self, layout: &TextLayout) {
+        self.cmds.extend(layout.polylines(self.stroke));
+    }
+
+    /// Draw a regular polygon with `sides` corners on a circle of
+    /// `radius`, the first at `rotation` radians counter-clockwise from
+    /// +X. Draws nothing with fewer than three sides.
+    pub fn regular_polygon(&mut self, center: Vec2, radius: f32, sides: u32, rotation: f32) {
+        if sides < 3 {
+            return;
+        }
+        let pts = ring(center, sides, rotation, |_| radius);
+        self.polyline(&pts, true);
+    }
+
+    /// Draw a star with `points` tips on a circle of `outer` radius and
+    /// notches between them at `inner` radius, the first tip at
+    /// `rotation` radians counter-clockwise from +X. Draws nothing with
+    /// fewer than two points.
+    pub fn star(&mut self, center: Vec2, outer: f32, inner: f32, points: u32, rotation: f32) {
+        if points < 2 {
+            return;
+        }
+        let pts = ring(center, points * 2, rotation, |i| {
+            if i % 2 == 0 { outer } else { inner }
+        });
+        self.polyline(&pts, true);
+    }
+
+    /// Push a transform, popped when the returned scope is dropped.
+    pub fn transform(&mut self, m: Mat3) -> Scope<'_> {
+        self.scope(DrawCmd::PushTransform(m), Some(DrawCmd::PopTransform))
+    }
+
+    /// Push a blend mode, popped when the returned scope is dropped.
+    pub fn blend(&mut self, mode: BlendMode) -> Scope<'_> {
+        self.scope(DrawCmd::PushBlend(mode), Some(DrawCmd::PopBlend))
+    }
+
+    /// Push a clip rectangle, popped when the returned scope is dropped.
+    pub fn clip_rect(&mut self, rect: Aabb) -> Scope<'_> {
+        self.scope(DrawCmd::PushClipRect(rect), Some(DrawCmd::PopClip))
+    }
+
+    /// Push a clip circle, popped when the returned scope is dropped.
+    pub fn clip_circle(&mut self, circle: Circle) -> Scope<'_> {
+        self.scope(DrawCmd::PushClipCircle(circle), Some(DrawCmd::PopClip))
+    }
+
+    /// Begin a layer, ended when the returned scope is dropped.
+    pub fn layer(&mut self, id: LayerId) -> Scope<'_> {
+        self.scope(DrawCmd::BeginLayer(id), Some(DrawCmd::EndLayer))
+    }
+
+    /// Begin a layer and switch to its default stroke in `layers`, if it
+    /// has one. The layer ends and the stroke is restored when the
+    /// returned scope is dropped.
+    pub fn styled_layer(&mut self, id: LayerId, layers: &LayerSet) -> Scope<'_> {
+        let stroke = layers.style(id).stroke;
+        let mut scope = self.layer(id);
+        if let Some(stroke) = stroke {
+            scope.set_stroke(stroke);
+        }
+        scope
+    }
+
+    /// Switch to `stroke` until the returned scope is dropped.
+    pub fn with_stroke(&mut self, stroke: Stroke) -> Scope<'_> {
+        let saved = std::mem::replace(&mut self.stroke, stroke);
+        Scope {
+            list: self,
+            pop: None,
+            stroke: saved,
+        }
+    }
+
+    fn scope(&mut self, push: DrawCmd, pop: Option<DrawCmd>) -> Scope<'_> {
+        self.push(push);
+        Scope {
+            stroke: self.stroke,
+            list: self,
+            pop,
+        }
+    }
+}
+
+/// A scope opened on a [`DrawList`].
+///
+/// Derefs to the list, so drawing and nested scopes go through it. When
+/// dropped, it pushes the matching pop command (if any) and restores the
+/// stroke that was current when it was opened.
+#[must_use = "the scope ends as soon as it is dropped"]
+pub struct Scope<'a> {
+    list: &'a mut DrawList,
+    pop: Option<DrawCmd>,
+    stroke: Stroke,
+}
+
+impl Deref for Scope<'_> {
+    type Target = DrawList;
+
+    fn deref(&self) -> &DrawList {
+        self.list
+    }
+}
+
+impl DerefMut for Scope<'_> {
+    fn deref_mut(&mut self) -> &mut DrawList {
+        self.list
+    }
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        if let Some(pop) = self.pop.take() {
+            self.list.push(pop);
+        }
+        self.list.stroke = self.stroke;
+    }
+}
+
+/// Return `n` points around `center`, the first at `rotation`, with the
+/// distance of point `i` given by `radius(i)`.
+fn ring(center: Vec2, n: u32, rotation: f32, radius: impl Fn(u32) -> f32) -> Vec<Vec2> {
+    (0..n)
+        .map(|i| {
+            let angle = rotation + TAU * i as f32 / n as f32;
+            center + Vec2::from_angle(angle) * radius(i)
+        })
+        .collect()
+}
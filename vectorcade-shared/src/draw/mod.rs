@@ -0,0 +1,365 @@
+//! Display-list drawing primitives.
+//!
+//! Games emit a `Vec<DrawCmd>` each frame describing what to render.
+//! The renderer consumes these commands to produce the final image.
+//!
+//! [`DrawList`] builds display lists with a current stroke, shape
+//! helpers and self-closing scopes.
+//!
+//! Shared display-list processing:
+//! - [`tessellate`] - Curves to polylines for backends without native curves
+//! - [`flatten`] - Canonical transform-stack semantics
+//! - [`optimize`] - Shorter equivalent lists
+//! - [`sort_layers`] - Layers in z order, with host visibility and blend
+//! - [`expand_stroke`] - Caps, joins and dashes as triangles
+//! - [`triangulate`](fn@crate::triangulate) - Filled polygons as triangles (from
+//!   `vectorcade-math`)
+//! - [`validate`] - Diagnostics for malformed lists
+
+mod blend;
+mod curve;
+mod flatten;
+mod gradient;
+mod layer;
+mod list;
+mod optimize;
+mod stroke;
+mod validate;
+
+pub use blend::BlendMode;
+pub use curve::{CURVE_TOLERANCE_PX, segments_for_radius, tessellate};
+pub use flatten::{FlattenError, flatten};
+pub use gradient::{StrokeVertex, trail};
+pub use layer::{LayerId, LayerSet, LayerStyle, sort_layers};
+pub use list::{DrawList, Scope};
+pub use optimize::{OptimizeOptions, OptimizeStats, optimize};
+pub use stroke::{MAX_DASHES, MIN_DASH_PX, MITER_LIMIT, StrokeMesh, dash_path, expand_stroke};
+pub use validate::{Diagnostic, DiagnosticKind, Severity, validate};
+
+use glam::{Mat3, Vec2};
+use vectorcade_core::Rgba;
+use vectorcade_math::{Aabb, Circle, FillRule};
+
+/// How the open ends of a stroke are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    /// End exactly at the end point.
+    Butt,
+    /// Half-circle around the end point (a round beam spot).
+    #[default]
+    Round,
+    /// Extend by half the width past the end point.
+    Square,
+}
+
+/// How a stroke turns a corner between segments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    /// Extend the outer edges to a point, falling back to `Bevel`
+    /// beyond [`MITER_LIMIT`].
+    Miter,
+    /// Cut the corner off with a straight edge.
+    Bevel,
+    /// Round the corner with an arc.
+    #[default]
+    Round,
+}
+
+/// Dash pattern for a stroke, measured in screen pixels along the path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dash {
+    /// Length of each drawn dash.
+    pub on_px: f32,
+    /// Length of each gap.
+    pub off_px: f32,
+    /// Distance into the pattern at which the path starts. Animating
+    /// this makes dashes crawl along the path.
+    pub phase_px: f32,
+}
+
+impl Dash {
+    /// Create a dash pattern.
+    #[must_use]
+    pub const fn new(on_px: f32, off_px: f32, phase_px: f32) -> Self {
+        Self {
+            on_px,
+            off_px,
+            phase_px,
+        }
+    }
+}
+
+/// Stroke style for vector lines.
+///
+/// Caps, joins and dashes default to round, round and solid: the look
+/// of a vector display's beam.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stroke {
+    pub color: Rgba,
+    /// Width in screen pixels (mimics vector display beam width).
+    pub width_px: f32,
+    /// Phosphor glow intensity hint (0.0 = none, 1.0 = full).
+    /// Renderer may ignore this; `vectorcade-render-cpu`'s phosphor
+    /// emulation is the reference interpretation.
+    pub glow: f32,
+    /// End style of open paths and dashes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cap: LineCap,
+    /// Corner style between segments.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub join: LineJoin,
+    /// Optional dash pattern; `None` draws a solid line.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dash: Option<Dash>,
+}
+
+impl Stroke {
+    /// Create a stroke with given color and width, no glow.
+    #[must_use]
+    pub const fn new(color: Rgba, width_px: f32) -> Self {
+        Self::with_glow(color, width_px, 0.0)
+    }
+
+    /// Create a stroke with glow effect.
+    #[must_use]
+    pub const fn with_glow(color: Rgba, width_px: f32, glow: f32) -> Self {
+        Self {
+            color,
+            width_px,
+            glow,
+            cap: LineCap::Round,
+            join: LineJoin::Round,
+            dash: None,
+        }
+    }
+
+    /// Return this stroke with a different cap style.
+    #[must_use]
+    pub const fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Return this stroke with a different join style.
+    #[must_use]
+    pub const fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Return this stroke with a dash pattern.
+    #[must_use]
+    pub const fn dashed(mut self, dash: Dash) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self::new(Rgba::WHITE, 1.0)
+    }
+}
+
+/// A line segment with stroke style.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line2 {
+    /// Start point of the line.
+    pub a: Vec2,
+    /// End point of the line.
+    pub b: Vec2,
+    /// Stroke style (color, width, glow).
+    pub stroke: Stroke,
+}
+
+/// Display-list command for vector rendering.
+///
+/// Games emit a `Vec<DrawCmd>` each frame. The renderer processes
+/// these commands in order to produce the final image.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawCmd {
+    /// Fill the entire screen with a solid color.
+    Clear {
+        /// Background color.
+        color: Rgba,
+    },
+
+    /// Draw a single line segment.
+    Line(Line2),
+
+    /// Draw a series of connected line segments.
+    Polyline {
+        /// Points forming the polyline path.
+        pts: Vec<Vec2>,
+        /// If true, connect the last point back to the first.
+        closed: bool,
+        /// Stroke style for all segments.
+        stroke: Stroke,
+    },
+
+    /// Draw connected segments whose color and width vary per vertex.
+    ///
+    /// Color and width are interpolated linearly along each segment.
+    /// Caps and joins are round and there is no dash pattern. See
+    /// [`trail`] for fading motion trails.
+    GradientPolyline {
+        /// Vertices along the path.
+        verts: Vec<StrokeVertex>,
+        /// If true, connect the last vertex back to the first.
+        closed: bool,
+        /// Phosphor glow intensity hint, as `Stroke::glow`.
+        glow: f32,
+    },
+
+    /// Fill the area enclosed by one or more contours.
+    ///
+    /// Contours are implicitly closed and may be concave. Nested contours
+    /// make holes (and islands inside holes) as decided by `rule`.
+    /// Backends without native polygon fill use
+    /// [`triangulate`](fn@crate::triangulate). Edges are antialiased but
+    /// not stroked; add a `Polyline` for an outline.
+    FillPolygon {
+        /// Closed contours, outline and holes alike.
+        contours: Vec<Vec<Vec2>>,
+        /// Which enclosed regions are filled.
+        rule: FillRule,
+        /// Fill color.
+        color: Rgba,
+    },
+
+    /// Draw a circle outline.
+    ///
+    /// Backends without native curves use [`tessellate`].
+    Circle {
+        /// Center point.
+        center: Vec2,
+        /// Radius in the same units as `center`.
+        radius: f32,
+        /// Stroke style.
+        stroke: Stroke,
+    },
+
+    /// Draw a circular arc.
+    ///
+    /// Angles are in radians, counter-clockwise from +X. The arc runs
+    /// from `start_angle` to `end_angle`, clockwise if `end_angle` is
+    /// smaller.
+    Arc {
+        /// Center point.
+        center: Vec2,
+        /// Radius in the same units as `center`.
+        radius: f32,
+        /// Angle of the first point.
+        start_angle: f32,
+        /// Angle of the last point.
+        end_angle: f32,
+        /// Stroke style.
+        stroke: Stroke,
+    },
+
+    /// Draw an ellipse outline.
+    Ellipse {
+        /// Center point.
+        center: Vec2,
+        /// Semi-axis lengths along the ellipse's local X and Y.
+        radii: Vec2,
+        /// Counter-clockwise rotation of the local X axis, in radians.
+        rotation: f32,
+        /// Stroke style.
+        stroke: Stroke,
+    },
+
+    /// Draw text using a vector font.
+    ///
+    /// Requires a font backend that implements the `VectorFont` trait.
+    Text {
+        /// Position of the text baseline origin.
+        pos: Vec2,
+        /// The text string to render.
+        text: String,
+        /// Font size in screen pixels.
+        size_px: f32,
+        /// Text color.
+        color: Rgba,
+        /// Font style (Atari, Midway, etc.).
+        style: crate::font::FontStyleId,
+    },
+
+    /// Push a transformation matrix onto the transform stack.
+    ///
+    /// Subsequent draw commands are transformed by this matrix,
+    /// composed with the enclosing transforms, until a matching
+    /// `PopTransform` is issued. See [`flatten`] for the exact rules.
+    PushTransform(Mat3),
+
+    /// Pop the most recent transformation from the stack.
+    PopTransform,
+
+    /// Draw subsequent commands with `BlendMode` until the matching
+    /// `PopBlend`. The list starts in [`BlendMode::Alpha`].
+    PushBlend(BlendMode),
+
+    /// Restore the blend mode in effect before the last `PushBlend`.
+    PopBlend,
+
+    /// Clip subsequent commands to a rectangle until the matching
+    /// `PopClip`.
+    ///
+    /// Clip regions are in NDC and ignore the transform stack, like a
+    /// scissor rectangle. Nested clips intersect. Strokes are clipped at
+    /// the region's edge, caps included.
+    PushClipRect(Aabb),
+
+    /// Clip subsequent commands to a circle until the matching
+    /// `PopClip`.
+    ///
+    /// As `PushClipRect`; on a non-square screen the circle is an
+    /// ellipse in pixels, like a `Circle` of the same radius.
+    PushClipCircle(Circle),
+
+    /// Remove the clip region added by the last `PushClipRect` or
+    /// `PushClipCircle`.
+    PopClip,
+
+    /// Begin a render layer, ended by the matching `EndLayer`.
+    ///
+    /// Renderers draw layers in list order; run [`sort_layers`] first to
+    /// order them by z and apply a [`LayerSet`].
+    BeginLayer(LayerId),
+
+    /// End the current render layer.
+    EndLayer,
+}
+
+/// Create a wireframe rectangle from corner coordinates.
+///
+/// Returns a closed `Polyline` forming a rectangle from `min` to `max`.
+///
+/// # Example
+///
+/// ```ignore
+/// let rect = rect_wire(
+///     Vec2::new(-0.5, -0.5),
+///     Vec2::new(0.5, 0.5),
+///     Stroke::new(Rgba::GREEN, 2.0),
+/// );
+/// ```
+#[must_use]
+pub fn rect_wire(min: Vec2, max: Vec2, stroke: Stroke) -> DrawCmd {
+    DrawCmd::Polyline {
+        pts: vec![
+            Vec2::new(min.x, min.y),
+            Vec2::new(max.x, min.y),
+            Vec2::new(max.x, max.y),
+            Vec2::new(min.x, max.y),
+        ],
+        closed: true,
+        stroke,
+    }
+}
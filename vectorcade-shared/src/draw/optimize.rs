@@ -0,0 +1,412 @@
+//! Display-list optimizer.
+//!
+//! [`optimize`] rewrites a display list into an equivalent, shorter one:
+//!
+//! - Runs of `Line` commands with identical strokes, where each line
+//!   starts at the previous line's end, are merged into one `Polyline`.
+//!   Interior points that lie on a straight run are dropped, and a chain
+//!   that returns to its start becomes a closed polyline. Only solid
+//!   strokes with round caps and joins are merged: a dash pattern would
+//!   run on across the old line ends instead of restarting, and other
+//!   caps and joins would trade per-line caps for join geometry. Lines
+//!   under [`BlendMode::Additive`] or with translucent colors are not
+//!   merged either, since separate lines blend twice where they meet and
+//!   a polyline blends once.
+//! - `PushTransform(Mat3::IDENTITY)` and its matching `PopTransform`
+//!   are dropped, as are transform, blend, clip and layer pairs with
+//!   nothing inside.
+//! - Repeated polyline points are removed, as are zero-length lines and
+//!   single-point polylines with butt caps. With round or square caps
+//!   those draw dots (stars, bullets), so they are kept.
+//! - Lines, polylines (plain or gradient), filled polygons and curves
+//!   whose transformed bounds, grown by how far their stroke reaches,
+//!   lie entirely outside the NDC viewport are culled.
+//!
+//! Text is never culled, since its extent depends on the font.
+
+use super::{BlendMode, DrawCmd, Line2, LineCap, LineJoin, MITER_LIMIT, Stroke};
+use crate::game::ScreenInfo;
+use glam::{Mat3, Vec2};
+use std::f32::consts::SQRT_2;
+
+/// Tolerance for treating three chained points as collinear.
+const COLLINEAR_EPS: f32 = 1e-6;
+
+/// Options for [`optimize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimizeOptions {
+    /// Merge chained `Line`s into `Polyline`s.
+    pub merge_lines: bool,
+    /// Cull geometry entirely outside the viewport.
+    pub cull: bool,
+    /// Extra NDC distance kept around the viewport when culling, to
+    /// allow for glow. Stroke width is allowed for already.
+    pub cull_margin: f32,
+    /// Screen the list is drawn on, used to convert stroke widths from
+    /// pixels to NDC when culling.
+    pub screen: ScreenInfo,
+}
+
+impl Default for OptimizeOptions {
+    fn default() -> Self {
+        Self {
+            merge_lines: true,
+            cull: true,
+            cull_margin: 0.0,
+            screen: ScreenInfo::default(),
+        }
+    }
+}
+
+/// Counts of what [`optimize`] changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OptimizeStats {
+    /// Commands in the input list.
+    pub input: usize,
+    /// Commands in the output list.
+    pub output: usize,
+    /// `Line` commands merged with their neighbors into longer strokes.
+    pub lines_merged: usize,
+    /// Invisible zero-length lines and repeated polyline points removed.
+    pub degenerate_removed: usize,
+    /// Geometry culled outside the viewport.
+    pub culled: usize,
+    /// Transform push/pop commands removed.
+    pub transforms_removed: usize,
+    /// Layer begin/end commands removed.
+    pub layers_removed: usize,
+    /// Blend push/pop commands removed.
+    pub blends_removed: usize,
+    /// Clip push/pop commands removed.
+    pub clips_removed: usize,
+}
+
+impl OptimizeStats {
+    /// Return how many commands the output saved over the input.
+    #[must_use]
+    pub fn saved(&self) -> usize {
+        self.input.saturating_sub(self.output)
+    }
+}
+
+/// Write an optimized copy of `cmds` into `out`.
+///
+/// `out` is cleared first, so a list kept across frames reuses its
+/// allocation.
+pub fn optimize(
+    cmds: &[DrawCmd],
+    out: &mut Vec<DrawCmd>,
+    options: &OptimizeOptions,
+) -> OptimizeStats {
+    out.clear();
+    let mut opt = Optimizer {
+        options: *options,
+        out,
+        stats: OptimizeStats {
+            input: cmds.len(),
+            ..OptimizeStats::default()
+        },
+        stack: vec![(Mat3::IDENTITY, true)],
+        blends: Vec::new(),
+        clips: 0,
+        chain: Vec::new(),
+        chain_stroke: Stroke::default(),
+        chain_lines: 0,
+    };
+    for cmd in cmds {
+        opt.push(cmd);
+    }
+    opt.flush_chain();
+    opt.stats.output = opt.out.len();
+    opt.stats
+}
+
+struct Optimizer<'a> {
+    options: OptimizeOptions,
+    out: &'a mut Vec<DrawCmd>,
+    stats: OptimizeStats,
+    /// Composed transform per push level, and whether its push was kept.
+    stack: Vec<(Mat3, bool)>,
+    /// Blend modes pushed and not yet popped.
+    blends: Vec<BlendMode>,
+    /// Clips pushed and not yet popped.
+    clips: usize,
+    chain: Vec<Vec2>,
+    chain_stroke: Stroke,
+    chain_lines: usize,
+}
+
+impl Optimizer<'_> {
+    fn push(&mut self, cmd: &DrawCmd) {
+        if let DrawCmd::Line(line) = cmd {
+            self.line(line);
+            return;
+        }
+        self.flush_chain();
+        match cmd {
+            DrawCmd::PushTransform(t) => {
+                let keep = *t != Mat3::IDENTITY;
+                self.stack.push((self.transform() * *t, keep));
+                if keep {
+                    self.out.push(cmd.clone());
+                } else {
+                    self.stats.transforms_removed += 1;
+                }
+            }
+            // An unbalanced pop is passed through for the validator to report.
+            DrawCmd::PopTransform if self.stack.len() == 1 => self.out.push(DrawCmd::PopTransform),
+            DrawCmd::PopTransform => match self.stack.pop() {
+                Some((_, false)) => self.stats.transforms_removed += 1,
+                _ => self.close(DrawCmd::PopTransform),
+            },
+            DrawCmd::PushBlend(mode) => {
+                self.blends.push(*mode);
+                self.out.push(cmd.clone());
+            }
+            DrawCmd::PopBlend if self.blends.is_empty() => self.out.push(DrawCmd::PopBlend),
+            DrawCmd::PopBlend => {
+                self.blends.pop();
+                self.close(DrawCmd::PopBlend);
+            }
+            DrawCmd::PushClipRect(_) | DrawCmd::PushClipCircle(_) => {
+                self.clips += 1;
+                self.out.push(cmd.clone());
+            }
+            DrawCmd::PopClip if self.clips == 0 => self.out.push(DrawCmd::PopClip),
+            DrawCmd::PopClip => {
+                self.clips -= 1;
+                self.close(DrawCmd::PopClip);
+            }
+            DrawCmd::EndLayer => self.close(DrawCmd::EndLayer),
+            DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke,
+            } => self.polyline(pts, *closed, *stroke),
+            DrawCmd::GradientPolyline { verts, .. } => {
+                let pts: Vec<Vec2> = verts.iter().map(|v| v.pos).collect();
+                let width = verts.iter().map(|v| v.width_px).fold(0.0, f32::max);
+                self.bounded(cmd, &pts, width * 0.5);
+            }
+            DrawCmd::FillPolygon { contours, .. } => {
+                let pts: Vec<Vec2> = contours.iter().flatten().copied().collect();
+                self.bounded(cmd, &pts, 0.0);
+            }
+            DrawCmd::Circle {
+                center,
+                radius,
+                stroke,
+            }
+            | DrawCmd::Arc {
+                center,
+                radius,
+                stroke,
+                ..
+            } => self.curve(cmd, *center, radius.abs(), stroke),
+            DrawCmd::Ellipse {
+                center,
+                radii,
+                stroke,
+                ..
+            } => self.curve(cmd, *center, radii.abs().max_element(), stroke),
+            _ => self.out.push(cmd.clone()),
+        }
+    }
+
+    fn transform(&self) -> Mat3 {
+        self.stack.last().map_or(Mat3::IDENTITY, |(m, _)| *m)
+    }
+
+    /// Emit a closing command, or drop it with its opener if the block
+    /// turned out empty.
+    fn close(&mut self, cmd: DrawCmd) {
+        let opener_is_last = match (&cmd, self.out.last()) {
+            (DrawCmd::PopTransform, Some(DrawCmd::PushTransform(_))) => {
+                self.stats.transforms_removed += 2;
+                true
+            }
+            (DrawCmd::PopBlend, Some(DrawCmd::PushBlend(_))) => {
+                self.stats.blends_removed += 2;
+                true
+            }
+            (DrawCmd::PopClip, Some(DrawCmd::PushClipRect(_) | DrawCmd::PushClipCircle(_))) => {
+                self.stats.clips_removed += 2;
+                true
+            }
+            (DrawCmd::EndLayer, Some(DrawCmd::BeginLayer(_))) => {
+                self.stats.layers_removed += 2;
+                true
+            }
+            _ => false,
+        };
+        if opener_is_last {
+            self.out.pop();
+        } else {
+            self.out.push(cmd);
+        }
+    }
+
+    fn line(&mut self, line: &Line2) {
+        if line.a == line.b && line.stroke.cap == LineCap::Butt {
+            self.stats.degenerate_removed += 1;
+            return;
+        }
+        if self.culled(&[line.a, line.b], reach_px(&line.stroke)) {
+            self.stats.culled += 1;
+            return;
+        }
+        if line.a == line.b {
+            // A dot: keep it out of chains, which would drop it.
+            self.flush_chain();
+            self.out.push(DrawCmd::Line(*line));
+            return;
+        }
+        let extends = self.options.merge_lines
+            && mergeable(&line.stroke)
+            && self.blends.last() != Some(&BlendMode::Additive)
+            && self.chain_lines > 0
+            && self.chain_stroke == line.stroke
+            && self.chain.last() == Some(&line.a);
+        if !extends {
+            self.flush_chain();
+            self.chain.push(line.a);
+            self.chain_stroke = line.stroke;
+        }
+        push_point(&mut self.chain, line.b);
+        self.chain_lines += 1;
+    }
+
+    fn flush_chain(&mut self) {
+        let lines = std::mem::take(&mut self.chain_lines);
+        if lines == 0 {
+            return;
+        }
+        if lines > 1 {
+            self.stats.lines_merged += lines;
+        }
+        let stroke = self.chain_stroke;
+        let mut pts = std::mem::take(&mut self.chain);
+        if let [a, b] = pts[..] {
+            self.out.push(DrawCmd::Line(Line2 { a, b, stroke }));
+            self.chain = pts;
+            self.chain.clear();
+            return;
+        }
+        let closed = pts.len() > 3 && pts.first() == pts.last();
+        if closed {
+            pts.pop();
+        }
+        self.out.push(DrawCmd::Polyline {
+            pts,
+            closed,
+            stroke,
+        });
+    }
+
+    fn polyline(&mut self, pts: &[Vec2], mut closed: bool, stroke: Stroke) {
+        let mut kept: Vec<Vec2> = Vec::with_capacity(pts.len());
+        for &p in pts {
+            if kept.last() != Some(&p) {
+                kept.push(p);
+            }
+        }
+        if closed && kept.len() > 1 && kept.first() == kept.last() {
+            kept.pop();
+        }
+        if let [p] = kept[..]
+            && stroke.cap != LineCap::Butt
+        {
+            // All points coincide: draw the dot as a zero-length segment.
+            kept.push(p);
+            closed = false;
+        }
+        self.stats.degenerate_removed += pts.len().saturating_sub(kept.len());
+        if kept.len() < 2 {
+            self.stats.degenerate_removed += 1;
+            return;
+        }
+        if self.culled(&kept, reach_px(&stroke)) {
+            self.stats.culled += 1;
+            return;
+        }
+        self.out.push(DrawCmd::Polyline {
+            pts: kept,
+            closed,
+            stroke,
+        });
+    }
+
+    /// Emit a curve unless the square bounding its largest radius is culled.
+    fn curve(&mut self, cmd: &DrawCmd, center: Vec2, radius: f32, stroke: &Stroke) {
+        let (r, s) = (Vec2::splat(radius), Vec2::new(radius, -radius));
+        let corners = [center - r, center - s, center + s, center + r];
+        self.bounded(cmd, &corners, reach_px(stroke));
+    }
+
+    /// Emit `cmd` unchanged unless the bounds of `pts`, grown by
+    /// `reach_px`, are culled.
+    fn bounded(&mut self, cmd: &DrawCmd, pts: &[Vec2], reach_px: f32) {
+        if self.culled(pts, reach_px) {
+            self.stats.culled += 1;
+        } else {
+            self.out.push(cmd.clone());
+        }
+    }
+
+    /// Check if the transformed bounds of `pts`, grown by `reach_px`
+    /// screen pixels and the cull margin, miss the viewport.
+    fn culled(&self, pts: &[Vec2], reach_px: f32) -> bool {
+        if !self.options.cull {
+            return false;
+        }
+        let m = self.transform();
+        let screen = self.options.screen;
+        let px = Vec2::new(
+            screen.width_px.max(1) as f32,
+            screen.height_px.max(1) as f32,
+        );
+        let limit = Vec2::ONE * (1.0 + self.options.cull_margin) + reach_px.max(0.0) * 2.0 / px;
+        let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+        for &p in pts {
+            let q = m.transform_point2(p);
+            min = min.min(q);
+            max = max.max(q);
+        }
+        min.x > limit.x || min.y > limit.y || max.x < -limit.x || max.y < -limit.y
+    }
+}
+
+/// Return how far beyond its path `stroke` can draw, in pixels: half
+/// its width, stretched for square-cap corners and miter tips.
+fn reach_px(stroke: &Stroke) -> f32 {
+    let half = stroke.width_px * 0.5;
+    if stroke.join == LineJoin::Miter {
+        half * MITER_LIMIT
+    } else if stroke.cap == LineCap::Square {
+        half * SQRT_2
+    } else {
+        half
+    }
+}
+
+/// Check if lines drawn with `stroke` look the same merged into a
+/// polyline: solid and opaque, with round caps and joins.
+fn mergeable(stroke: &Stroke) -> bool {
+    stroke.dash.is_none()
+        && stroke.color.3 >= 1.0
+        && stroke.cap == LineCap::Round
+        && stroke.join == LineJoin::Round
+}
+
+/// Append `p` to a chain, replacing the previous point if it lies on a
+/// straight run between its neighbor and `p`.
+fn push_point(chain: &mut Vec<Vec2>, p: Vec2) {
+    if let [.., a, b] = chain.as_slice() {
+        let (d0, d1) = (*b - *a, p - *b);
+        let scale = d0.length() * d1.length();
+        if d0.perp_dot(d1).abs() <= COLLINEAR_EPS * scale && d0.dot(d1) > 0.0 {
+            chain.pop();
+        }
+    }
+    chain.push(p);
+}
@@ -0,0 +1,248 @@
+//! Styled stroke expansion.
+//!
+//! [`expand_stroke`] turns a pixel-space path and its [`Stroke`] into
+//! triangles: one quad per segment, plus join and cap geometry, after
+//! splitting the path into dashes. Backends that cannot draw caps, joins
+//! or dashes natively fill these triangles so every backend draws the
+//! same shape. Triangles may overlap at joins; fill them as a union (for
+//! example with a stencil or coverage mask) to avoid double blending.
+
+use super::curve::segments_for_radius;
+use super::{Dash, LineCap, LineJoin, Stroke};
+use glam::Vec2;
+use std::f32::consts::{PI, TAU};
+
+/// Largest ratio of miter length to stroke width before a `Miter` join
+/// falls back to `Bevel`. Matches the SVG `stroke-miterlimit` default.
+pub const MITER_LIMIT: f32 = 4.0;
+
+/// Shortest dash or gap [`dash_path`] splits a path into. Finer patterns
+/// cannot be seen and draw solid.
+pub const MIN_DASH_PX: f32 = 0.01;
+
+/// Most dashes [`dash_path`] splits one path into. A pattern that would
+/// need more for the path's length draws solid, which also keeps every
+/// step along the path well above `f32` precision.
+pub const MAX_DASHES: usize = 10_000;
+
+/// Indexed triangle list produced by [`expand_stroke`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StrokeMesh {
+    /// Vertex positions in the same space as the input path.
+    pub vertices: Vec<Vec2>,
+    /// Three indices into `vertices` per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl StrokeMesh {
+    /// Create an empty mesh.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove all triangles, keeping the allocations.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+    }
+
+    /// Iterate over the triangles' corner positions.
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec2; 3]> + '_ {
+        self.indices.chunks_exact(3).map(|t| {
+            let v = |i: u32| self.vertices[i as usize];
+            [v(t[0]), v(t[1]), v(t[2])]
+        })
+    }
+
+    fn fan(&mut self, center: Vec2, rim: &[Vec2]) {
+        let base = self.vertices.len() as u32;
+        self.vertices.push(center);
+        self.vertices.extend_from_slice(rim);
+        for i in 1..rim.len() as u32 {
+            self.indices.extend([base, base + i, base + i + 1]);
+        }
+    }
+}
+
+/// Split a path into the dashes of `dash`.
+///
+/// Lengths are measured along the path in the same units as `pts`
+/// (pixels, for a [`Dash`] in a [`Stroke`]). For closed paths the
+/// closing segment is included and a dash running over the start point
+/// is kept in one piece. A pattern with no gap returns the whole path
+/// (with the first point repeated if closed), as does one with a dash or
+/// gap shorter than [`MIN_DASH_PX`] or needing more than [`MAX_DASHES`]
+/// dashes. A pattern with no dash, or with negative or non-finite
+/// lengths, returns nothing, as does an empty path.
+#[must_use]
+pub fn dash_path(pts: &[Vec2], closed: bool, dash: &Dash) -> Vec<Vec<Vec2>> {
+    let (on, off) = (dash.on_px, dash.off_px);
+    let period = on + off;
+    if pts.is_empty()
+        || !(on > 0.0 && off >= 0.0 && period.is_finite() && dash.phase_px.is_finite())
+    {
+        return Vec::new();
+    }
+    let mut path = pts.to_vec();
+    if closed && pts.len() > 2 {
+        path.push(pts[0]);
+    }
+    let length: f32 = path.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let shortest = on.min(off);
+    if shortest < MIN_DASH_PX || shortest * (MAX_DASHES as f32) < length {
+        return vec![path];
+    }
+
+    let pos = dash.phase_px.rem_euclid(period);
+    let mut drawing = pos < on;
+    let mut left = if drawing { on - pos } else { period - pos };
+    let starts_on = drawing;
+    let mut out = Vec::new();
+    let mut cur = if drawing { vec![path[0]] } else { Vec::new() };
+    for w in path.windows(2) {
+        let (mut a, b) = (w[0], w[1]);
+        let mut len = a.distance(b);
+        let dir = (b - a).normalize_or_zero();
+        while len > left {
+            a += dir * left;
+            len -= left;
+            cur.push(a);
+            if drawing {
+                out.push(std::mem::take(&mut cur));
+            }
+            drawing = !drawing;
+            left = if drawing { on } else { off };
+        }
+        left -= len;
+        if drawing {
+            cur.push(b);
+        }
+    }
+    let ends_on = drawing && cur.len() > 1;
+    if ends_on {
+        out.push(cur);
+    }
+    let wraps = closed && starts_on && ends_on && out.len() > 1;
+    if wraps {
+        let first = out.remove(0);
+        if let Some(last) = out.last_mut() {
+            last.extend_from_slice(&first[1..]);
+        }
+    }
+    out
+}
+
+/// Append the triangles covering `pts` drawn with `stroke` to `mesh`.
+///
+/// `pts` must be in pixel space, since widths and dash lengths are in
+/// pixels. A single point (or a path whose points all coincide) draws a
+/// dot with round or square caps and nothing with butt caps.
+pub fn expand_stroke(pts: &[Vec2], closed: bool, stroke: &Stroke, mesh: &mut StrokeMesh) {
+    let half = stroke.width_px * 0.5;
+    if half.is_nan() || half <= 0.0 {
+        return;
+    }
+    match &stroke.dash {
+        Some(dash) => {
+            for part in dash_path(pts, closed, dash) {
+                let ring = part.len() > 2 && part.first() == part.last();
+                expand_path(&part, ring, half, stroke, mesh);
+            }
+        }
+        None => expand_path(pts, closed, half, stroke, mesh),
+    }
+}
+
+fn expand_path(pts: &[Vec2], closed: bool, half: f32, stroke: &Stroke, mesh: &mut StrokeMesh) {
+    let mut path: Vec<Vec2> = Vec::with_capacity(pts.len());
+    for &p in pts {
+        if path.last() != Some(&p) {
+            path.push(p);
+        }
+    }
+    let closed = closed && path.len() > 2;
+    if closed && path.first() == path.last() {
+        path.pop();
+    }
+    match path[..] {
+        [] => return,
+        [p] => return dot(p, half, stroke.cap, mesh),
+        _ => {}
+    }
+
+    let n = path.len();
+    let segs = if closed { n } else { n - 1 };
+    for i in 0..segs {
+        let (a, b) = (path[i], path[(i + 1) % n]);
+        let side = (b - a).normalize().perp() * half;
+        mesh.fan(a + side, &[b + side, b - side, a - side]);
+    }
+    let joints = if closed { 0..n } else { 1..n - 1 };
+    for i in joints {
+        let prev = path[(i + n - 1) % n];
+        let (p, next) = (path[i], path[(i + 1) % n]);
+        join(prev, p, next, half, stroke.join, mesh);
+    }
+    if !closed {
+        cap(path[0], path[1], half, stroke.cap, mesh);
+        cap(path[n - 1], path[n - 2], half, stroke.cap, mesh);
+    }
+}
+
+/// Fill the outer gap at corner `p` between `prev -> p` and `p -> next`.
+fn join(prev: Vec2, p: Vec2, next: Vec2, half: f32, style: LineJoin, mesh: &mut StrokeMesh) {
+    let (d0, d1) = ((p - prev).normalize(), (next - p).normalize());
+    let turn = d0.perp_dot(d1);
+    if turn.abs() < 1e-6 && d0.dot(d1) > 0.0 {
+        return;
+    }
+    // The gap is on the right of a left turn and vice versa.
+    let side = if turn > 0.0 { -half } else { half };
+    let (o0, o1) = (d0.perp() * side, d1.perp() * side);
+    match style {
+        LineJoin::Round => arc(p, o0, o0.perp_dot(o1).atan2(o0.dot(o1)), mesh),
+        LineJoin::Miter => {
+            let mid = (o0 + o1).normalize_or_zero();
+            let cos = mid.dot(o0) / half;
+            if cos > 1.0 / MITER_LIMIT {
+                mesh.fan(p, &[p + o0, p + mid * (half / cos), p + o1]);
+            } else {
+                mesh.fan(p, &[p + o0, p + o1]);
+            }
+        }
+        LineJoin::Bevel => mesh.fan(p, &[p + o0, p + o1]),
+    }
+}
+
+/// Cap the path end `p`, whose neighbor along the path is `inner`.
+fn cap(p: Vec2, inner: Vec2, half: f32, style: LineCap, mesh: &mut StrokeMesh) {
+    let out = (p - inner).normalize() * half;
+    let side = out.perp();
+    match style {
+        LineCap::Butt => {}
+        LineCap::Round => arc(p, side, -PI, mesh),
+        LineCap::Square => mesh.fan(p + side, &[p + side + out, p - side + out, p - side]),
+    }
+}
+
+fn dot(p: Vec2, half: f32, style: LineCap, mesh: &mut StrokeMesh) {
+    match style {
+        LineCap::Butt => {}
+        LineCap::Round => arc(p, Vec2::new(half, 0.0), TAU, mesh),
+        LineCap::Square => {
+            let (a, b) = (Vec2::splat(half), Vec2::new(half, -half));
+            mesh.fan(p - a, &[p + b, p + a, p - b]);
+        }
+    }
+}
+
+/// Add a pie slice at `center` starting at offset `from` and turning
+/// counter-clockwise by `sweep` radians (clockwise if negative).
+fn arc(center: Vec2, from: Vec2, sweep: f32, mesh: &mut StrokeMesh) {
+    let segments = segments_for_radius(from.length(), sweep);
+    let rim: Vec<Vec2> = (0..=segments)
+        .map(|i| center + Vec2::from_angle(sweep * i as f32 / segments as f32).rotate(from))
+        .collect();
+    mesh.fan(center, &rim);
+}
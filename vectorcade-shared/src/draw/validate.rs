@@ -0,0 +1,346 @@
+//! Display-list validation.
+//!
+//! Renderers tolerate malformed lists (unbalanced pops are ignored, bad
+//! geometry draws garbage), so mistakes go unnoticed. [`validate`]
+//! reports them with the index of the offending command.
+
+use super::{DrawCmd, MIN_DASH_PX, Stroke};
+use glam::Vec2;
+use std::fmt;
+use vectorcade_core::Rgba;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Draws, but probably not as intended.
+    Warning,
+    /// Renderers disagree on or cannot draw the command.
+    Error,
+}
+
+/// The problem found by [`validate`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// `PopTransform` without a matching `PushTransform`.
+    UnbalancedPop,
+    /// `PushTransform` never popped.
+    UnclosedPush,
+    /// `PopBlend` without a matching `PushBlend`.
+    UnbalancedPopBlend,
+    /// `PushBlend` never popped.
+    UnclosedPushBlend,
+    /// `PopClip` without a matching push.
+    UnbalancedPopClip,
+    /// `PushClipRect` or `PushClipCircle` never popped.
+    UnclosedPushClip,
+    /// Clip rectangle with `min` above `max` on some axis, so nothing
+    /// inside it is drawn.
+    EmptyClip,
+    /// `EndLayer` without a matching `BeginLayer`.
+    UnbalancedEndLayer,
+    /// `BeginLayer` never ended.
+    UnclosedLayer,
+    /// `Polyline` with fewer than two points.
+    TooFewPoints {
+        /// Number of points given.
+        count: usize,
+    },
+    /// `FillPolygon` with no contour of three or more points.
+    EmptyFill,
+    /// A coordinate, matrix entry, size or color is NaN or infinite.
+    NonFinite,
+    /// `Stroke::width_px` below zero.
+    NegativeWidth {
+        /// The width given.
+        width_px: f32,
+    },
+    /// Curve radius below zero.
+    NegativeRadius {
+        /// The radius given.
+        radius: f32,
+    },
+    /// Dash lengths negative, non-finite, or with no drawn part.
+    InvalidDash,
+    /// Dash or gap shorter than [`MIN_DASH_PX`], so the pattern draws
+    /// solid.
+    DashTooFine,
+    /// Color alpha outside `0.0..=1.0`.
+    AlphaOutOfRange {
+        /// The alpha given.
+        alpha: f32,
+    },
+}
+
+impl DiagnosticKind {
+    /// Return the severity of this kind of problem.
+    #[must_use]
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::UnclosedLayer
+            | Self::TooFewPoints { .. }
+            | Self::EmptyFill
+            | Self::EmptyClip
+            | Self::DashTooFine
+            | Self::AlphaOutOfRange { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnbalancedPop => write!(f, "PopTransform without matching PushTransform"),
+            Self::UnclosedPush => write!(f, "PushTransform is never popped"),
+            Self::UnbalancedPopBlend => write!(f, "PopBlend without matching PushBlend"),
+            Self::UnclosedPushBlend => write!(f, "PushBlend is never popped"),
+            Self::UnbalancedPopClip => write!(f, "PopClip without matching push"),
+            Self::UnclosedPushClip => write!(f, "clip is never popped"),
+            Self::EmptyClip => write!(f, "clip rectangle is empty"),
+            Self::UnbalancedEndLayer => write!(f, "EndLayer without matching BeginLayer"),
+            Self::UnclosedLayer => write!(f, "BeginLayer is never ended"),
+            Self::TooFewPoints { count } => write!(f, "Polyline has {count} point(s), needs 2"),
+            Self::EmptyFill => write!(f, "FillPolygon has no contour of 3 or more points"),
+            Self::NonFinite => write!(f, "non-finite value"),
+            Self::NegativeWidth { width_px } => write!(f, "negative stroke width {width_px}"),
+            Self::NegativeRadius { radius } => write!(f, "negative radius {radius}"),
+            Self::InvalidDash => write!(f, "dash pattern draws nothing"),
+            Self::DashTooFine => write!(f, "dash pattern is too fine and draws solid"),
+            Self::AlphaOutOfRange { alpha } => write!(f, "alpha {alpha} outside 0..1"),
+        }
+    }
+}
+
+/// A problem found in a display list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Index of the offending command.
+    pub index: usize,
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// What is wrong.
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "command {}: {level}: {}", self.index, self.kind)
+    }
+}
+
+/// Check `cmds` and return every problem found, ordered by index.
+#[must_use]
+pub fn validate(cmds: &[DrawCmd]) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    let mut report = |index: usize, kind: DiagnosticKind| {
+        diags.push(Diagnostic {
+            index,
+            severity: kind.severity(),
+            kind,
+        });
+    };
+    let mut pushes = Vec::new();
+    let mut blends = Vec::new();
+    let mut clips = Vec::new();
+    let mut layers = Vec::new();
+    for (i, cmd) in cmds.iter().enumerate() {
+        match cmd {
+            DrawCmd::Clear { color } => check_color(i, *color, &mut report),
+            DrawCmd::Line(l) => {
+                check_points(i, &[l.a, l.b], &mut report);
+                check_stroke(i, &l.stroke, &mut report);
+            }
+            DrawCmd::Polyline { pts, stroke, .. } => {
+                if pts.len() < 2 {
+                    report(i, DiagnosticKind::TooFewPoints { count: pts.len() });
+                }
+                check_points(i, pts, &mut report);
+                check_stroke(i, stroke, &mut report);
+            }
+            DrawCmd::GradientPolyline { verts, glow, .. } => {
+                if verts.len() < 2 {
+                    report(i, DiagnosticKind::TooFewPoints { count: verts.len() });
+                }
+                // Report each kind once, not once per vertex.
+                let mut seen = Vec::new();
+                let mut once = |i, kind: DiagnosticKind| {
+                    let d = std::mem::discriminant(&kind);
+                    if !seen.contains(&d) {
+                        seen.push(d);
+                        report(i, kind);
+                    }
+                };
+                let pts: Vec<Vec2> = verts.iter().map(|v| v.pos).collect();
+                check_points(i, &pts, &mut once);
+                for v in verts {
+                    let stroke = Stroke::with_glow(v.color, v.width_px, *glow);
+                    check_stroke(i, &stroke, &mut once);
+                }
+            }
+            DrawCmd::FillPolygon {
+                contours, color, ..
+            } => {
+                if contours.iter().all(|c| c.len() < 3) {
+                    report(i, DiagnosticKind::EmptyFill);
+                }
+                if !contours.iter().flatten().all(|p| p.is_finite()) {
+                    report(i, DiagnosticKind::NonFinite);
+                }
+                check_color(i, *color, &mut report);
+            }
+            DrawCmd::Circle {
+                center,
+                radius,
+                stroke,
+            } => {
+                check_curve(i, *center, Vec2::splat(*radius), &[], &mut report);
+                check_stroke(i, stroke, &mut report);
+            }
+            DrawCmd::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                stroke,
+            } => {
+                let angles = [*start_angle, *end_angle];
+                check_curve(i, *center, Vec2::splat(*radius), &angles, &mut report);
+                check_stroke(i, stroke, &mut report);
+            }
+            DrawCmd::Ellipse {
+                center,
+                radii,
+                rotation,
+                stroke,
+            } => {
+                check_curve(i, *center, *radii, &[*rotation], &mut report);
+                check_stroke(i, stroke, &mut report);
+            }
+            DrawCmd::Text {
+                pos,
+                size_px,
+                color,
+                ..
+            } => {
+                if !(pos.is_finite() && size_px.is_finite()) {
+                    report(i, DiagnosticKind::NonFinite);
+                }
+                check_color(i, *color, &mut report);
+            }
+            DrawCmd::PushTransform(m) => {
+                if !m.is_finite() {
+                    report(i, DiagnosticKind::NonFinite);
+                }
+                pushes.push(i);
+            }
+            DrawCmd::PopTransform => {
+                if pushes.pop().is_none() {
+                    report(i, DiagnosticKind::UnbalancedPop);
+                }
+            }
+            DrawCmd::PushBlend(_) => blends.push(i),
+            DrawCmd::PopBlend => {
+                if blends.pop().is_none() {
+                    report(i, DiagnosticKind::UnbalancedPopBlend);
+                }
+            }
+            DrawCmd::PushClipRect(r) => {
+                if !(r.min.is_finite() && r.max.is_finite()) {
+                    report(i, DiagnosticKind::NonFinite);
+                } else if r.min.cmpgt(r.max).any() {
+                    report(i, DiagnosticKind::EmptyClip);
+                }
+                clips.push(i);
+            }
+            DrawCmd::PushClipCircle(c) => {
+                check_curve(i, c.center, Vec2::splat(c.radius), &[], &mut report);
+                clips.push(i);
+            }
+            DrawCmd::PopClip => {
+                if clips.pop().is_none() {
+                    report(i, DiagnosticKind::UnbalancedPopClip);
+                }
+            }
+            DrawCmd::BeginLayer(_) => layers.push(i),
+            DrawCmd::EndLayer => {
+                if layers.pop().is_none() {
+                    report(i, DiagnosticKind::UnbalancedEndLayer);
+                }
+            }
+        }
+    }
+    for i in pushes {
+        report(i, DiagnosticKind::UnclosedPush);
+    }
+    for i in blends {
+        report(i, DiagnosticKind::UnclosedPushBlend);
+    }
+    for i in clips {
+        report(i, DiagnosticKind::UnclosedPushClip);
+    }
+    for i in layers {
+        report(i, DiagnosticKind::UnclosedLayer);
+    }
+    diags.sort_by_key(|d| d.index);
+    diags
+}
+
+fn check_points(i: usize, pts: &[Vec2], report: &mut impl FnMut(usize, DiagnosticKind)) {
+    if !pts.iter().all(|p| p.is_finite()) {
+        report(i, DiagnosticKind::NonFinite);
+    }
+}
+
+/// Check a curve's center, radii, and angles.
+fn check_curve(
+    i: usize,
+    center: Vec2,
+    radii: Vec2,
+    angles: &[f32],
+    report: &mut impl FnMut(usize, DiagnosticKind),
+) {
+    if !(center.is_finite() && radii.is_finite() && angles.iter().all(|a| a.is_finite())) {
+        report(i, DiagnosticKind::NonFinite);
+    } else if radii.min_element() < 0.0 {
+        report(
+            i,
+            DiagnosticKind::NegativeRadius {
+                radius: radii.min_element(),
+            },
+        );
+    }
+}
+
+fn check_stroke(i: usize, stroke: &Stroke, report: &mut impl FnMut(usize, DiagnosticKind)) {
+    if !(stroke.width_px.is_finite() && stroke.glow.is_finite()) {
+        report(i, DiagnosticKind::NonFinite);
+    } else if stroke.width_px < 0.0 {
+        report(
+            i,
+            DiagnosticKind::NegativeWidth {
+                width_px: stroke.width_px,
+            },
+        );
+    }
+    if let Some(d) = stroke.dash {
+        let lengths = [d.on_px, d.off_px, d.phase_px];
+        if !(lengths.iter().all(|v| v.is_finite()) && d.on_px > 0.0 && d.off_px >= 0.0) {
+            report(i, DiagnosticKind::InvalidDash);
+        } else if d.on_px < MIN_DASH_PX || (d.off_px > 0.0 && d.off_px < MIN_DASH_PX) {
+            report(i, DiagnosticKind::DashTooFine);
+        }
+    }
+    check_color(i, stroke.color, report);
+}
+
+fn check_color(i: usize, c: Rgba, report: &mut impl FnMut(usize, DiagnosticKind)) {
+    if ![c.0, c.1, c.2, c.3].iter().all(|v| v.is_finite()) {
+        report(i, DiagnosticKind::NonFinite);
+    } else if !(0.0..=1.0).contains(&c.3) {
+        report(i, DiagnosticKind::AlphaOutOfRange { alpha: c.3 });
+    }
+}
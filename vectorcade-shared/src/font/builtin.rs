@@ -0,0 +1,132 @@
+//! Built-in single-stroke font.
+//!
+//! A small simplex font in the spirit of the Hershey fonts and the
+//! vector arcade character sets: uppercase letters, digits and common
+//! punctuation, each drawn with a few straight strokes. It is original
+//! to this crate and shares its license, so tests and headless tools can
+//! render text without a font dependency.
+
+use super::{FontStyleId, GlyphPath, GlyphPathCmd, VectorFont};
+use glam::Vec2;
+
+/// Grid units per em. Glyphs sit on a 4 x 6 grid above the baseline.
+const GRID: f32 = 6.0;
+
+/// Horizontal advance of every glyph, in font units.
+const ADVANCE: f32 = 1.0;
+
+/// Glyph strokes on the grid, sorted by character.
+///
+/// Strokes are separated by spaces; each is a run of `xy` digit pairs
+/// joined by lines, with `y` up from the baseline. A stroke with the
+/// same point twice is a dot.
+const GLYPHS: &[(char, &str)] = &[
+    ('!', "2622 2020"),
+    ('"', "1615 3635"),
+    ('\'', "2625"),
+    ('(', "36141230"),
+    (')', "16343210"),
+    ('*', "1234 1432 2521"),
+    ('+', "1333 2422"),
+    (',', "2110"),
+    ('-', "1333"),
+    ('.', "2020"),
+    ('/', "0046"),
+    ('0', "0006464000 0046"),
+    ('1', "152620 1030"),
+    ('2', "064643030040"),
+    ('3', "06464000 0343"),
+    ('4', "060343 4640"),
+    ('5', "4606033342413000"),
+    ('6', "460600404303"),
+    ('7', "064610"),
+    ('8', "0006464000 0343"),
+    ('9', "4303064640"),
+    (':', "2424 2121"),
+    (';', "2424 2110"),
+    ('<', "351331"),
+    ('=', "0242 0444"),
+    ('>', "153311"),
+    ('?', "05163645442322 2020"),
+    ('A', "0004264440 0343"),
+    ('B', "00063645443303 3342413000"),
+    ('C', "40000646"),
+    ('D', "00062644422000"),
+    ('E', "40000646 0333"),
+    ('F', "000646 0333"),
+    ('G', "45460600404222"),
+    ('H', "0006 4046 0343"),
+    ('I', "0646 2026 0040"),
+    ('J', "0110304146"),
+    ('K', "0600 460340"),
+    ('L', "060040"),
+    ('M', "0006244640"),
+    ('N', "00064046"),
+    ('O', "0006464000"),
+    ('P', "0006464303"),
+    ('Q', "000646422000 2240"),
+    ('R', "0006464303 1340"),
+    ('S', "460603434000"),
+    ('T', "0646 2620"),
+    ('U', "06004046"),
+    ('V', "062046"),
+    ('W', "0600224046"),
+    ('X', "0046 0640"),
+    ('Y', "062346 2320"),
+    ('Z', "06464000"),
+    ('[', "36262030"),
+    (']', "16262010"),
+    ('_', "0040"),
+];
+
+/// The built-in single-stroke font, for [`FontStyleId::DEFAULT`].
+///
+/// Covers `A`-`Z` (lowercase is drawn as uppercase), `0`-`9`, space and
+/// `! " ' ( ) * + , - . / : ; < = > ? [ ] _`. Glyphs are 2/3 em wide and
+/// one em tall, with a fixed advance of one em. Other characters have
+/// no glyph and draw nothing, but still advance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuiltinFont;
+
+impl BuiltinFont {
+    fn strokes(ch: char) -> Option<&'static str> {
+        let ch = ch.to_ascii_uppercase();
+        GLYPHS
+            .binary_search_by_key(&ch, |&(c, _)| c)
+            .ok()
+            .map(|i| GLYPHS[i].1)
+    }
+}
+
+impl VectorFont for BuiltinFont {
+    fn style_id(&self) -> FontStyleId {
+        FontStyleId::DEFAULT
+    }
+
+    fn has_glyph(&self, ch: char) -> bool {
+        ch == ' ' || Self::strokes(ch).is_some()
+    }
+
+    fn glyph_paths(&self, ch: char) -> Vec<GlyphPath> {
+        let Some(strokes) = Self::strokes(ch) else {
+            return Vec::new();
+        };
+        let mut cmds = Vec::new();
+        for stroke in strokes.split(' ') {
+            let digits: Vec<f32> = stroke.bytes().map(|b| f32::from(b - b'0') / GRID).collect();
+            for (i, p) in digits.chunks_exact(2).enumerate() {
+                let p = Vec2::new(p[0], p[1]);
+                cmds.push(if i == 0 {
+                    GlyphPathCmd::MoveTo(p)
+                } else {
+                    GlyphPathCmd::LineTo(p)
+                });
+            }
+        }
+        vec![GlyphPath { cmds }]
+    }
+
+    fn advance(&self, _ch: char) -> f32 {
+        ADVANCE
+    }
+}
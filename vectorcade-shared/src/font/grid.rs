@@ -0,0 +1,363 @@
+//! Grid-based text format for designing stroke fonts.
+//!
+//! Glyphs are strokes through integer points on a small grid, one
+//! directive per line:
+//!
+//! ```text
+//! # Asteroids-style capitals.
+//! grid 4 6
+//! style 1
+//! advance 6
+//!
+//! glyph A
+//!   stroke 0,0 0,4 2,6 4,4 4,0
+//!   stroke 0,3 4,3
+//! glyph I advance 5
+//!   stroke 0,6 4,6
+//!   stroke 2,6 2,0
+//!   stroke 0,0 4,0
+//! glyph U+0020
+//! ```
+//!
+//! `grid <width> <height>` comes first. It may be followed by
+//! `style <id>` ([`FontStyleId`] number, default 0), `baseline <row>`
+//! (grid row of the baseline, default 0; rows below it hold
+//! descenders) and `advance <units>` (default advance, default the grid
+//! width). Each `glyph` names one character, literally or as `U+XXXX`,
+//! with an optional `advance` override, and owns the `stroke` lines
+//! after it. A one-point stroke is a dot. Blank lines, indentation and
+//! `#` comment lines are ignored.
+//!
+//! One em is the height above the baseline, so `grid 4 6` glyphs are
+//! 2/3 em wide. [`GridFont::to_text`] writes the same format back out.
+
+use super::{FontStyleId, GlyphPath, GlyphPathCmd, VectorFont};
+use glam::{IVec2, Vec2};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+/// The problem found by [`GridFont::parse`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridFontErrorKind {
+    /// The line starts with an unknown keyword.
+    UnknownKeyword,
+    /// The directive has missing or extra arguments.
+    BadArguments,
+    /// An argument is not a non-negative integer.
+    BadNumber,
+    /// A glyph is neither a single character nor `U+XXXX`.
+    BadChar,
+    /// The grid is empty, or the baseline is not a row inside it.
+    BadGrid,
+    /// The first directive is not `grid`, or the file has none.
+    MissingGrid,
+    /// A second `grid`, or a header directive after the first glyph.
+    MisplacedHeader,
+    /// The character already has a glyph.
+    DuplicateGlyph {
+        /// The repeated character.
+        ch: char,
+    },
+    /// A `stroke` before the first `glyph`.
+    StrokeOutsideGlyph,
+    /// A `stroke` with no points.
+    EmptyStroke,
+    /// A point is not written as `x,y`.
+    BadPoint,
+    /// A point lies outside the grid.
+    PointOutsideGrid {
+        /// Column of the point.
+        x: i32,
+        /// Row of the point.
+        y: i32,
+    },
+}
+
+impl fmt::Display for GridFontErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKeyword => write!(f, "unknown keyword"),
+            Self::BadArguments => write!(f, "wrong number of arguments"),
+            Self::BadNumber => write!(f, "expected a non-negative integer"),
+            Self::BadChar => write!(f, "expected a character or U+XXXX"),
+            Self::BadGrid => write!(f, "grid is empty or baseline is outside it"),
+            Self::MissingGrid => write!(f, "expected grid first"),
+            Self::MisplacedHeader => write!(f, "header directive after grid or glyphs"),
+            Self::DuplicateGlyph { ch } => write!(f, "duplicate glyph {ch:?}"),
+            Self::StrokeOutsideGlyph => write!(f, "stroke before the first glyph"),
+            Self::EmptyStroke => write!(f, "stroke has no points"),
+            Self::BadPoint => write!(f, "expected a point as x,y"),
+            Self::PointOutsideGrid { x, y } => write!(f, "point {x},{y} is outside the grid"),
+        }
+    }
+}
+
+/// Error returned when parsing a grid font.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridFontError {
+    /// Line of the problem, from 1.
+    pub line: usize,
+    /// What is wrong.
+    pub kind: GridFontErrorKind,
+}
+
+impl fmt::Display for GridFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for GridFontError {}
+
+/// One glyph of a [`GridFont`], in grid units with Y up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GridGlyph {
+    /// Advance override, or `None` for the font's default.
+    pub advance: Option<i32>,
+    /// Strokes as runs of points; a one-point stroke is a dot.
+    pub strokes: Vec<Vec<IVec2>>,
+}
+
+/// A stroke font defined on an integer grid.
+///
+/// Fields are public so tools can edit fonts directly; [`Self::parse`]
+/// and [`Self::to_text`] convert to and from the text format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridFont {
+    /// Style reported by [`VectorFont::style_id`].
+    pub style: FontStyleId,
+    /// Grid columns; points have `x` in `0..=width`.
+    pub width: i32,
+    /// Grid rows; points have `y` in `0..=height`.
+    pub height: i32,
+    /// Row of the baseline, below `height`. One em is `height - baseline`.
+    pub baseline: i32,
+    /// Default advance in grid units.
+    pub advance: i32,
+    /// Glyphs by character.
+    pub glyphs: BTreeMap<char, GridGlyph>,
+}
+
+impl GridFont {
+    /// Create an empty font of `style` on a `width` x `height` grid, with
+    /// the baseline on row 0 and an advance of `width`.
+    #[must_use]
+    pub fn new(style: FontStyleId, width: i32, height: i32) -> Self {
+        Self {
+            style,
+            width,
+            height,
+            baseline: 0,
+            advance: width,
+            glyphs: BTreeMap::new(),
+        }
+    }
+
+    /// Parse a font from the grid text format.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridFontError`] with the line number of the first
+    /// malformed directive.
+    pub fn parse(src: &str) -> Result<Self, GridFontError> {
+        let mut font: Option<Self> = None;
+        let mut current: Option<char> = None;
+        for (i, text) in src.lines().enumerate() {
+            let text = text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let err = |kind| GridFontError { line: i + 1, kind };
+            let mut words = text.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let args: Vec<&str> = words.collect();
+            match (keyword, font.as_mut()) {
+                ("grid", None) => font = Some(grid(&args).map_err(err)?),
+                ("grid", Some(_)) => return Err(err(GridFontErrorKind::MisplacedHeader)),
+                ("style" | "baseline" | "advance" | "glyph" | "stroke", None) => {
+                    return Err(err(GridFontErrorKind::MissingGrid));
+                }
+                ("style" | "baseline" | "advance", Some(_)) if current.is_some() => {
+                    return Err(err(GridFontErrorKind::MisplacedHeader));
+                }
+                ("style", Some(f)) => f.style = FontStyleId(single(&args).map_err(err)? as u32),
+                ("baseline", Some(f)) => {
+                    f.baseline = single(&args).map_err(err)?;
+                    if f.baseline >= f.height {
+                        return Err(err(GridFontErrorKind::BadGrid));
+                    }
+                }
+                ("advance", Some(f)) => f.advance = single(&args).map_err(err)?,
+                ("glyph", Some(f)) => {
+                    let (ch, glyph) = glyph(&args).map_err(err)?;
+                    if f.glyphs.insert(ch, glyph).is_some() {
+                        return Err(err(GridFontErrorKind::DuplicateGlyph { ch }));
+                    }
+                    current = Some(ch);
+                }
+                ("stroke", Some(f)) => {
+                    let stroke = f.stroke(&args).map_err(err)?;
+                    let glyph = current
+                        .and_then(|ch| f.glyphs.get_mut(&ch))
+                        .ok_or(err(GridFontErrorKind::StrokeOutsideGlyph))?;
+                    glyph.strokes.push(stroke);
+                }
+                _ => return Err(err(GridFontErrorKind::UnknownKeyword)),
+            }
+        }
+        font.ok_or(GridFontError {
+            line: 1,
+            kind: GridFontErrorKind::MissingGrid,
+        })
+    }
+
+    /// Write the font in the grid text format.
+    ///
+    /// Every header directive is written, glyphs come in character
+    /// order, and empty strokes are skipped, so parsing the output
+    /// gives back an equal font (unless it had empty strokes).
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "grid {} {}", self.width, self.height);
+        let _ = writeln!(out, "style {}", self.style.0);
+        let _ = writeln!(out, "baseline {}", self.baseline);
+        let _ = writeln!(out, "advance {}", self.advance);
+        for (&ch, glyph) in &self.glyphs {
+            out.push('\n');
+            if ch.is_whitespace() || ch.is_control() {
+                let _ = write!(out, "glyph U+{:04X}", u32::from(ch));
+            } else {
+                let _ = write!(out, "glyph {ch}");
+            }
+            if let Some(advance) = glyph.advance {
+                let _ = write!(out, " advance {advance}");
+            }
+            out.push('\n');
+            for stroke in glyph.strokes.iter().filter(|s| !s.is_empty()) {
+                out.push_str("  stroke");
+                for p in stroke {
+                    let _ = write!(out, " {},{}", p.x, p.y);
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Height of one em in grid units.
+    fn em(&self) -> f32 {
+        (self.height - self.baseline) as f32
+    }
+
+    /// Parse the points of a `stroke` line.
+    fn stroke(&self, args: &[&str]) -> Result<Vec<IVec2>, GridFontErrorKind> {
+        if args.is_empty() {
+            return Err(GridFontErrorKind::EmptyStroke);
+        }
+        args.iter()
+            .map(|arg| {
+                let (x, y) = arg.split_once(',').ok_or(GridFontErrorKind::BadPoint)?;
+                let parse = |s: &str| s.parse::<i32>().map_err(|_| GridFontErrorKind::BadPoint);
+                let p = IVec2::new(parse(x)?, parse(y)?);
+                if (0..=self.width).contains(&p.x) && (0..=self.height).contains(&p.y) {
+                    Ok(p)
+                } else {
+                    Err(GridFontErrorKind::PointOutsideGrid { x: p.x, y: p.y })
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parse the arguments of a `grid` line into an empty font.
+fn grid(args: &[&str]) -> Result<GridFont, GridFontErrorKind> {
+    let [w, h] = args[..] else {
+        return Err(GridFontErrorKind::BadArguments);
+    };
+    let (width, height) = (number(w)?, number(h)?);
+    if width == 0 || height == 0 {
+        return Err(GridFontErrorKind::BadGrid);
+    }
+    Ok(GridFont::new(FontStyleId::DEFAULT, width, height))
+}
+
+/// Parse the arguments of a `glyph` line.
+fn glyph(args: &[&str]) -> Result<(char, GridGlyph), GridFontErrorKind> {
+    let (name, advance) = match args[..] {
+        [name] => (name, None),
+        [name, "advance", n] => (name, Some(number(n)?)),
+        _ => return Err(GridFontErrorKind::BadArguments),
+    };
+    let mut chars = name.chars();
+    let ch = match (chars.next(), chars.next()) {
+        (Some(ch), None) => ch,
+        _ => name
+            .strip_prefix("U+")
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or(GridFontErrorKind::BadChar)?,
+    };
+    let glyph = GridGlyph {
+        advance,
+        strokes: Vec::new(),
+    };
+    Ok((ch, glyph))
+}
+
+/// Parse a directive's only argument as a number.
+fn single(args: &[&str]) -> Result<i32, GridFontErrorKind> {
+    match args[..] {
+        [n] => number(n),
+        _ => Err(GridFontErrorKind::BadArguments),
+    }
+}
+
+fn number(s: &str) -> Result<i32, GridFontErrorKind> {
+    s.parse::<i32>()
+        .ok()
+        .filter(|&n| n >= 0)
+        .ok_or(GridFontErrorKind::BadNumber)
+}
+
+impl VectorFont for GridFont {
+    fn style_id(&self) -> FontStyleId {
+        self.style
+    }
+
+    fn has_glyph(&self, ch: char) -> bool {
+        self.glyphs.contains_key(&ch)
+    }
+
+    fn glyph_paths(&self, ch: char) -> Vec<GlyphPath> {
+        let Some(glyph) = self.glyphs.get(&ch) else {
+            return Vec::new();
+        };
+        let em = self.em();
+        let to_font = |p: &IVec2| Vec2::new(p.x as f32, (p.y - self.baseline) as f32) / em;
+        let mut cmds = Vec::new();
+        for stroke in glyph.strokes.iter().filter(|s| !s.is_empty()) {
+            cmds.push(GlyphPathCmd::MoveTo(to_font(&stroke[0])));
+            // A lone point is drawn as a zero-length line: a dot.
+            let rest = if stroke.len() == 1 {
+                stroke
+            } else {
+                &stroke[1..]
+            };
+            cmds.extend(rest.iter().map(|p| GlyphPathCmd::LineTo(to_font(p))));
+        }
+        if cmds.is_empty() {
+            return Vec::new();
+        }
+        vec![GlyphPath { cmds }]
+    }
+
+    fn advance(&self, ch: char) -> f32 {
+        let advance = self
+            .glyphs
+            .get(&ch)
+            .and_then(|g| g.advance)
+            .unwrap_or(self.advance);
+        advance as f32 / self.em()
+    }
+}
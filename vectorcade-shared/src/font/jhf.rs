@@ -0,0 +1,320 @@
+//! Hershey `.jhf` font files.
+//!
+//! Each record in a JHF file is one glyph:
+//!
+//! | Columns | Contents                                             |
+//! |---------|------------------------------------------------------|
+//! | 0..5    | glyph number, right-aligned                          |
+//! | 5..8    | vertex count, right-aligned, including the bounds    |
+//! | 8..10   | left and right bounds                                |
+//! | 10..    | `x y` coordinate pairs; the pair `" R"` lifts the pen |
+//!
+//! Coordinates are ASCII characters offset from `'R'`, so `'R'` is 0 and
+//! `'M'` is -5, with Y down. Long records wrap onto the following lines.
+//! The distributed files list glyphs in ASCII order from space, so
+//! [`JhfFont`] maps records to characters by position, not by number.
+
+use super::{FontStyleId, GlyphPath, GlyphPathCmd, VectorFont};
+use glam::{IVec2, Vec2};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Pen-up marker in the coordinate data.
+const PEN_UP: [u8; 2] = *b" R";
+
+/// The problem found by [`parse_jhf`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JhfErrorKind {
+    /// The line contains non-ASCII characters.
+    NotAscii,
+    /// The glyph number in columns 0..5 is not an integer.
+    BadNumber,
+    /// The vertex count in columns 5..8 is not a positive integer.
+    BadVertexCount,
+    /// The file ended before the record's vertices.
+    Truncated {
+        /// Vertices the record declared, including the bounds.
+        expected: usize,
+        /// Vertices found.
+        found: usize,
+    },
+    /// A coordinate is not a printable ASCII character.
+    BadCoordinate {
+        /// The character found.
+        ch: char,
+    },
+    /// The line has data after the record's last vertex.
+    TrailingData,
+}
+
+impl fmt::Display for JhfErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAscii => write!(f, "line is not ASCII"),
+            Self::BadNumber => write!(f, "glyph number is not an integer"),
+            Self::BadVertexCount => write!(f, "vertex count is not a positive integer"),
+            Self::Truncated { expected, found } => {
+                write!(f, "record has {found} of {expected} vertices")
+            }
+            Self::BadCoordinate { ch } => write!(f, "invalid coordinate {ch:?}"),
+            Self::TrailingData => write!(f, "unexpected data after last vertex"),
+        }
+    }
+}
+
+/// Error returned when parsing a JHF file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JhfError {
+    /// Line of the problem, from 1. For a truncated record, the line
+    /// the record starts on.
+    pub line: usize,
+    /// What is wrong.
+    pub kind: JhfErrorKind,
+}
+
+impl fmt::Display for JhfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for JhfError {}
+
+/// One glyph record, in Hershey units (Y down).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JhfRecord {
+    /// Glyph number from columns 0..5.
+    pub number: u32,
+    /// Left bound; the glyph origin.
+    pub left: i32,
+    /// Right bound; `right - left` is the advance.
+    pub right: i32,
+    /// Pen-down runs of points. Empty runs are dropped.
+    pub strokes: Vec<Vec<IVec2>>,
+}
+
+/// Parse every record in a JHF file. Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns a [`JhfError`] with the line number of the first malformed
+/// record.
+pub fn parse_jhf(src: &str) -> Result<Vec<JhfRecord>, JhfError> {
+    let mut lines = src
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i + 1, text.strip_suffix('\r').unwrap_or(text)));
+    let mut records = Vec::new();
+    while let Some((line, text)) = lines.next() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let err = |kind| JhfError { line, kind };
+        if !text.is_ascii() {
+            return Err(err(JhfErrorKind::NotAscii));
+        }
+        let number = text
+            .get(..5)
+            .and_then(|s| s.trim().parse().ok())
+            .ok_or(err(JhfErrorKind::BadNumber))?;
+        let count: usize = text
+            .get(5..8)
+            .and_then(|s| s.trim().parse().ok())
+            .filter(|&n| n > 0)
+            .ok_or(err(JhfErrorKind::BadVertexCount))?;
+
+        // Gather `2 * count` data bytes, each tagged with its line.
+        let mut data: Vec<(usize, u8)> = text.bytes().skip(8).map(|b| (line, b)).collect();
+        let mut last = line;
+        while data.len() < 2 * count {
+            let Some((next, text)) = lines.next() else {
+                return Err(err(JhfErrorKind::Truncated {
+                    expected: count,
+                    found: data.len() / 2,
+                }));
+            };
+            if !text.is_ascii() {
+                return Err(JhfError {
+                    line: next,
+                    kind: JhfErrorKind::NotAscii,
+                });
+            }
+            data.extend(text.bytes().map(|b| (next, b)));
+            last = next;
+        }
+        if data[2 * count..]
+            .iter()
+            .any(|(_, b)| !b.is_ascii_whitespace())
+        {
+            return Err(JhfError {
+                line: last,
+                kind: JhfErrorKind::TrailingData,
+            });
+        }
+        data.truncate(2 * count);
+        records.push(record(number, &data)?);
+    }
+    Ok(records)
+}
+
+/// Decode the bounds and strokes of a record from its tagged data,
+/// which holds at least one pair.
+fn record(number: u32, data: &[(usize, u8)]) -> Result<JhfRecord, JhfError> {
+    let mut pairs = Vec::with_capacity(data.len() / 2);
+    for pair in data.chunks_exact(2) {
+        let [(line, x), (_, y)] = [pair[0], pair[1]];
+        if let Some(b) = [x, y].into_iter().find(|b| !(b' '..=b'~').contains(b)) {
+            return Err(JhfError {
+                line,
+                kind: JhfErrorKind::BadCoordinate { ch: char::from(b) },
+            });
+        }
+        pairs.push([x, y]);
+    }
+    let [left, right] = pairs[0];
+    let mut strokes = Vec::new();
+    let mut current = Vec::new();
+    for &pair in &pairs[1..] {
+        if pair == PEN_UP {
+            strokes.push(std::mem::take(&mut current));
+        } else {
+            current.push(IVec2::new(offset(pair[0]), offset(pair[1])));
+        }
+    }
+    strokes.push(current);
+    strokes.retain(|s| !s.is_empty());
+    Ok(JhfRecord {
+        number,
+        left: offset(left),
+        right: offset(right),
+        strokes,
+    })
+}
+
+fn offset(b: u8) -> i32 {
+    i32::from(b) - i32::from(b'R')
+}
+
+/// How [`JhfFont`] maps Hershey units to characters and font units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JhfOptions {
+    /// Character of the first record; later records follow in order.
+    pub first_char: char,
+    /// Hershey Y of the baseline.
+    pub baseline: i32,
+    /// Hershey units per em.
+    pub em: f32,
+}
+
+impl Default for JhfOptions {
+    /// Space first, with the baseline at 9 and 21 units per em, which
+    /// puts the capitals of the Simplex fonts (tops at -12) one em tall.
+    fn default() -> Self {
+        Self {
+            first_char: ' ',
+            baseline: 9,
+            em: 21.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct JhfGlyph {
+    path: GlyphPath,
+    advance: f32,
+}
+
+/// A [`VectorFont`] loaded from a JHF file.
+///
+/// Characters without a record have no paths and advance like a space
+/// (or not at all if the file has no space).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JhfFont {
+    style: FontStyleId,
+    glyphs: HashMap<char, JhfGlyph>,
+}
+
+impl JhfFont {
+    /// Parse a JHF file into a font of `style`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`JhfError`] if the file is malformed.
+    pub fn parse(src: &str, style: FontStyleId, options: JhfOptions) -> Result<Self, JhfError> {
+        Ok(Self::from_records(&parse_jhf(src)?, style, options))
+    }
+
+    /// Build a font of `style` from parsed records.
+    ///
+    /// Records past the last valid `char` are ignored.
+    #[must_use]
+    pub fn from_records(records: &[JhfRecord], style: FontStyleId, options: JhfOptions) -> Self {
+        let to_font = |p: IVec2, left: i32| {
+            Vec2::new((p.x - left) as f32, (options.baseline - p.y) as f32) / options.em
+        };
+        let glyphs = records
+            .iter()
+            .zip(u32::from(options.first_char)..)
+            .filter_map(|(rec, code)| Some((char::from_u32(code)?, rec)))
+            .map(|(ch, rec)| {
+                let cmds = rec
+                    .strokes
+                    .iter()
+                    .flat_map(|stroke| {
+                        stroke.iter().enumerate().map(|(i, &p)| {
+                            let p = to_font(p, rec.left);
+                            if i == 0 {
+                                GlyphPathCmd::MoveTo(p)
+                            } else {
+                                GlyphPathCmd::LineTo(p)
+                            }
+                        })
+                    })
+                    .collect();
+                let glyph = JhfGlyph {
+                    path: GlyphPath { cmds },
+                    advance: (rec.right - rec.left) as f32 / options.em,
+                };
+                (ch, glyph)
+            })
+            .collect();
+        Self { style, glyphs }
+    }
+
+    /// Return the number of glyphs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Check if the font has no glyphs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+impl VectorFont for JhfFont {
+    fn style_id(&self) -> FontStyleId {
+        self.style
+    }
+
+    fn has_glyph(&self, ch: char) -> bool {
+        self.glyphs.contains_key(&ch)
+    }
+
+    fn glyph_paths(&self, ch: char) -> Vec<GlyphPath> {
+        self.glyphs
+            .get(&ch)
+            .filter(|g| !g.path.cmds.is_empty())
+            .map(|g| vec![g.path.clone()])
+            .unwrap_or_default()
+    }
+
+    fn advance(&self, ch: char) -> f32 {
+        self.glyphs
+            .get(&ch)
+            .or_else(|| self.glyphs.get(&' '))
+            .map_or(0.0, |g| g.advance)
+    }
+}
@@ -0,0 +1,344 @@
+//! Text layout on top of [`VectorFont`].
+//!
+//! [`layout_text`] places the glyphs of a string: it word-wraps to a
+//! maximum width, breaks lines at `\n`, applies letter and line spacing,
+//! and aligns and anchors the block around an origin. The result holds
+//! the glyph paths already moved into place, ready to become `Polyline`
+//! commands with [`TextLayout::polylines`].
+//!
+//! Layout works in a Y-up space in the units of the origin and size
+//! (NDC, pixels flipped to Y-up, or anything else with a uniform
+//! scale). Glyphs are assumed to span one em from the baseline up, as
+//! font units (`0..1`) suggest.
+
+use super::{GlyphPath, GlyphPathCmd, VectorFont};
+use crate::draw::{DrawCmd, Stroke};
+use glam::Vec2;
+use vectorcade_math::Aabb;
+
+/// Horizontal placement of each line relative to the origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Lines start at the origin.
+    #[default]
+    Left,
+    /// Lines are centered on the origin.
+    Center,
+    /// Lines end at the origin.
+    Right,
+}
+
+/// Vertical placement of the text block relative to the origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// The origin is at the top of the first line.
+    Top,
+    /// The origin is halfway between the top of the first line and the
+    /// baseline of the last.
+    Middle,
+    /// The origin is on the first line's baseline.
+    #[default]
+    Baseline,
+}
+
+/// How [`layout_text`] sizes and places text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle {
+    /// Height of one em in output units; font units are scaled by this.
+    pub size: f32,
+    /// Horizontal alignment of each line.
+    pub align: Align,
+    /// Vertical anchoring of the block.
+    pub anchor: Anchor,
+    /// Width in output units at which lines wrap between words, or
+    /// `None` to break only at `\n`. A word wider than this overflows
+    /// on a line of its own.
+    pub max_width: Option<f32>,
+    /// Extra space between glyphs, in ems.
+    pub letter_spacing: f32,
+    /// Distance between baselines, in ems.
+    pub line_spacing: f32,
+}
+
+impl TextStyle {
+    /// Default distance between baselines, in ems.
+    pub const DEFAULT_LINE_SPACING: f32 = 1.5;
+
+    /// Create a left-aligned, baseline-anchored style of `size`, with no
+    /// wrapping or extra letter spacing.
+    #[must_use]
+    pub const fn new(size: f32) -> Self {
+        Self {
+            size,
+            align: Align::Left,
+            anchor: Anchor::Baseline,
+            max_width: None,
+            letter_spacing: 0.0,
+            line_spacing: Self::DEFAULT_LINE_SPACING,
+        }
+    }
+
+    /// Return this style with a different alignment.
+    #[must_use]
+    pub const fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Return this style with a different anchor.
+    #[must_use]
+    pub const fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Return this style wrapping at `max_width`.
+    #[must_use]
+    pub const fn wrap(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Return this style with extra `ems` between glyphs.
+    #[must_use]
+    pub const fn letter_spacing(mut self, ems: f32) -> Self {
+        self.letter_spacing = ems;
+        self
+    }
+
+    /// Return this style with baselines `ems` apart.
+    #[must_use]
+    pub const fn line_spacing(mut self, ems: f32) -> Self {
+        self.line_spacing = ems;
+        self
+    }
+}
+
+/// A glyph placed by [`layout_text`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionedGlyph {
+    /// The character drawn.
+    pub ch: char,
+    /// Index of the line the glyph is on, from 0.
+    pub line: usize,
+    /// Baseline origin of the glyph.
+    pub origin: Vec2,
+    /// Horizontal advance to the next glyph, letter spacing included.
+    pub advance: f32,
+    /// Glyph paths in output coordinates.
+    pub paths: Vec<GlyphPath>,
+}
+
+/// Text placed by [`layout_text`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLayout {
+    /// Glyphs in reading order. Spaces dropped at wrap points are
+    /// omitted.
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Number of lines, counting empty ones.
+    pub lines: usize,
+    /// Box from the top of the first line to the baseline of the last,
+    /// spanning the widest line; a zero-size box at the origin for empty
+    /// text.
+    pub bounds: Aabb,
+}
+
+impl TextLayout {
+    /// Return every glyph path, in reading order.
+    pub fn paths(&self) -> impl Iterator<Item = &GlyphPath> + '_ {
+        self.glyphs.iter().flat_map(|g| &g.paths)
+    }
+
+    /// Return one `Polyline` per glyph sub-path, drawn with `stroke`.
+    #[must_use]
+    pub fn polylines(&self, stroke: Stroke) -> Vec<DrawCmd> {
+        self.paths()
+            .flat_map(path_polylines)
+            .map(|(pts, closed)| DrawCmd::Polyline {
+                pts,
+                closed,
+                stroke,
+            })
+            .collect()
+    }
+}
+
+/// Split a glyph path into its sub-paths and whether each is closed.
+///
+/// Sub-paths with fewer than two points are dropped.
+#[must_use]
+pub fn path_polylines(path: &GlyphPath) -> Vec<(Vec<Vec2>, bool)> {
+    let mut out = Vec::new();
+    let mut current: Vec<Vec2> = Vec::new();
+    let mut closed = false;
+    let mut flush = |current: &mut Vec<Vec2>, closed: &mut bool| {
+        if current.len() > 1 {
+            out.push((std::mem::take(current), *closed));
+        }
+        current.clear();
+        *closed = false;
+    };
+    for cmd in &path.cmds {
+        match cmd {
+            GlyphPathCmd::MoveTo(p) => {
+                flush(&mut current, &mut closed);
+                current.push(*p);
+            }
+            GlyphPathCmd::LineTo(p) => current.push(*p),
+            GlyphPathCmd::Close => {
+                closed = true;
+                flush(&mut current, &mut closed);
+            }
+        }
+    }
+    flush(&mut current, &mut closed);
+    out
+}
+
+/// Return the width and height of `text` as [`layout_text`] would
+/// place it, without building glyph paths.
+#[must_use]
+pub fn measure_text(font: &dyn VectorFont, text: &str, style: &TextStyle) -> Vec2 {
+    let lines = wrap_lines(font, text, style);
+    let width = lines
+        .iter()
+        .map(|l| line_width(font, l, style))
+        .fold(0.0, f32::max);
+    Vec2::new(width, block_height(lines.len(), style))
+}
+
+/// Lay out `text` around `origin` with `style`.
+#[must_use]
+pub fn layout_text(
+    font: &dyn VectorFont,
+    text: &str,
+    origin: Vec2,
+    style: &TextStyle,
+) -> TextLayout {
+    let lines = wrap_lines(font, text, style);
+    let size = style.size;
+    let line_step = style.line_spacing * size;
+    let first_baseline = match style.anchor {
+        Anchor::Top => origin.y - size,
+        Anchor::Middle => origin.y + block_height(lines.len(), style) * 0.5 - size,
+        Anchor::Baseline => origin.y,
+    };
+    let mut out = TextLayout {
+        glyphs: Vec::new(),
+        lines: lines.len(),
+        bounds: Aabb::from_min_max(origin, origin),
+    };
+    for (i, line) in lines.iter().enumerate() {
+        let width = line_width(font, line, style);
+        let shift = match style.align {
+            Align::Left => 0.0,
+            Align::Center => width * 0.5,
+            Align::Right => width,
+        };
+        let baseline = first_baseline - i as f32 * line_step;
+        let mut pen = Vec2::new(origin.x - shift, baseline);
+        let line_box = Aabb::from_min_max(pen, pen + Vec2::new(width, size));
+        out.bounds = if i == 0 {
+            line_box
+        } else {
+            out.bounds.union(&line_box)
+        };
+        for ch in line.chars() {
+            let advance = advance(font, ch, style);
+            let paths = font
+                .glyph_paths(ch)
+                .into_iter()
+                .map(|path| place(&path, pen, size))
+                .collect();
+            out.glyphs.push(PositionedGlyph {
+                ch,
+                line: i,
+                origin: pen,
+                advance,
+                paths,
+            });
+            pen.x += advance;
+        }
+    }
+    out
+}
+
+/// Move a glyph path from font units to output units at `origin`.
+fn place(path: &GlyphPath, origin: Vec2, size: f32) -> GlyphPath {
+    let to_out = |p: Vec2| origin + p * size;
+    GlyphPath {
+        cmds: path
+            .cmds
+            .iter()
+            .map(|cmd| match cmd {
+                GlyphPathCmd::MoveTo(p) => GlyphPathCmd::MoveTo(to_out(*p)),
+                GlyphPathCmd::LineTo(p) => GlyphPathCmd::LineTo(to_out(*p)),
+                GlyphPathCmd::Close => GlyphPathCmd::Close,
+            })
+            .collect(),
+    }
+}
+
+/// Advance of `ch` in output units, letter spacing included.
+fn advance(font: &dyn VectorFont, ch: char, style: &TextStyle) -> f32 {
+    (font.advance(ch) + style.letter_spacing) * style.size
+}
+
+/// Width of a line: the sum of its advances, without letter spacing
+/// after the last glyph.
+fn line_width(font: &dyn VectorFont, line: &str, style: &TextStyle) -> f32 {
+    if line.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = line.chars().map(|ch| advance(font, ch, style)).sum();
+    sum - style.letter_spacing * style.size
+}
+
+/// Height from the top of the first line to the baseline of the last.
+fn block_height(lines: usize, style: &TextStyle) -> f32 {
+    match lines {
+        0 => 0.0,
+        n => style.size + (n - 1) as f32 * style.line_spacing * style.size,
+    }
+}
+
+/// Split `text` into lines at `\n` and, if `style` wraps, between
+/// words. Spaces at wrap points are dropped.
+fn wrap_lines<'a>(font: &dyn VectorFont, text: &'a str, style: &TextStyle) -> Vec<&'a str> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let paragraph = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+        let Some(max) = style.max_width else {
+            lines.push(paragraph);
+            continue;
+        };
+        // `start` is where the current line begins and `end` where its
+        // last accepted word ends.
+        let mut start = 0;
+        let mut end = 0;
+        for (at, word) in words(paragraph) {
+            let candidate = &paragraph[start..at + word.len()];
+            if end > start && line_width(font, candidate, style) > max {
+                lines.push(&paragraph[start..end]);
+                start = at;
+            }
+            end = at + word.len();
+        }
+        lines.push(paragraph[start..].trim_end_matches(' '));
+    }
+    lines
+}
+
+/// Return each space-separated word of `line` with its byte offset.
+fn words(line: &str) -> impl Iterator<Item = (usize, &str)> + '_ {
+    line.split(' ')
+        .scan(0, |at, word| {
+            let item = (*at, word);
+            *at += word.len() + 1;
+            Some(item)
+        })
+        .filter(|(_, word)| !word.is_empty())
+}
@@ -0,0 +1,159 @@
+//! Single-line text measurement.
+//!
+//! [`TextMeasurer`] answers how wide a string is, where its strokes
+//! actually reach, and where a caret goes between characters. Each
+//! measurer is bound to one font and caches its glyph metrics, so
+//! measuring the same HUD strings every frame calls into the font only
+//! once per glyph.
+//!
+//! Results are in pixels at the given `size_px`, relative to the text's
+//! baseline origin, with Y up as in font units. For multi-line text,
+//! use [`layout_text`](super::layout_text).
+
+use super::{GlyphPathCmd, VectorFont};
+use glam::Vec2;
+use std::collections::HashMap;
+use std::fmt;
+use vectorcade_math::Aabb;
+
+/// Metrics of one glyph in font units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphMetrics {
+    /// Horizontal advance to the next glyph.
+    pub advance: f32,
+    /// Bounds of the glyph's path points relative to its origin, or
+    /// `None` if it draws nothing (like a space).
+    pub ink: Option<Aabb>,
+}
+
+impl GlyphMetrics {
+    /// Compute the metrics of `ch` from `font`'s advance and paths.
+    #[must_use]
+    pub fn of(font: &dyn VectorFont, ch: char) -> Self {
+        let ink = font
+            .glyph_paths(ch)
+            .iter()
+            .flat_map(|path| &path.cmds)
+            .filter_map(|cmd| match cmd {
+                GlyphPathCmd::MoveTo(p) | GlyphPathCmd::LineTo(p) => Some(*p),
+                GlyphPathCmd::Close => None,
+            })
+            .map(|p| Aabb::from_min_max(p, p))
+            .reduce(|a, b| a.union(&b));
+        Self {
+            advance: font.advance(ch),
+            ink,
+        }
+    }
+}
+
+/// Measurements of a line of text, in pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextMetrics {
+    /// Sum of the glyph advances.
+    pub advance: f32,
+    /// Tight bounds of the glyph path points, or `None` if nothing is
+    /// drawn. Stroke width is not included.
+    pub ink: Option<Aabb>,
+    /// Caret x positions: before each character, then after the last,
+    /// so one more entry than there are characters.
+    pub carets: Vec<f32>,
+}
+
+/// Measures text in one font with a per-glyph metrics cache.
+///
+/// Binding the font at construction keeps fonts that share a
+/// [`VectorFont::style_id`] from reading each other's metrics. Call
+/// [`Self::clear`] if the font's glyphs change.
+#[derive(Clone)]
+pub struct TextMeasurer<'f> {
+    font: &'f dyn VectorFont,
+    glyphs: HashMap<char, GlyphMetrics>,
+}
+
+impl fmt::Debug for TextMeasurer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextMeasurer")
+            .field("style", &self.font.style_id())
+            .field("cached", &self.glyphs.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'f> TextMeasurer<'f> {
+    /// Create a measurer for `font` with an empty cache.
+    #[must_use]
+    pub fn new(font: &'f dyn VectorFont) -> Self {
+        Self {
+            font,
+            glyphs: HashMap::new(),
+        }
+    }
+
+    /// Return the font being measured.
+    #[must_use]
+    pub fn font(&self) -> &'f dyn VectorFont {
+        self.font
+    }
+
+    /// Return the number of cached glyphs.
+    #[must_use]
+    pub fn cached(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Forget all cached glyphs.
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+    }
+
+    /// Return the metrics of `ch`, in font units.
+    pub fn glyph(&mut self, ch: char) -> GlyphMetrics {
+        let font = self.font;
+        *self
+            .glyphs
+            .entry(ch)
+            .or_insert_with(|| GlyphMetrics::of(font, ch))
+    }
+
+    /// Return the advance width of `text` at `size_px`.
+    pub fn advance_width(&mut self, text: &str, size_px: f32) -> f32 {
+        text.chars()
+            .map(|ch| self.glyph(ch).advance * size_px)
+            .sum()
+    }
+
+    /// Return the tight bounds of the strokes of `text` at `size_px`, or
+    /// `None` if it draws nothing.
+    pub fn ink_bounds(&mut self, text: &str, size_px: f32) -> Option<Aabb> {
+        self.measure(text, size_px).ink
+    }
+
+    /// Return the caret x positions of `text` at `size_px`.
+    pub fn caret_positions(&mut self, text: &str, size_px: f32) -> Vec<f32> {
+        self.measure(text, size_px).carets
+    }
+
+    /// Return all measurements of `text` at `size_px`.
+    pub fn measure(&mut self, text: &str, size_px: f32) -> TextMetrics {
+        let mut pen = 0.0;
+        let mut ink: Option<Aabb> = None;
+        let mut carets = Vec::with_capacity(text.len() + 1);
+        carets.push(0.0);
+        for ch in text.chars() {
+            let glyph = self.glyph(ch);
+            if let Some(g) = glyph.ink {
+                let offset = Vec2::new(pen, 0.0);
+                let placed = Aabb::from_min_max(offset + g.min * size_px, offset + g.max * size_px);
+                ink = Some(ink.map_or(placed, |i| i.union(&placed)));
+            }
+            pen += glyph.advance * size_px;
+            carets.push(pen);
+        }
+        TextMetrics {
+            advance: pen,
+            ink,
+            carets,
+        }
+    }
+}
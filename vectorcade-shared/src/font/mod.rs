@@ -1,7 +1,30 @@
 //! Vector font traits and glyph path types.
 //!
 //! This module defines the interface for stroke-based vector fonts.
-//! Concrete font implementations live in the `vectorcade-fonts` crate.
+//! Concrete font implementations live in the `vectorcade-fonts` crate;
+//! [`BuiltinFont`] is a small single-stroke font compiled in for tests
+//! and tools that need real glyphs without that dependency, and
+//! [`JhfFont`] loads Hershey `.jhf` files. [`GridFont`] reads and writes
+//! a grid-based text format for designing new fonts by hand.
+//!
+//! [`layout_text`] places strings in a font: alignment, anchoring, word
+//! wrap, and letter and line spacing. [`TextMeasurer`] measures single
+//! lines (advance, ink bounds, caret positions) with a glyph cache.
+
+mod builtin;
+mod grid;
+mod jhf;
+mod layout;
+mod measure;
+
+pub use builtin::BuiltinFont;
+pub use grid::{GridFont, GridFontError, GridFontErrorKind, GridGlyph};
+pub use jhf::{JhfError, JhfErrorKind, JhfFont, JhfOptions, JhfRecord, parse_jhf};
+pub use layout::{
+    Align, Anchor, PositionedGlyph, TextLayout, TextStyle, layout_text, measure_text,
+    path_polylines,
+};
+pub use measure::{GlyphMetrics, TextMeasurer, TextMetrics};
 
 use glam::Vec2;
 
@@ -14,6 +37,7 @@ use glam::Vec2;
 ///
 /// Font providers may support multiple styles.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FontStyleId(pub u32);
 
 impl FontStyleId {
@@ -31,6 +55,7 @@ impl FontStyleId {
 
 /// Stroke width for glyph rendering.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphStroke {
     /// Stroke width in pixels.
     pub width_px: f32,
@@ -38,6 +63,7 @@ pub struct GlyphStroke {
 
 /// A single path command for drawing glyph strokes.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GlyphPathCmd {
     /// Move to a position without drawing.
     MoveTo(Vec2),
@@ -51,6 +77,7 @@ pub enum GlyphPathCmd {
 ///
 /// Glyphs may consist of multiple paths for segmented/broken aesthetics.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphPath {
     /// Path commands in font-local coordinates (typically 0..1).
     pub cmds: Vec<GlyphPathCmd>,
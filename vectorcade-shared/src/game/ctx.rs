@@ -4,7 +4,8 @@ use crate::input::InputState;
 use vectorcade_core::GameRng;
 
 /// Information about the display surface.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScreenInfo {
     pub width_px: u32,
     pub height_px: u32,
@@ -43,6 +44,12 @@ pub trait AudioOut {
     fn beep(&self, _id: &'static str) {}
 }
 
+/// Audio output that discards every sound (headless runs and tests).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SilentAudio;
+
+impl AudioOut for SilentAudio {}
+
 /// Asset loading interface (stub for now).
 pub trait Assets {}
 
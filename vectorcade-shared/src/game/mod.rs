@@ -2,9 +2,11 @@
 
 mod coords;
 mod ctx;
+mod runner;
 
 pub use coords::{ndc_to_px, px_to_ndc};
-pub use ctx::{Assets, AudioOut, GameCtx, GameMeta, ScreenInfo};
+pub use ctx::{Assets, AudioOut, GameCtx, GameMeta, ScreenInfo, SilentAudio};
+pub use runner::{Frame, GameRunner, RunnerConfig};
 
 use crate::draw::DrawCmd;
 
@@ -12,7 +14,8 @@ use crate::draw::DrawCmd;
 ///
 /// Games implement this trait to define their logic and rendering.
 /// The engine calls `update` at a fixed timestep for determinism,
-/// then `render` to collect draw commands.
+/// then `render` to collect draw commands. [`GameRunner`] provides a
+/// platform-neutral implementation of that loop.
 pub trait Game {
     /// Return metadata about this game.
     fn metadata(&self) -> GameMeta;
@@ -0,0 +1,263 @@
+//! Fixed-timestep game runner.
+//!
+//! [`GameRunner`] owns a [`Game`], its RNG, and a reusable display list.
+//! Hosts feed it real elapsed time; it runs as many fixed `update` steps
+//! as the accumulated time allows and then collects the frame's draw
+//! commands. No window or clock is required, so games can be driven
+//! headlessly in tests.
+//!
+//! In debug builds the runner validates every display list and panics
+//! on errors, so malformed output fails fast during development.
+
+use super::{AudioOut, Game, GameCtx, ScreenInfo};
+use crate::draw::{DrawCmd, Severity, validate};
+use crate::input::{HeldInput, InputState};
+use vectorcade_core::{GameRng, Xorshift64};
+
+/// Timing configuration for a [`GameRunner`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunnerConfig {
+    /// Fixed update timestep in seconds. Zero, negative and non-finite
+    /// values are replaced by [`Self::DEFAULT_FIXED_DT`].
+    pub fixed_dt: f32,
+    /// Maximum updates per frame before excess time is dropped.
+    ///
+    /// Prevents the "spiral of death" when a frame takes longer than
+    /// the updates it has to catch up on. Zero is raised to 1.
+    pub max_steps: u32,
+}
+
+impl RunnerConfig {
+    /// Timestep used by default and in place of invalid ones: 60 Hz.
+    pub const DEFAULT_FIXED_DT: f32 = 1.0 / 60.0;
+
+    /// Create a config with the given timestep and catch-up clamp.
+    ///
+    /// A zero, negative or non-finite `fixed_dt` is replaced by
+    /// [`Self::DEFAULT_FIXED_DT`], and a zero `max_steps` by 1.
+    #[must_use]
+    pub const fn new(fixed_dt: f32, max_steps: u32) -> Self {
+        Self {
+            fixed_dt,
+            max_steps,
+        }
+        .sanitized()
+    }
+
+    /// Return this config with an invalid `fixed_dt` replaced by
+    /// [`Self::DEFAULT_FIXED_DT`] and `max_steps` raised to at least 1,
+    /// so the game always advances. [`GameRunner::new`] applies this to
+    /// configs built as literals or deserialized.
+    #[must_use]
+    pub const fn sanitized(mut self) -> Self {
+        if !(self.fixed_dt.is_finite() && self.fixed_dt > 0.0) {
+            self.fixed_dt = Self::DEFAULT_FIXED_DT;
+        }
+        if self.max_steps == 0 {
+            self.max_steps = 1;
+        }
+        self
+    }
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_FIXED_DT, 5)
+    }
+}
+
+/// Result of advancing a [`GameRunner`] by one host frame.
+#[derive(Debug)]
+pub struct Frame<'a> {
+    /// Display list produced by `Game::render` for this frame.
+    pub cmds: &'a [DrawCmd],
+    /// Fraction of a fixed step left in the accumulator (0.0..1.0).
+    ///
+    /// Renderers may use this to interpolate between the previous and
+    /// current simulation state.
+    pub alpha: f32,
+    /// Number of fixed `update` steps run this frame.
+    pub steps: u32,
+    /// Seconds of backlog discarded by the catch-up clamp.
+    pub dropped_s: f64,
+}
+
+/// Drives a [`Game`] at a fixed timestep.
+pub struct GameRunner<G: Game, R: GameRng = Xorshift64> {
+    game: G,
+    rng: R,
+    cmds: Vec<DrawCmd>,
+    config: RunnerConfig,
+    screen: ScreenInfo,
+    accumulator: f64,
+    now_s: f64,
+    tick: u64,
+    validate: bool,
+}
+
+impl<G: Game, R: GameRng> GameRunner<G, R> {
+    /// Create a runner for `game` using `rng` for gameplay randomness.
+    ///
+    /// An invalid `config` is repaired as by [`RunnerConfig::sanitized`].
+    #[must_use]
+    pub fn new(game: G, rng: R, config: RunnerConfig) -> Self {
+        Self {
+            game,
+            rng,
+            cmds: Vec::new(),
+            config: config.sanitized(),
+            screen: ScreenInfo::default(),
+            accumulator: 0.0,
+            now_s: 0.0,
+            tick: 0,
+            validate: cfg!(debug_assertions),
+        }
+    }
+
+    /// Reset the game and the runner's clock.
+    pub fn reset(&mut self, input: &dyn InputState, audio: &dyn AudioOut) {
+        self.accumulator = 0.0;
+        self.now_s = 0.0;
+        self.tick = 0;
+        let mut ctx = GameCtx {
+            input,
+            audio,
+            rng: &mut self.rng,
+            screen: self.screen,
+            now_s: self.now_s,
+        };
+        self.game.reset(&mut ctx);
+    }
+
+    /// Advance by `elapsed_s` seconds of real time and render a frame.
+    ///
+    /// Negative or non-finite elapsed times are treated as zero. Only the
+    /// first step sees key edges (`went_down`, `went_up`); later catch-up
+    /// steps get [`HeldInput`], so one press is handled once.
+    pub fn frame(
+        &mut self,
+        elapsed_s: f64,
+        input: &dyn InputState,
+        audio: &dyn AudioOut,
+    ) -> Frame<'_> {
+        let dt = f64::from(self.config.fixed_dt);
+        if elapsed_s.is_finite() && elapsed_s > 0.0 {
+            self.accumulator += elapsed_s;
+        }
+
+        let mut steps = 0;
+        while self.accumulator >= dt && steps < self.config.max_steps {
+            if steps == 0 {
+                self.step(input, audio);
+            } else {
+                self.step(&HeldInput(input), audio);
+            }
+            self.accumulator -= dt;
+            steps += 1;
+        }
+
+        let mut dropped_s = 0.0;
+        if self.accumulator >= dt {
+            dropped_s = self.accumulator - self.accumulator % dt;
+            self.accumulator -= dropped_s;
+        }
+
+        self.render(input, audio);
+        Frame {
+            cmds: &self.cmds,
+            alpha: (self.accumulator / dt) as f32,
+            steps,
+            dropped_s,
+        }
+    }
+
+    /// Run exactly one fixed `update` step, ignoring the accumulator.
+    pub fn step(&mut self, input: &dyn InputState, audio: &dyn AudioOut) {
+        let mut ctx = GameCtx {
+            input,
+            audio,
+            rng: &mut self.rng,
+            screen: self.screen,
+            now_s: self.now_s,
+        };
+        self.game.update(&mut ctx, self.config.fixed_dt);
+        self.tick += 1;
+        self.now_s = self.tick as f64 * f64::from(self.config.fixed_dt);
+    }
+
+    /// Clear the display list and collect draw commands from the game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if validation is enabled and the list has errors.
+    pub fn render(&mut self, input: &dyn InputState, audio: &dyn AudioOut) -> &[DrawCmd] {
+        self.cmds.clear();
+        let mut ctx = GameCtx {
+            input,
+            audio,
+            rng: &mut self.rng,
+            screen: self.screen,
+            now_s: self.now_s,
+        };
+        self.game.render(&mut ctx, &mut self.cmds);
+        if self.validate {
+            assert_valid(&self.cmds);
+        }
+        &self.cmds
+    }
+
+    /// Enable or disable display-list validation after each render.
+    ///
+    /// Enabled by default in debug builds.
+    pub fn set_validate(&mut self, validate: bool) {
+        self.validate = validate;
+    }
+
+    /// Set the display surface info passed to the game.
+    pub fn set_screen(&mut self, screen: ScreenInfo) {
+        self.screen = screen;
+    }
+
+    /// Return the timing configuration.
+    #[must_use]
+    pub fn config(&self) -> RunnerConfig {
+        self.config
+    }
+
+    /// Return simulated game time in seconds.
+    #[must_use]
+    pub fn now_s(&self) -> f64 {
+        self.now_s
+    }
+
+    /// Return the number of fixed steps run since the last reset.
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Borrow the game.
+    #[must_use]
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// Mutably borrow the game.
+    pub fn game_mut(&mut self) -> &mut G {
+        &mut self.game
+    }
+}
+
+fn assert_valid(cmds: &[DrawCmd]) {
+    let errors: Vec<String> = validate(cmds)
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(ToString::to_string)
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "invalid display list:\n{}",
+        errors.join("\n")
+    );
+}
@@ -5,6 +5,7 @@
 
 /// Keyboard keys commonly used in arcade-style games.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     /// Left arrow or equivalent.
     Left,
@@ -32,8 +33,27 @@ pub enum Key {
     C,
 }
 
+impl Key {
+    /// Every key, in declaration order.
+    pub const ALL: [Key; 12] = [
+        Key::Left,
+        Key::Right,
+        Key::Up,
+        Key::Down,
+        Key::Space,
+        Key::Enter,
+        Key::Escape,
+        Key::W,
+        Key::S,
+        Key::Z,
+        Key::X,
+        Key::C,
+    ];
+}
+
 /// Virtual axes for analog input (gamepad sticks, touch controls).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis {
     /// Horizontal movement axis.
     MoveX,
@@ -47,8 +67,20 @@ pub enum Axis {
     Thrust,
 }
 
+impl Axis {
+    /// Every axis, in declaration order.
+    pub const ALL: [Axis; 5] = [
+        Axis::MoveX,
+        Axis::MoveY,
+        Axis::AimX,
+        Axis::AimY,
+        Axis::Thrust,
+    ];
+}
+
 /// State of a digital button (keyboard key or gamepad button).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Button {
     /// True if the button is currently held down.
     pub is_down: bool,
@@ -69,6 +101,7 @@ impl Button {
 
 /// State of a pointer device (mouse or touch).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pointer {
     /// X position in screen pixels.
     pub x_px: f32,
@@ -97,3 +130,50 @@ pub trait InputState {
     /// Returns `None` if no pointer device is active.
     fn pointer(&self) -> Option<Pointer>;
 }
+
+/// Input source with every key up, every axis centered, and no pointer.
+///
+/// Useful for headless runs and tests that don't need player input.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullInput;
+
+impl InputState for NullInput {
+    fn key(&self, _k: Key) -> Button {
+        Button::UP
+    }
+
+    fn axis(&self, _a: Axis) -> f32 {
+        0.0
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        None
+    }
+}
+
+/// Input with every key's `went_down` and `went_up` cleared.
+///
+/// [`GameRunner::frame`](crate::game::GameRunner::frame) gives this to
+/// the catch-up steps after a frame's first, so a press seen by one
+/// step is not handled again by the next. Axes and the pointer pass
+/// through unchanged.
+#[derive(Clone, Copy)]
+pub struct HeldInput<'a>(pub &'a dyn InputState);
+
+impl InputState for HeldInput<'_> {
+    fn key(&self, k: Key) -> Button {
+        Button {
+            went_down: false,
+            went_up: false,
+            ..self.0.key(k)
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        self.0.axis(a)
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.0.pointer()
+    }
+}
@@ -8,6 +8,7 @@ use glam::{Vec2, Vec3};
 
 /// 3D projectile for first-person shooter games (Battlezone, etc.).
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Projectile3D {
     /// Current position in world space.
     pub pos: Vec3,
@@ -66,6 +67,7 @@ impl Projectile3D {
 
 /// 2D projectile for top-down shooter games (Asteroids, etc.).
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Projectile2D {
     /// Current position in world space.
     pub pos: Vec2,
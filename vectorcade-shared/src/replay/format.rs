@@ -0,0 +1,187 @@
+//! Binary replay file format.
+//!
+//! All integers and floats are little-endian.
+//!
+//! | Field      | Type         | Notes                                  |
+//! |------------|--------------|----------------------------------------|
+//! | magic      | `[u8; 4]`    | `b"VCRP"`                              |
+//! | version    | `u16`        | [`FORMAT_VERSION`]                     |
+//! | name_len   | `u16`        | Byte length of `name`                  |
+//! | name       | UTF-8        | `GameMeta::name` of the recorded game  |
+//! | seed       | `u64`        | `Xorshift64` seed                      |
+//! | dt         | `f32`        | Fixed timestep in seconds              |
+//! | ticks      | `u32`        | Number of tick records that follow     |
+//! | tick data  | records      | One delta record per tick              |
+//!
+//! Each tick record starts with a flags byte describing what changed
+//! since the previous tick (the first tick is compared against
+//! [`InputFrame::NEUTRAL`]); only changed fields follow, in bit order:
+//!
+//! | Bit  | Payload                                                    |
+//! |------|------------------------------------------------------------|
+//! | 0    | key masks: `down`, `pressed`, `released` as three `u16`    |
+//! | 1..5 | one `f32` per changed axis, in [`Axis::ALL`] order         |
+//! | 6    | pointer: `u8` present flag, then `x_px`, `y_px` as `f32` and `is_down` as `u8` when present |
+//! | 7    | reserved, must be zero                                     |
+//!
+//! An idle tick therefore costs a single byte.
+//!
+//! [`Axis::ALL`]: crate::input::Axis::ALL
+
+use super::wire::{Reader, read_tick, write_tick};
+use super::{InputFrame, Replay};
+use crate::game::GameMeta;
+use std::fmt;
+
+/// Magic bytes at the start of every replay file.
+pub const MAGIC: [u8; 4] = *b"VCRP";
+
+/// Current replay format version.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Error returned when encoding or decoding a replay file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The file was written by an unsupported format version.
+    UnsupportedVersion {
+        /// Version found in the header.
+        found: u16,
+    },
+    /// The data ended before a complete field could be read.
+    Truncated,
+    /// The game name is not valid UTF-8.
+    InvalidName,
+    /// A tick record set the reserved flag bit.
+    InvalidFlags {
+        /// Index of the offending tick.
+        tick: u32,
+    },
+    /// Extra bytes follow the last tick record.
+    TrailingData,
+    /// The game name is longer than `u16::MAX` bytes.
+    NameTooLong {
+        /// Byte length of the name.
+        len: usize,
+    },
+    /// The replay has more than `u32::MAX` ticks.
+    TooManyTicks {
+        /// Number of ticks.
+        count: usize,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a replay file (bad magic)"),
+            Self::UnsupportedVersion { found } => write!(
+                f,
+                "unsupported replay version {found} (expected {FORMAT_VERSION})"
+            ),
+            Self::Truncated => write!(f, "replay data is truncated"),
+            Self::InvalidName => write!(f, "replay game name is not valid UTF-8"),
+            Self::InvalidFlags { tick } => write!(f, "invalid flags in tick {tick}"),
+            Self::TrailingData => write!(f, "unexpected data after last tick"),
+            Self::NameTooLong { len } => {
+                write!(f, "replay game name is {len} bytes (at most {})", u16::MAX)
+            }
+            Self::TooManyTicks { count } => {
+                write!(f, "replay has {count} ticks (at most {})", u32::MAX)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// A replay plus the header metadata stored alongside it on disk.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayFile {
+    /// Name of the recorded game (from `GameMeta::name`).
+    pub game: String,
+    /// Fixed timestep the session was recorded at.
+    pub dt: f32,
+    /// The recorded session.
+    pub replay: Replay,
+}
+
+impl ReplayFile {
+    /// Wrap a replay recorded from a game with the given metadata.
+    #[must_use]
+    pub fn new(meta: &GameMeta, dt: f32, replay: Replay) -> Self {
+        Self {
+            game: meta.name.to_string(),
+            dt,
+            replay,
+        }
+    }
+
+    /// Encode to the binary replay format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReplayError::NameTooLong`] if the game name is longer
+    /// than `u16::MAX` bytes, or [`ReplayError::TooManyTicks`] if the
+    /// replay has more than `u32::MAX` ticks.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ReplayError> {
+        let name = self.game.as_bytes();
+        let name_len =
+            u16::try_from(name.len()).map_err(|_| ReplayError::NameTooLong { len: name.len() })?;
+        let count = self.replay.frames.len();
+        let ticks = u32::try_from(count).map_err(|_| ReplayError::TooManyTicks { count })?;
+
+        let mut out = Vec::with_capacity(24 + name.len() + count);
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&name_len.to_le_bytes());
+        out.extend_from_slice(name);
+        out.extend_from_slice(&self.replay.seed.to_le_bytes());
+        out.extend_from_slice(&self.dt.to_le_bytes());
+        out.extend_from_slice(&ticks.to_le_bytes());
+
+        let mut prev = InputFrame::NEUTRAL;
+        for frame in &self.replay.frames {
+            write_tick(&mut out, &prev, frame);
+            prev = *frame;
+        }
+        Ok(out)
+    }
+
+    /// Decode from the binary replay format.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ReplayError`] if the data is not a replay file, was
+    /// written by a different format version, or is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut r = Reader::new(bytes);
+        if r.take(4)? != MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        let version = r.u16()?;
+        if version != FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion { found: version });
+        }
+        let name_len = usize::from(r.u16()?);
+        let game = std::str::from_utf8(r.take(name_len)?)
+            .map_err(|_| ReplayError::InvalidName)?
+            .to_string();
+        let seed = r.u64()?;
+        let dt = r.f32()?;
+        let ticks = r.u32()?;
+
+        let mut replay = Replay::new(seed);
+        let mut prev = InputFrame::NEUTRAL;
+        for tick in 0..ticks {
+            prev = read_tick(&mut r, &prev, tick)?;
+            replay.frames.push(prev);
+        }
+        if !r.is_empty() {
+            return Err(ReplayError::TrailingData);
+        }
+        Ok(Self { game, dt, replay })
+    }
+}
@@ -0,0 +1,133 @@
+//! Deterministic input recording and replay.
+//!
+//! A [`Replay`] pairs the RNG seed a session started with and a snapshot
+//! of the input the game saw on every fixed tick. Because `update` only
+//! reads input through [`InputState`] and randomness through the seeded
+//! [`GameRng`](vectorcade_core::GameRng), feeding the same snapshots back
+//! through [`ReplayInput`] reproduces the session exactly.
+//!
+//! Record with [`InputRecorder`], calling `record` once per fixed step.
+//! [`ReplayFile`] stores a replay on disk in a compact, versioned binary
+//! format (see [`format`](mod@format) for the layout).
+
+pub mod format;
+mod playback;
+mod record;
+mod wire;
+
+pub use format::{ReplayError, ReplayFile};
+pub use playback::ReplayInput;
+pub use record::InputRecorder;
+
+use crate::input::{Axis, Button, InputState, Key, Pointer};
+use vectorcade_core::Xorshift64;
+
+/// Input seen by the game during a single fixed tick.
+///
+/// Key state is packed into bitmasks indexed by position in [`Key::ALL`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputFrame {
+    /// Keys held down.
+    pub down: u16,
+    /// Keys that went down this tick.
+    pub pressed: u16,
+    /// Keys that went up this tick.
+    pub released: u16,
+    /// Axis values, indexed by position in [`Axis::ALL`].
+    pub axes: [f32; Axis::ALL.len()],
+    /// Pointer state, if a pointer was active.
+    pub pointer: Option<Pointer>,
+}
+
+impl InputFrame {
+    /// A frame with no keys held, centered axes, and no pointer.
+    pub const NEUTRAL: InputFrame = InputFrame {
+        down: 0,
+        pressed: 0,
+        released: 0,
+        axes: [0.0; Axis::ALL.len()],
+        pointer: None,
+    };
+
+    /// Snapshot the current state of an input source.
+    #[must_use]
+    pub fn capture(input: &dyn InputState) -> Self {
+        let mut frame = Self::NEUTRAL;
+        for (i, &k) in Key::ALL.iter().enumerate() {
+            let b = input.key(k);
+            frame.down |= u16::from(b.is_down) << i;
+            frame.pressed |= u16::from(b.went_down) << i;
+            frame.released |= u16::from(b.went_up) << i;
+        }
+        for (slot, &a) in frame.axes.iter_mut().zip(Axis::ALL.iter()) {
+            *slot = input.axis(a);
+        }
+        frame.pointer = input.pointer();
+        frame
+    }
+}
+
+impl Default for InputFrame {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+impl InputState for InputFrame {
+    fn key(&self, k: Key) -> Button {
+        let bit = 1 << k as u16;
+        Button {
+            is_down: self.down & bit != 0,
+            went_down: self.pressed & bit != 0,
+            went_up: self.released & bit != 0,
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        self.axes[a as usize]
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.pointer
+    }
+}
+
+/// A recorded play session: RNG seed plus per-tick input.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    /// Seed the session's `Xorshift64` was created with.
+    pub seed: u64,
+    /// Input for each fixed tick, in order.
+    pub frames: Vec<InputFrame>,
+}
+
+impl Replay {
+    /// Create an empty replay for a session seeded with `seed`.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Return a fresh RNG in the same state the session started with.
+    #[must_use]
+    pub const fn rng(&self) -> Xorshift64 {
+        Xorshift64::new(self.seed)
+    }
+
+    /// Number of recorded ticks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return true if no ticks were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
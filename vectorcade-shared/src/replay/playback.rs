@@ -0,0 +1,61 @@
+//! Replay playback as an [`InputState`].
+
+use super::{InputFrame, Replay};
+use crate::input::{Axis, Button, InputState, Key, Pointer};
+
+/// Plays back a [`Replay`] tick-for-tick.
+///
+/// Reports the input of the current tick; call [`advance`](Self::advance)
+/// after each fixed step. Past the end of the recording all input reads
+/// as neutral.
+#[derive(Clone, Debug)]
+pub struct ReplayInput<'a> {
+    replay: &'a Replay,
+    tick: usize,
+}
+
+impl<'a> ReplayInput<'a> {
+    /// Start playback at the first recorded tick.
+    #[must_use]
+    pub const fn new(replay: &'a Replay) -> Self {
+        Self { replay, tick: 0 }
+    }
+
+    /// Move to the next tick.
+    pub fn advance(&mut self) {
+        self.tick = self.tick.saturating_add(1);
+    }
+
+    /// Index of the tick currently being played.
+    #[must_use]
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// Return true once every recorded tick has been played.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.replay.frames.len()
+    }
+
+    fn current(&self) -> &InputFrame {
+        self.replay
+            .frames
+            .get(self.tick)
+            .unwrap_or(&InputFrame::NEUTRAL)
+    }
+}
+
+impl InputState for ReplayInput<'_> {
+    fn key(&self, k: Key) -> Button {
+        self.current().key(k)
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        self.current().axis(a)
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        self.current().pointer()
+    }
+}
@@ -0,0 +1,43 @@
+//! Input recorder.
+
+use super::{InputFrame, Replay};
+use crate::input::InputState;
+
+/// Captures per-tick input snapshots into a [`Replay`].
+///
+/// Call [`record`](Self::record) once per fixed `update` step with the
+/// same input the game is given. When driving a
+/// [`GameRunner`](crate::game::GameRunner) with `frame`, record once for
+/// each of the frame's `steps`: `input` for the first and
+/// [`HeldInput`](crate::input::HeldInput) of it for the rest.
+#[derive(Clone, Debug)]
+pub struct InputRecorder {
+    replay: Replay,
+}
+
+impl InputRecorder {
+    /// Start recording a session whose RNG was seeded with `seed`.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            replay: Replay::new(seed),
+        }
+    }
+
+    /// Snapshot `input` as the next tick.
+    pub fn record(&mut self, input: &dyn InputState) {
+        self.replay.frames.push(InputFrame::capture(input));
+    }
+
+    /// Number of ticks recorded so far.
+    #[must_use]
+    pub fn ticks(&self) -> usize {
+        self.replay.frames.len()
+    }
+
+    /// Stop recording and return the replay.
+    #[must_use]
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
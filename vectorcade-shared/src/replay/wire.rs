@@ -0,0 +1,139 @@
+//! Byte-level encoding helpers for the replay format.
+
+use super::InputFrame;
+use super::format::ReplayError;
+use crate::input::Pointer;
+
+const KEYS_BIT: u8 = 1 << 0;
+const AXIS_SHIFT: u8 = 1;
+const POINTER_BIT: u8 = 1 << 6;
+const RESERVED_BIT: u8 = 1 << 7;
+
+/// Cursor over an input byte slice.
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(super) fn take(&mut self, n: usize) -> Result<&'a [u8], ReplayError> {
+        if self.bytes.len() < n {
+            return Err(ReplayError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let mut buf = [0; N];
+        buf.copy_from_slice(self.take(N)?);
+        Ok(buf)
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, ReplayError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, ReplayError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, ReplayError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    pub(super) fn f32(&mut self) -> Result<f32, ReplayError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// Append the delta record for `cur` relative to `prev`.
+///
+/// Axes and pointer coordinates are compared bitwise so that `-0.0`
+/// and NaN payloads survive the round trip unchanged.
+pub(super) fn write_tick(out: &mut Vec<u8>, prev: &InputFrame, cur: &InputFrame) {
+    let keys_changed =
+        (prev.down, prev.pressed, prev.released) != (cur.down, cur.pressed, cur.released);
+    let mut flags = if keys_changed { KEYS_BIT } else { 0 };
+    for (i, (p, c)) in prev.axes.iter().zip(&cur.axes).enumerate() {
+        if p.to_bits() != c.to_bits() {
+            flags |= 1 << (AXIS_SHIFT + i as u8);
+        }
+    }
+    if pointer_bits(prev.pointer) != pointer_bits(cur.pointer) {
+        flags |= POINTER_BIT;
+    }
+
+    out.push(flags);
+    if keys_changed {
+        for mask in [cur.down, cur.pressed, cur.released] {
+            out.extend_from_slice(&mask.to_le_bytes());
+        }
+    }
+    for (i, v) in cur.axes.iter().enumerate() {
+        if flags & (1 << (AXIS_SHIFT + i as u8)) != 0 {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    if flags & POINTER_BIT != 0 {
+        match cur.pointer {
+            None => out.push(0),
+            Some(p) => {
+                out.push(1);
+                out.extend_from_slice(&p.x_px.to_le_bytes());
+                out.extend_from_slice(&p.y_px.to_le_bytes());
+                out.push(u8::from(p.is_down));
+            }
+        }
+    }
+}
+
+/// Read one delta record and apply it on top of `prev`.
+pub(super) fn read_tick(
+    r: &mut Reader<'_>,
+    prev: &InputFrame,
+    tick: u32,
+) -> Result<InputFrame, ReplayError> {
+    let flags = r.u8()?;
+    if flags & RESERVED_BIT != 0 {
+        return Err(ReplayError::InvalidFlags { tick });
+    }
+    let mut cur = *prev;
+    if flags & KEYS_BIT != 0 {
+        cur.down = r.u16()?;
+        cur.pressed = r.u16()?;
+        cur.released = r.u16()?;
+    }
+    for (i, v) in cur.axes.iter_mut().enumerate() {
+        if flags & (1 << (AXIS_SHIFT + i as u8)) != 0 {
+            *v = r.f32()?;
+        }
+    }
+    if flags & POINTER_BIT != 0 {
+        cur.pointer = match r.u8()? {
+            0 => None,
+            _ => Some(Pointer {
+                x_px: r.f32()?,
+                y_px: r.f32()?,
+                is_down: r.u8()? != 0,
+            }),
+        };
+    }
+    Ok(cur)
+}
+
+fn pointer_bits(p: Option<Pointer>) -> Option<(u32, u32, bool)> {
+    p.map(|p| (p.x_px.to_bits(), p.y_px.to_bits(), p.is_down))
+}
@@ -0,0 +1,165 @@
+//! Golden-file comparison.
+
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+/// Environment variable that switches golden checks to update mode.
+///
+/// When set to anything other than `0` or an empty string, golden files
+/// are overwritten with the actual output instead of compared.
+pub const UPDATE_ENV: &str = "VECTORCADE_UPDATE_SNAPSHOTS";
+
+/// Difference between a snapshot and its golden file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotMismatch {
+    /// 1-based line number of the first difference.
+    pub line: usize,
+    /// Expected line (`None` past the end of the golden file).
+    pub expected: Option<String>,
+    /// Actual line (`None` past the end of the snapshot).
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |s: &Option<String>| s.clone().unwrap_or_else(|| "<end of file>".into());
+        write!(
+            f,
+            "snapshot differs at line {}\n  expected: {}\n  actual:   {}",
+            self.line,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+/// Error returned by [`check_golden`] and [`compare_golden`].
+#[derive(Debug)]
+pub enum GoldenError {
+    /// The snapshot differs from the golden file.
+    Mismatch(SnapshotMismatch),
+    /// The golden file does not exist.
+    Missing {
+        /// Path of the missing file.
+        path: PathBuf,
+    },
+    /// The golden file or its directory could not be read or written.
+    Io {
+        /// Path of the golden file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch(m) => write!(f, "{m}"),
+            Self::Missing { path } => write!(
+                f,
+                "golden file {} is missing (set {UPDATE_ENV}=1 to create it)",
+                path.display()
+            ),
+            Self::Io { path, error } => write!(f, "golden file {}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Compare `actual` against the golden file at `path`.
+///
+/// In update mode (see [`UPDATE_ENV`]) the file (and its directory) is
+/// written instead and `Ok` is returned.
+///
+/// # Errors
+///
+/// Returns the first mismatching line, [`GoldenError::Missing`] if the
+/// golden file does not exist, or [`GoldenError::Io`] if it cannot be
+/// read or, in update mode, written.
+pub fn check_golden(path: impl AsRef<Path>, actual: &str) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    if !update_mode() {
+        return compare_golden(path, actual);
+    }
+    let io = |error| GoldenError::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(io)?;
+    }
+    std::fs::write(path, actual).map_err(io)
+}
+
+/// Compare `actual` against the golden file at `path`, ignoring update
+/// mode.
+///
+/// # Errors
+///
+/// As [`check_golden`], without the update-mode write errors.
+pub fn compare_golden(path: impl AsRef<Path>, actual: &str) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    let expected = std::fs::read_to_string(path).map_err(|error| {
+        if error.kind() == io::ErrorKind::NotFound {
+            GoldenError::Missing {
+                path: path.to_path_buf(),
+            }
+        } else {
+            GoldenError::Io {
+                path: path.to_path_buf(),
+                error,
+            }
+        }
+    })?;
+    first_difference(&expected, actual).map_or(Ok(()), |m| Err(GoldenError::Mismatch(m)))
+}
+
+/// Assert that `actual` matches the golden file at `path`.
+///
+/// # Panics
+///
+/// Panics with the first differing line if the snapshot does not match,
+/// or with the error if the golden file is missing or unusable.
+pub fn assert_golden(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    match check_golden(path, actual) {
+        Ok(()) => {}
+        Err(GoldenError::Mismatch(m)) => panic!(
+            "{m}\n  golden:   {}\n(set {UPDATE_ENV}=1 to update)",
+            path.display()
+        ),
+        Err(e) => panic!("{e}"),
+    }
+}
+
+fn update_mode() -> bool {
+    std::env::var(UPDATE_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<SnapshotMismatch> {
+    let mut exp = expected.lines();
+    let mut act = actual.lines();
+    let mut line = 1;
+    loop {
+        match (exp.next(), act.next()) {
+            (None, None) => return None,
+            (e, a) if e != a => {
+                return Some(SnapshotMismatch {
+                    line,
+                    expected: e.map(str::to_string),
+                    actual: a.map(str::to_string),
+                });
+            }
+            _ => line += 1,
+        }
+    }
+}
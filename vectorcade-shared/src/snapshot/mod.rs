@@ -0,0 +1,42 @@
+//! Display-list snapshot testing.
+//!
+//! Runs a [`Game`] headlessly with scripted input and a fixed seed,
+//! captures the `Vec<DrawCmd>` it renders, and compares a stable text
+//! form of it against a golden file:
+//!
+//! ```ignore
+//! let cmds = capture(Pong::new(), &script, 120);
+//! assert_golden("tests/golden/pong_serve.txt", &to_text(&cmds));
+//! ```
+//!
+//! Set `VECTORCADE_UPDATE_SNAPSHOTS=1` to (re)write golden files
+//! instead of comparing against them.
+
+mod golden;
+mod text;
+
+pub use golden::{
+    GoldenError, SnapshotMismatch, UPDATE_ENV, assert_golden, check_golden, compare_golden,
+};
+pub use text::to_text;
+
+use crate::draw::DrawCmd;
+use crate::game::{Game, GameRunner, RunnerConfig, SilentAudio};
+use crate::replay::{Replay, ReplayInput};
+
+/// Run `game` for `ticks` fixed steps and return the final display list.
+///
+/// Input comes from `script` tick-for-tick (neutral once it runs out)
+/// and the RNG is seeded from `script.seed`. The game is reset before
+/// the first tick.
+#[must_use]
+pub fn capture<G: Game>(game: G, script: &Replay, ticks: usize) -> Vec<DrawCmd> {
+    let mut runner = GameRunner::new(game, script.rng(), RunnerConfig::default());
+    let mut input = ReplayInput::new(script);
+    runner.reset(&input, &SilentAudio);
+    for _ in 0..ticks {
+        runner.step(&input, &SilentAudio);
+        input.advance();
+    }
+    runner.render(&input, &SilentAudio).to_vec()
+}
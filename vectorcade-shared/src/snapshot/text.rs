@@ -0,0 +1,244 @@
+//! Stable, diff-friendly text form of a display list.
+//!
+//! One command per line. Floats are rounded to four decimals (with
+//! negative zero folded into zero) so that tiny platform-dependent
+//! rounding differences don't churn golden files. Commands inside a
+//! transform, blend, clip or layer are indented by two spaces per level.
+
+use crate::draw::{BlendMode, DrawCmd, LineCap, LineJoin, Stroke};
+use glam::{Mat3, Vec2};
+use std::fmt::Write;
+use vectorcade_core::Rgba;
+use vectorcade_math::FillRule;
+
+/// Render a display list as snapshot text.
+#[must_use]
+pub fn to_text(cmds: &[DrawCmd]) -> String {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    for cmd in cmds {
+        if matches!(
+            cmd,
+            DrawCmd::PopTransform | DrawCmd::PopBlend | DrawCmd::PopClip | DrawCmd::EndLayer
+        ) {
+            depth = depth.saturating_sub(1);
+        }
+        out.push_str(&"  ".repeat(depth));
+        write_cmd(&mut out, cmd);
+        out.push('\n');
+        if matches!(
+            cmd,
+            DrawCmd::PushTransform(_)
+                | DrawCmd::PushBlend(_)
+                | DrawCmd::PushClipRect(_)
+                | DrawCmd::PushClipCircle(_)
+                | DrawCmd::BeginLayer(_)
+        ) {
+            depth += 1;
+        }
+    }
+    out
+}
+
+fn write_cmd(out: &mut String, cmd: &DrawCmd) {
+    match cmd {
+        DrawCmd::Clear { color } => {
+            out.push_str("clear ");
+            write_rgba(out, *color);
+        }
+        DrawCmd::Line(l) => {
+            out.push_str("line ");
+            write_pts(out, &[l.a, l.b]);
+            write_stroke(out, &l.stroke);
+        }
+        DrawCmd::Polyline {
+            pts,
+            closed,
+            stroke,
+        } => {
+            out.push_str(if *closed {
+                "polyline closed "
+            } else {
+                "polyline "
+            });
+            write_pts(out, pts);
+            write_stroke(out, stroke);
+        }
+        DrawCmd::GradientPolyline {
+            verts,
+            closed,
+            glow,
+        } => {
+            out.push_str(if *closed {
+                "gradient closed "
+            } else {
+                "gradient "
+            });
+            let _ = write!(out, "glow {}", num(*glow));
+            for v in verts {
+                out.push_str(" | ");
+                write_pts(out, &[v.pos]);
+                let _ = write!(out, "w {} ", num(v.width_px));
+                write_rgba(out, v.color);
+            }
+        }
+        DrawCmd::FillPolygon {
+            contours,
+            rule,
+            color,
+        } => {
+            out.push_str(match rule {
+                FillRule::NonZero => "fill nonzero ",
+                FillRule::EvenOdd => "fill evenodd ",
+            });
+            write_rgba(out, *color);
+            for contour in contours {
+                out.push_str(" | ");
+                write_pts(out, contour);
+                out.pop();
+            }
+        }
+        DrawCmd::Circle {
+            center,
+            radius,
+            stroke,
+        } => {
+            out.push_str("circle ");
+            write_pts(out, &[*center]);
+            let _ = write!(out, "r {} ", num(*radius));
+            write_stroke(out, stroke);
+        }
+        DrawCmd::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            stroke,
+        } => {
+            out.push_str("arc ");
+            write_pts(out, &[*center]);
+            let _ = write!(
+                out,
+                "r {} from {} to {} ",
+                num(*radius),
+                num(*start_angle),
+                num(*end_angle)
+            );
+            write_stroke(out, stroke);
+        }
+        DrawCmd::Ellipse {
+            center,
+            radii,
+            rotation,
+            stroke,
+        } => {
+            out.push_str("ellipse ");
+            write_pts(out, &[*center]);
+            out.push_str("radii ");
+            write_pts(out, &[*radii]);
+            let _ = write!(out, "rot {} ", num(*rotation));
+            write_stroke(out, stroke);
+        }
+        DrawCmd::Text {
+            pos,
+            text,
+            size_px,
+            color,
+            style,
+        } => {
+            let _ = write!(out, "text {text:?} at ");
+            write_pts(out, &[*pos]);
+            let _ = write!(out, "size {} style {} ", num(*size_px), style.0);
+            write_rgba(out, *color);
+        }
+        DrawCmd::PushTransform(m) => {
+            out.push_str("push ");
+            write_mat3(out, m);
+        }
+        DrawCmd::PopTransform => out.push_str("pop"),
+        DrawCmd::PushBlend(mode) => out.push_str(match mode {
+            BlendMode::Alpha => "blend alpha",
+            BlendMode::Additive => "blend additive",
+            BlendMode::Max => "blend max",
+        }),
+        DrawCmd::PopBlend => out.push_str("pop_blend"),
+        DrawCmd::PushClipRect(r) => {
+            out.push_str("clip rect ");
+            write_pts(out, &[r.min, r.max]);
+            out.pop();
+        }
+        DrawCmd::PushClipCircle(c) => {
+            out.push_str("clip circle ");
+            write_pts(out, &[c.center]);
+            let _ = write!(out, "r {}", num(c.radius));
+        }
+        DrawCmd::PopClip => out.push_str("pop_clip"),
+        DrawCmd::BeginLayer(id) => {
+            let _ = write!(out, "layer {:?} z {}", id.name, id.z);
+        }
+        DrawCmd::EndLayer => out.push_str("end_layer"),
+    }
+}
+
+fn write_pts(out: &mut String, pts: &[Vec2]) {
+    for p in pts {
+        let _ = write!(out, "({} {}) ", num(p.x), num(p.y));
+    }
+}
+
+/// Write a stroke; cap, join and dash appear only when not the default.
+fn write_stroke(out: &mut String, s: &Stroke) {
+    let _ = write!(out, "w {} glow {} ", num(s.width_px), num(s.glow));
+    match s.cap {
+        LineCap::Butt => out.push_str("cap butt "),
+        LineCap::Square => out.push_str("cap square "),
+        LineCap::Round => {}
+    }
+    match s.join {
+        LineJoin::Miter => out.push_str("join miter "),
+        LineJoin::Bevel => out.push_str("join bevel "),
+        LineJoin::Round => {}
+    }
+    if let Some(d) = s.dash {
+        let _ = write!(
+            out,
+            "dash {} {} phase {} ",
+            num(d.on_px),
+            num(d.off_px),
+            num(d.phase_px)
+        );
+    }
+    write_rgba(out, s.color);
+}
+
+fn write_rgba(out: &mut String, c: Rgba) {
+    let _ = write!(
+        out,
+        "rgba({} {} {} {})",
+        num(c.0),
+        num(c.1),
+        num(c.2),
+        num(c.3)
+    );
+}
+
+fn write_mat3(out: &mut String, m: &Mat3) {
+    let rows = [m.row(0), m.row(1), m.row(2)];
+    let parts: Vec<String> = rows
+        .iter()
+        .map(|r| format!("{} {} {}", num(r.x), num(r.y), num(r.z)))
+        .collect();
+    let _ = write!(out, "[{}]", parts.join("; "));
+}
+
+/// Format a float rounded to four decimals, without negative zero.
+fn num(v: f32) -> String {
+    let s = format!("{v:.4}");
+    if !s.contains('.') {
+        return s;
+    }
+    match s.trim_end_matches('0').trim_end_matches('.') {
+        "-0" => "0".to_string(),
+        t => t.to_string(),
+    }
+}
@@ -0,0 +1,72 @@
+use glam::Vec2;
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{
+    BlendMode, DiagnosticKind, DrawCmd, Line2, OptimizeOptions, Stroke, optimize, validate,
+};
+use vectorcade_shared::snapshot::to_text;
+
+fn line(a: Vec2, b: Vec2) -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a,
+        b,
+        stroke: Stroke::new(Rgba::GREEN, 1.0),
+    })
+}
+
+fn chain() -> [DrawCmd; 2] {
+    [
+        line(Vec2::ZERO, Vec2::X * 0.5),
+        line(Vec2::X * 0.5, Vec2::ONE * 0.5),
+    ]
+}
+
+#[test]
+fn additive_lines_are_not_merged() {
+    let mut out = Vec::new();
+    let mut cmds = vec![DrawCmd::PushBlend(BlendMode::Additive)];
+    cmds.extend(chain());
+    cmds.push(DrawCmd::PopBlend);
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(out, cmds);
+    assert_eq!(stats.lines_merged, 0);
+
+    cmds[0] = DrawCmd::PushBlend(BlendMode::Max);
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(out.len(), 3);
+    assert_eq!(stats.lines_merged, 2);
+}
+
+#[test]
+fn empty_blend_scopes_are_removed() {
+    let cmds = [
+        DrawCmd::PushBlend(BlendMode::Additive),
+        DrawCmd::PopBlend,
+        DrawCmd::PopBlend,
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    // The unbalanced pop is kept for the validator to report.
+    assert_eq!(out, [DrawCmd::PopBlend]);
+    assert_eq!(stats.blends_removed, 2);
+}
+
+#[test]
+fn blend_scopes_are_validated_and_indented() {
+    let cmds = [
+        DrawCmd::PopBlend,
+        DrawCmd::PushBlend(BlendMode::Max),
+        line(Vec2::ZERO, Vec2::X),
+    ];
+    let kinds: Vec<_> = validate(&cmds).iter().map(|d| (d.index, d.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, DiagnosticKind::UnbalancedPopBlend),
+            (1, DiagnosticKind::UnclosedPushBlend),
+        ]
+    );
+    assert_eq!(
+        to_text(&cmds),
+        "pop_blend\nblend max\n  line (0 0) (1 0) w 1 glow 0 rgba(0 1 0 1)\n"
+    );
+}
@@ -0,0 +1,67 @@
+use glam::Vec2;
+use vectorcade_shared::font::{
+    BuiltinFont, FontStyleId, GlyphPathCmd, TextMeasurer, TextStyle, VectorFont, measure_text,
+};
+
+const PUNCTUATION: &str = "!\"'()*+,-./:;<=>?[]_";
+
+fn points(ch: char) -> Vec<Vec2> {
+    BuiltinFont
+        .glyph_paths(ch)
+        .iter()
+        .flat_map(|path| &path.cmds)
+        .filter_map(|cmd| match cmd {
+            GlyphPathCmd::MoveTo(p) | GlyphPathCmd::LineTo(p) => Some(*p),
+            GlyphPathCmd::Close => None,
+        })
+        .collect()
+}
+
+#[test]
+fn covers_uppercase_digits_and_punctuation() {
+    let font = BuiltinFont;
+    assert_eq!(font.style_id(), FontStyleId::DEFAULT);
+    for ch in ('A'..='Z').chain('0'..='9').chain(PUNCTUATION.chars()) {
+        assert!(font.has_glyph(ch), "{ch:?}");
+        let pts = points(ch);
+        assert!(pts.len() >= 2, "{ch:?}");
+        for p in pts {
+            assert!((0.0..=4.0 / 6.0).contains(&p.x), "{ch:?} {p}");
+            assert!((0.0..=1.0).contains(&p.y), "{ch:?} {p}");
+        }
+    }
+    assert!(font.has_glyph(' '));
+    assert!(font.glyph_paths(' ').is_empty());
+}
+
+#[test]
+fn every_stroke_starts_with_a_move() {
+    for ch in ('A'..='Z').chain('0'..='9').chain(PUNCTUATION.chars()) {
+        for path in BuiltinFont.glyph_paths(ch) {
+            assert!(matches!(path.cmds[0], GlyphPathCmd::MoveTo(_)), "{ch:?}");
+        }
+    }
+}
+
+#[test]
+fn lowercase_draws_as_uppercase_and_unknown_draws_nothing() {
+    let font = BuiltinFont;
+    assert_eq!(font.glyph_paths('q'), font.glyph_paths('Q'));
+    assert!(!font.has_glyph('~'));
+    assert!(font.glyph_paths('~').is_empty());
+    assert_eq!(font.advance('~'), 1.0);
+}
+
+#[test]
+fn measures_and_lays_out_with_fixed_advance() {
+    let size = TextStyle::new(10.0);
+    assert_eq!(
+        measure_text(&BuiltinFont, "GAME OVER", &size),
+        Vec2::new(90.0, 10.0)
+    );
+
+    let mut measurer = TextMeasurer::new(&BuiltinFont);
+    let ink = measurer.ink_bounds("I", 12.0).unwrap();
+    assert_eq!(ink.min, Vec2::ZERO);
+    assert_eq!(ink.max, Vec2::new(8.0, 12.0));
+}
@@ -0,0 +1,70 @@
+use glam::Vec2;
+use vectorcade_shared::draw::{
+    DiagnosticKind, DrawCmd, Line2, OptimizeOptions, Stroke, optimize, validate,
+};
+use vectorcade_shared::snapshot::to_text;
+use vectorcade_shared::{Aabb, Circle, Rgba};
+
+fn line() -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a: Vec2::ZERO,
+        b: Vec2::X,
+        stroke: Stroke::new(Rgba::GREEN, 1.0),
+    })
+}
+
+#[test]
+fn clips_are_snapshotted_and_indented() {
+    let cmds = [
+        DrawCmd::PushClipRect(Aabb::from_min_max(Vec2::splat(-0.5), Vec2::splat(0.5))),
+        DrawCmd::PushClipCircle(Circle::new(Vec2::new(0.25, 0.0), 0.5)),
+        line(),
+        DrawCmd::PopClip,
+        DrawCmd::PopClip,
+    ];
+    assert_eq!(
+        to_text(&cmds),
+        "clip rect (-0.5 -0.5) (0.5 0.5)\n  clip circle (0.25 0) r 0.5\n    \
+         line (0 0) (1 0) w 1 glow 0 rgba(0 1 0 1)\n  pop_clip\npop_clip\n"
+    );
+    assert!(validate(&cmds).is_empty());
+}
+
+#[test]
+fn clip_scopes_are_validated() {
+    let cmds = [
+        DrawCmd::PopClip,
+        DrawCmd::PushClipRect(Aabb::from_min_max(Vec2::X, Vec2::ZERO)),
+        DrawCmd::PushClipCircle(Circle::new(Vec2::ZERO, -1.0)),
+        line(),
+    ];
+    let kinds: Vec<_> = validate(&cmds).iter().map(|d| (d.index, d.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, DiagnosticKind::UnbalancedPopClip),
+            (1, DiagnosticKind::EmptyClip),
+            (1, DiagnosticKind::UnclosedPushClip),
+            (2, DiagnosticKind::NegativeRadius { radius: -1.0 }),
+            (2, DiagnosticKind::UnclosedPushClip),
+        ]
+    );
+}
+
+#[test]
+fn empty_clip_scopes_are_removed() {
+    let rect = DrawCmd::PushClipRect(Aabb::from_min_max(-Vec2::ONE, Vec2::ONE));
+    let cmds = [
+        rect.clone(),
+        DrawCmd::PushClipCircle(Circle::new(Vec2::ZERO, 0.5)),
+        DrawCmd::PopClip,
+        line(),
+        DrawCmd::PopClip,
+        DrawCmd::PopClip,
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    // The unbalanced pop is kept for the validator to report.
+    assert_eq!(out, [rect, line(), DrawCmd::PopClip, DrawCmd::PopClip]);
+    assert_eq!(stats.clips_removed, 2);
+}
@@ -0,0 +1,235 @@
+use glam::{Mat3, Vec2};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use vectorcade_shared::draw::{
+    CURVE_TOLERANCE_PX, DiagnosticKind, DrawCmd, Stroke, flatten, segments_for_radius, tessellate,
+    validate,
+};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::snapshot::to_text;
+use vectorcade_shared::{rot2, scale2, scale2_uniform, translate2};
+
+const SQUARE: ScreenInfo = ScreenInfo {
+    width_px: 200,
+    height_px: 200,
+    dpi_scale: 1.0,
+};
+
+fn assert_close(a: Vec2, b: Vec2) {
+    assert!(a.distance(b) < 1e-4, "{a} != {b}");
+}
+
+fn polyline(cmd: Option<DrawCmd>) -> (Vec<Vec2>, bool) {
+    match cmd {
+        Some(DrawCmd::Polyline { pts, closed, .. }) => (pts, closed),
+        other => panic!("expected a polyline, got {other:?}"),
+    }
+}
+
+fn arc(start_angle: f32, end_angle: f32) -> DrawCmd {
+    DrawCmd::Arc {
+        center: Vec2::new(0.1, 0.2),
+        radius: 0.5,
+        start_angle,
+        end_angle,
+        stroke: Stroke::default(),
+    }
+}
+
+fn arc_end(cmd: &DrawCmd, end: bool) -> Vec2 {
+    let DrawCmd::Arc {
+        center,
+        radius,
+        start_angle,
+        end_angle,
+        ..
+    } = *cmd
+    else {
+        panic!("expected an arc, got {cmd:?}");
+    };
+    let t = if end { end_angle } else { start_angle };
+    center + Vec2::from_angle(t) * radius
+}
+
+#[test]
+fn segment_count_grows_with_radius_and_sweep() {
+    assert_eq!(segments_for_radius(0.1, TAU), 8);
+    assert_eq!(segments_for_radius(1e9, TAU), 512);
+    let small = segments_for_radius(10.0, TAU);
+    let large = segments_for_radius(100.0, TAU);
+    assert!(small < large);
+    assert_eq!(segments_for_radius(100.0, PI), large.div_ceil(2));
+    assert_eq!(segments_for_radius(100.0, -PI), large.div_ceil(2));
+    assert_eq!(segments_for_radius(100.0, 0.0), 1);
+}
+
+#[test]
+fn circle_tessellation_stays_within_tolerance() {
+    let cmd = DrawCmd::Circle {
+        center: Vec2::ZERO,
+        radius: 0.5,
+        stroke: Stroke::default(),
+    };
+    let (pts, closed) = polyline(tessellate(&cmd, Mat3::IDENTITY, SQUARE));
+    assert!(closed);
+    assert_eq!(pts.len(), segments_for_radius(50.0, TAU));
+    for (a, b) in pts.iter().zip(pts.iter().cycle().skip(1)) {
+        assert!((a.length() - 0.5).abs() < 1e-5);
+        let sagitta_px = (0.5 - ((*a + *b) * 0.5).length()) * 100.0;
+        assert!(sagitta_px <= CURVE_TOLERANCE_PX + 1e-3);
+    }
+}
+
+#[test]
+fn transform_and_screen_set_the_detail() {
+    let cmd = DrawCmd::Circle {
+        center: Vec2::ZERO,
+        radius: 0.1,
+        stroke: Stroke::default(),
+    };
+    let (base, _) = polyline(tessellate(&cmd, Mat3::IDENTITY, SQUARE));
+    let (zoomed, _) = polyline(tessellate(&cmd, scale2_uniform(8.0), SQUARE));
+    let (big, _) = polyline(tessellate(&cmd, Mat3::IDENTITY, ScreenInfo::default()));
+    assert!(zoomed.len() > base.len());
+    assert!(big.len() > base.len());
+    assert!(tessellate(&DrawCmd::PopTransform, Mat3::IDENTITY, SQUARE).is_none());
+}
+
+#[test]
+fn arcs_are_open_with_exact_ends_in_either_direction() {
+    for (start, end) in [(0.0, FRAC_PI_2), (PI, -FRAC_PI_2)] {
+        let cmd = arc(start, end);
+        let (pts, closed) = polyline(tessellate(&cmd, Mat3::IDENTITY, SQUARE));
+        assert!(!closed);
+        assert_close(pts[0], arc_end(&cmd, false));
+        assert_close(*pts.last().unwrap(), arc_end(&cmd, true));
+        let mid = pts[pts.len() / 2] - Vec2::new(0.1, 0.2);
+        let mid_angle = (start + end) * 0.5;
+        assert_close(mid, Vec2::from_angle(mid_angle) * 0.5);
+    }
+}
+
+#[test]
+fn ellipse_tessellation_follows_rotation() {
+    let cmd = DrawCmd::Ellipse {
+        center: Vec2::ZERO,
+        radii: Vec2::new(0.5, 0.25),
+        rotation: FRAC_PI_2,
+        stroke: Stroke::default(),
+    };
+    let (pts, closed) = polyline(tessellate(&cmd, Mat3::IDENTITY, SQUARE));
+    assert!(closed);
+    assert_close(pts[0], Vec2::new(0.0, 0.5));
+}
+
+#[test]
+fn flatten_keeps_circles_under_similarity() {
+    let circle = DrawCmd::Circle {
+        center: Vec2::X * 0.1,
+        radius: 0.2,
+        stroke: Stroke::default(),
+    };
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::Y) * rot2(1.0) * scale2_uniform(2.0)),
+        circle,
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    let DrawCmd::Circle { center, radius, .. } = out[0] else {
+        panic!("expected a circle, got {:?}", out[0]);
+    };
+    assert_close(center, Vec2::Y + Vec2::from_angle(1.0) * 0.2);
+    assert!((radius - 0.4).abs() < 1e-5);
+}
+
+#[test]
+fn flatten_turns_stretched_circles_into_ellipses() {
+    let cmds = [
+        DrawCmd::PushTransform(rot2(FRAC_PI_2) * scale2(Vec2::new(1.0, 3.0))),
+        DrawCmd::Circle {
+            center: Vec2::ZERO,
+            radius: 0.1,
+            stroke: Stroke::default(),
+        },
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    let DrawCmd::Ellipse {
+        radii, rotation, ..
+    } = out[0]
+    else {
+        panic!("expected an ellipse, got {:?}", out[0]);
+    };
+    assert_close(radii, Vec2::new(0.3, 0.1));
+    // The long axis, originally +Y, now points along -X or +X.
+    assert!(Vec2::from_angle(rotation).x.abs() > 0.9999);
+}
+
+#[test]
+fn flatten_maps_arc_ends_through_rotation_and_reflection() {
+    let src = arc(0.3, 1.9);
+    for m in [
+        rot2(0.7) * scale2_uniform(1.5),
+        scale2(Vec2::new(-2.0, 2.0)),
+        translate2(Vec2::X) * scale2(Vec2::new(1.0, -1.0)),
+    ] {
+        let cmds = [
+            DrawCmd::PushTransform(m),
+            src.clone(),
+            DrawCmd::PopTransform,
+        ];
+        let mut out = Vec::new();
+        flatten(&cmds, &mut out).unwrap();
+        assert_close(
+            arc_end(&out[0], false),
+            m.transform_point2(arc_end(&src, false)),
+        );
+        assert_close(
+            arc_end(&out[0], true),
+            m.transform_point2(arc_end(&src, true)),
+        );
+    }
+
+    let cmds = [
+        DrawCmd::PushTransform(scale2(Vec2::new(1.0, 2.0))),
+        src.clone(),
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    let (pts, closed) = polyline(out.pop());
+    assert!(!closed);
+    assert_close(pts[0], Vec2::new(1.0, 2.0) * arc_end(&src, false));
+}
+
+#[test]
+fn curves_are_validated_and_snapshotted() {
+    let cmds = [
+        DrawCmd::Circle {
+            center: Vec2::ZERO,
+            radius: -1.0,
+            stroke: Stroke::default(),
+        },
+        arc(0.0, f32::NAN),
+        DrawCmd::Ellipse {
+            center: Vec2::ZERO,
+            radii: Vec2::new(0.5, 0.25),
+            rotation: 0.0,
+            stroke: Stroke::default(),
+        },
+    ];
+    let kinds: Vec<_> = validate(&cmds).iter().map(|d| (d.index, d.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, DiagnosticKind::NegativeRadius { radius: -1.0 }),
+            (1, DiagnosticKind::NonFinite),
+        ]
+    );
+    let text = to_text(&cmds[2..]);
+    assert_eq!(
+        text,
+        "ellipse (0 0) radii (0.5 0.25) rot 0 w 1 glow 0 rgba(1 1 1 1)\n"
+    );
+}
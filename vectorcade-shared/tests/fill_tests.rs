@@ -0,0 +1,70 @@
+use glam::Vec2;
+use vectorcade_shared::draw::{
+    DiagnosticKind, DrawCmd, OptimizeOptions, flatten, optimize, validate,
+};
+use vectorcade_shared::snapshot::to_text;
+use vectorcade_shared::{FillRule, Rgba, scale2_uniform, translate2};
+
+fn triangle() -> Vec<Vec2> {
+    vec![Vec2::ZERO, Vec2::X, Vec2::Y]
+}
+
+fn fill(contours: Vec<Vec<Vec2>>) -> DrawCmd {
+    DrawCmd::FillPolygon {
+        contours,
+        rule: FillRule::EvenOdd,
+        color: Rgba::RED,
+    }
+}
+
+#[test]
+fn fill_snapshot_lists_contours() {
+    let cmd = fill(vec![triangle(), vec![Vec2::new(0.1, 0.1)]]);
+    assert_eq!(
+        to_text(&[cmd]),
+        "fill evenodd rgba(1 0 0 1) | (0 0) (1 0) (0 1) | (0.1 0.1)\n"
+    );
+}
+
+#[test]
+fn fills_are_validated() {
+    let cmds = [
+        fill(vec![vec![Vec2::ZERO, Vec2::X]]),
+        fill(vec![vec![Vec2::ZERO, Vec2::X, Vec2::INFINITY]]),
+        fill(vec![triangle()]),
+    ];
+    let kinds: Vec<_> = validate(&cmds).iter().map(|d| (d.index, d.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, DiagnosticKind::EmptyFill),
+            (1, DiagnosticKind::NonFinite)
+        ]
+    );
+}
+
+#[test]
+fn fills_are_flattened_and_culled() {
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::X) * scale2_uniform(2.0)),
+        fill(vec![triangle()]),
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    assert_eq!(
+        out,
+        [DrawCmd::FillPolygon {
+            contours: vec![vec![Vec2::X, Vec2::new(3.0, 0.0), Vec2::new(1.0, 2.0)]],
+            rule: FillRule::EvenOdd,
+            color: Rgba::RED,
+        }]
+    );
+
+    let offscreen = [fill(vec![
+        triangle().iter().map(|&p| p + Vec2::X * 2.0).collect(),
+    ])];
+    let stats = optimize(&offscreen, &mut out, &OptimizeOptions::default());
+    assert_eq!(stats.culled, 1);
+    assert!(out.is_empty());
+}
@@ -0,0 +1,144 @@
+use glam::{Mat3, Vec2};
+use vectorcade_shared::draw::{DrawCmd, FlattenError, LayerId, Line2, Stroke, flatten, rect_wire};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::{Rgba, rot2, scale2_uniform, translate2};
+
+fn line(a: Vec2, b: Vec2) -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a,
+        b,
+        stroke: Stroke::default(),
+    })
+}
+
+fn assert_close(a: Vec2, b: Vec2) {
+    assert!(a.distance(b) < 1e-5, "{a} != {b}");
+}
+
+#[test]
+fn pushes_compose_with_parent() {
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::new(0.5, 0.0))),
+        DrawCmd::PushTransform(scale2_uniform(2.0)),
+        line(Vec2::ZERO, Vec2::new(0.1, 0.0)),
+        DrawCmd::PopTransform,
+        line(Vec2::ZERO, Vec2::new(0.1, 0.0)),
+        DrawCmd::PopTransform,
+        line(Vec2::ZERO, Vec2::new(0.1, 0.0)),
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    let ends: Vec<(Vec2, Vec2)> = out
+        .iter()
+        .map(|c| match c {
+            DrawCmd::Line(l) => (l.a, l.b),
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+    assert_eq!(ends.len(), 3);
+    assert_close(ends[0].0, Vec2::new(0.5, 0.0));
+    assert_close(ends[0].1, Vec2::new(0.7, 0.0));
+    assert_close(ends[1].1, Vec2::new(0.6, 0.0));
+    assert_close(ends[2].1, Vec2::new(0.1, 0.0));
+}
+
+#[test]
+fn child_transform_applies_first() {
+    let cmds = [
+        DrawCmd::PushTransform(rot2(std::f32::consts::FRAC_PI_2)),
+        DrawCmd::PushTransform(translate2(Vec2::new(0.5, 0.0))),
+        line(Vec2::ZERO, Vec2::ZERO),
+        DrawCmd::PopTransform,
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    let DrawCmd::Line(l) = &out[0] else {
+        panic!("expected a line");
+    };
+    assert_close(l.a, Vec2::new(0.0, 0.5));
+}
+
+#[test]
+fn polylines_and_text_are_transformed() {
+    let stroke = Stroke::new(Rgba::GREEN, 3.0);
+    let cmds = [
+        DrawCmd::Clear { color: Rgba::BLACK },
+        DrawCmd::PushTransform(translate2(Vec2::new(0.25, -0.25))),
+        rect_wire(Vec2::ZERO, Vec2::ONE * 0.5, stroke),
+        DrawCmd::Text {
+            pos: Vec2::ZERO,
+            text: "HI".into(),
+            size_px: 24.0,
+            color: Rgba::WHITE,
+            style: FontStyleId::DEFAULT,
+        },
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    assert_eq!(out.len(), 3);
+    assert_eq!(out[0], cmds[0]);
+    let DrawCmd::Polyline {
+        pts,
+        closed,
+        stroke: s,
+    } = &out[1]
+    else {
+        panic!("expected a polyline");
+    };
+    assert!(*closed);
+    assert_eq!(*s, stroke);
+    assert_close(pts[2], Vec2::new(0.75, 0.25));
+    let DrawCmd::Text { pos, size_px, .. } = &out[2] else {
+        panic!("expected text");
+    };
+    assert_close(*pos, Vec2::new(0.25, -0.25));
+    assert_eq!(*size_px, 24.0);
+}
+
+#[test]
+fn layers_pass_through_and_output_has_no_transforms() {
+    let cmds = [
+        DrawCmd::BeginLayer(LayerId::new("hud", 0)),
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        DrawCmd::PopTransform,
+        DrawCmd::EndLayer,
+    ];
+    let mut out = vec![line(Vec2::ZERO, Vec2::ONE)];
+    flatten(&cmds, &mut out).unwrap();
+    assert_eq!(
+        out,
+        [
+            DrawCmd::BeginLayer(LayerId::new("hud", 0)),
+            DrawCmd::EndLayer
+        ]
+    );
+}
+
+#[test]
+fn unbalanced_pop_is_an_error() {
+    let cmds = [
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        DrawCmd::PopTransform,
+        DrawCmd::PopTransform,
+    ];
+    let err = flatten(&cmds, &mut Vec::new()).unwrap_err();
+    assert_eq!(err, FlattenError::UnbalancedPop { index: 2 });
+    assert!(err.to_string().contains("command 2"));
+}
+
+#[test]
+fn unclosed_push_reports_outermost() {
+    let cmds = [
+        line(Vec2::ZERO, Vec2::ONE),
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        DrawCmd::PopTransform,
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+    ];
+    assert_eq!(
+        flatten(&cmds, &mut Vec::new()),
+        Err(FlattenError::UnclosedPush { index: 1, depth: 2 })
+    );
+}
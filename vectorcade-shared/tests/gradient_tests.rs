@@ -0,0 +1,112 @@
+use glam::Vec2;
+use vectorcade_shared::draw::{
+    DiagnosticKind, DrawCmd, OptimizeOptions, Stroke, StrokeVertex, flatten, optimize, trail,
+    validate,
+};
+use vectorcade_shared::snapshot::to_text;
+use vectorcade_shared::{Rgba, translate2};
+
+fn verts(cmd: &DrawCmd) -> &[StrokeVertex] {
+    match cmd {
+        DrawCmd::GradientPolyline { verts, .. } => verts,
+        other => panic!("expected a gradient polyline, got {other:?}"),
+    }
+}
+
+#[test]
+fn trail_fades_toward_oldest_point() {
+    let history = [Vec2::ZERO, Vec2::X, Vec2::X * 2.0];
+    let stroke = Stroke::with_glow(Rgba::CYAN.with_a(0.8), 3.0, 0.5);
+    let cmd = trail(&history, stroke, 2.0);
+    let v = verts(&cmd);
+    let alphas: Vec<f32> = v.iter().map(|v| v.color.3).collect();
+    assert_eq!(alphas, [0.0, 0.2, 0.8]);
+    assert!(v.iter().all(|v| v.width_px == 3.0));
+    assert_eq!(v[2].pos, Vec2::X * 2.0);
+    assert!(matches!(
+        cmd,
+        DrawCmd::GradientPolyline {
+            closed: false,
+            glow: 0.5,
+            ..
+        }
+    ));
+
+    let flat = trail(&history, stroke, 0.0);
+    assert!(verts(&flat).iter().all(|v| v.color.3 == 0.8));
+}
+
+#[test]
+fn single_point_trail_is_fully_opaque() {
+    let stroke = Stroke::new(Rgba::CYAN.with_a(0.8), 3.0);
+    let cmd = trail(&[Vec2::X], stroke, 2.0);
+    assert_eq!(verts(&cmd), [StrokeVertex::new(Vec2::X, stroke.color, 3.0)]);
+    assert!(verts(&trail(&[], stroke, 2.0)).is_empty());
+}
+
+#[test]
+fn gradient_snapshot_lists_every_vertex() {
+    let cmd = DrawCmd::GradientPolyline {
+        verts: vec![
+            StrokeVertex::new(Vec2::ZERO, Rgba::RED, 1.0),
+            StrokeVertex::new(Vec2::X, Rgba::BLUE.with_a(0.5), 4.0),
+        ],
+        closed: true,
+        glow: 0.25,
+    };
+    assert_eq!(
+        to_text(&[cmd]),
+        "gradient closed glow 0.25 | (0 0) w 1 rgba(1 0 0 1) | (1 0) w 4 rgba(0 0 1 0.5)\n"
+    );
+}
+
+#[test]
+fn gradient_vertices_are_validated() {
+    let cmds = [
+        DrawCmd::GradientPolyline {
+            verts: vec![StrokeVertex::new(Vec2::ZERO, Rgba::RED, 1.0)],
+            closed: false,
+            glow: 0.0,
+        },
+        DrawCmd::GradientPolyline {
+            verts: vec![
+                StrokeVertex::new(Vec2::ZERO, Rgba::RED, -2.0),
+                StrokeVertex::new(Vec2::NAN, Rgba::RED, 1.0),
+            ],
+            closed: false,
+            glow: 0.0,
+        },
+    ];
+    let kinds: Vec<_> = validate(&cmds).iter().map(|d| (d.index, d.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, DiagnosticKind::TooFewPoints { count: 1 }),
+            (1, DiagnosticKind::NonFinite),
+            (1, DiagnosticKind::NegativeWidth { width_px: -2.0 }),
+        ]
+    );
+}
+
+#[test]
+fn gradients_are_flattened_and_culled() {
+    let line = trail(&[Vec2::ZERO, Vec2::X * 0.5], Stroke::default(), 1.0);
+    let cmds = [
+        DrawCmd::PushTransform(translate2(Vec2::Y * 0.25)),
+        line.clone(),
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    flatten(&cmds, &mut out).unwrap();
+    assert_eq!(verts(&out[0])[1].pos, Vec2::new(0.5, 0.25));
+    assert_eq!(verts(&out[0])[1].color, verts(&line)[1].color);
+
+    let offscreen = [
+        DrawCmd::PushTransform(translate2(Vec2::X * 3.0)),
+        line,
+        DrawCmd::PopTransform,
+    ];
+    let stats = optimize(&offscreen, &mut out, &OptimizeOptions::default());
+    assert_eq!(stats.culled, 1);
+    assert!(out.is_empty());
+}
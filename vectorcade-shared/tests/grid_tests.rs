@@ -0,0 +1,184 @@
+use glam::{IVec2, Vec2};
+use vectorcade_shared::font::{
+    FontStyleId, GlyphPathCmd, GridFont, GridFontError, GridFontErrorKind, GridGlyph, VectorFont,
+};
+
+const SRC: &str = "\
+# Asteroids-style capitals.
+grid 4 6
+style 1
+advance 6
+
+glyph A
+  stroke 0,0 0,4 2,6 4,4 4,0
+  stroke 0,3 4,3
+glyph . advance 2
+  stroke 0,0
+glyph U+0020
+";
+
+fn kind(src: &str) -> GridFontErrorKind {
+    GridFont::parse(src).unwrap_err().kind
+}
+
+fn ivecs(pts: &[(i32, i32)]) -> Vec<IVec2> {
+    pts.iter().map(|&(x, y)| IVec2::new(x, y)).collect()
+}
+
+#[test]
+fn parses_header_glyphs_and_strokes() {
+    let font = GridFont::parse(SRC).unwrap();
+    assert_eq!(font.style, FontStyleId::ATARI);
+    assert_eq!((font.width, font.height, font.baseline), (4, 6, 0));
+    assert_eq!(font.advance, 6);
+    assert_eq!(font.glyphs.len(), 3);
+    assert_eq!(
+        font.glyphs[&'A'].strokes,
+        vec![
+            ivecs(&[(0, 0), (0, 4), (2, 6), (4, 4), (4, 0)]),
+            ivecs(&[(0, 3), (4, 3)]),
+        ]
+    );
+    assert_eq!(font.glyphs[&'.'].advance, Some(2));
+    assert!(font.glyphs[&' '].strokes.is_empty());
+}
+
+#[test]
+fn glyphs_are_scaled_to_font_units() {
+    let font = GridFont::parse(SRC).unwrap();
+    assert_eq!(font.style_id(), FontStyleId::ATARI);
+    assert!(font.has_glyph('A') && font.has_glyph(' '));
+    assert!(!font.has_glyph('B'));
+
+    let a = font.glyph_paths('A');
+    assert_eq!(a.len(), 1);
+    assert_eq!(a[0].cmds.len(), 7);
+    assert_eq!(
+        a[0].cmds[2],
+        GlyphPathCmd::LineTo(Vec2::new(2.0, 6.0) / 6.0)
+    );
+    assert_eq!(a[0].cmds[5], GlyphPathCmd::MoveTo(Vec2::new(0.0, 0.5)));
+
+    // A lone point is a zero-length line.
+    let dot = &font.glyph_paths('.')[0].cmds;
+    assert_eq!(
+        dot,
+        &[
+            GlyphPathCmd::MoveTo(Vec2::ZERO),
+            GlyphPathCmd::LineTo(Vec2::ZERO)
+        ]
+    );
+
+    assert!(font.glyph_paths(' ').is_empty());
+    assert_eq!(font.advance('A'), 1.0);
+    assert_eq!(font.advance('.'), 2.0 / 6.0);
+    assert_eq!(font.advance('B'), 1.0);
+}
+
+#[test]
+fn rows_below_the_baseline_are_descenders() {
+    let font = GridFont::parse("grid 4 8\nbaseline 2\nglyph ,\nstroke 2,2 1,0\n").unwrap();
+    assert_eq!(
+        font.glyph_paths(',')[0].cmds,
+        vec![
+            GlyphPathCmd::MoveTo(Vec2::new(2.0, 0.0) / 6.0),
+            GlyphPathCmd::LineTo(Vec2::new(1.0, -2.0) / 6.0),
+        ]
+    );
+    assert_eq!(font.advance(','), 4.0 / 6.0);
+}
+
+#[test]
+fn text_round_trips() {
+    let font = GridFont::parse(SRC).unwrap();
+    let text = font.to_text();
+    assert_eq!(
+        text,
+        "grid 4 6\nstyle 1\nbaseline 0\nadvance 6\n\
+         \nglyph U+0020\n\
+         \nglyph . advance 2\n  stroke 0,0\n\
+         \nglyph A\n  stroke 0,0 0,4 2,6 4,4 4,0\n  stroke 0,3 4,3\n"
+    );
+    assert_eq!(GridFont::parse(&text).unwrap(), font);
+}
+
+#[test]
+fn fonts_built_in_code_round_trip() {
+    let mut font = GridFont::new(FontStyleId::MIDWAY, 3, 5);
+    font.baseline = 1;
+    font.glyphs.insert(
+        '#',
+        GridGlyph {
+            advance: Some(4),
+            strokes: vec![ivecs(&[(0, 2), (3, 2)]), ivecs(&[(1, 0), (1, 5)])],
+        },
+    );
+    font.glyphs.insert('\t', GridGlyph::default());
+    let text = font.to_text();
+    assert!(text.contains("glyph # advance 4\n"));
+    assert!(text.contains("glyph U+0009\n"));
+    assert_eq!(GridFont::parse(&text).unwrap(), font);
+}
+
+#[test]
+fn errors_carry_line_numbers() {
+    assert_eq!(
+        GridFont::parse("grid 4 6\n\nglyph A\nstroke 0,0 5,0\n").unwrap_err(),
+        GridFontError {
+            line: 4,
+            kind: GridFontErrorKind::PointOutsideGrid { x: 5, y: 0 }
+        }
+    );
+    assert_eq!(
+        GridFont::parse("# empty\n\nglyph A\n").unwrap_err(),
+        GridFontError {
+            line: 3,
+            kind: GridFontErrorKind::MissingGrid
+        }
+    );
+    assert_eq!(GridFont::parse("").unwrap_err().line, 1);
+    assert_eq!(
+        kind("grid 4 6\nkern A V 1"),
+        GridFontErrorKind::UnknownKeyword
+    );
+    assert_eq!(kind("grid 4"), GridFontErrorKind::BadArguments);
+    assert_eq!(kind("grid 4 -6"), GridFontErrorKind::BadNumber);
+    assert_eq!(kind("grid 0 6"), GridFontErrorKind::BadGrid);
+    assert_eq!(kind("grid 4 6\nbaseline 6"), GridFontErrorKind::BadGrid);
+    assert_eq!(
+        kind("grid 4 6\ngrid 4 6"),
+        GridFontErrorKind::MisplacedHeader
+    );
+    assert_eq!(
+        kind("grid 4 6\nglyph A\nadvance 5"),
+        GridFontErrorKind::MisplacedHeader
+    );
+    assert_eq!(kind("grid 4 6\nglyph AB"), GridFontErrorKind::BadChar);
+    assert_eq!(kind("grid 4 6\nglyph U+D800"), GridFontErrorKind::BadChar);
+    assert_eq!(
+        kind("grid 4 6\nglyph A width 3"),
+        GridFontErrorKind::BadArguments
+    );
+    assert_eq!(
+        kind("grid 4 6\nglyph A\nglyph A"),
+        GridFontErrorKind::DuplicateGlyph { ch: 'A' }
+    );
+    assert_eq!(
+        kind("grid 4 6\nstroke 0,0"),
+        GridFontErrorKind::StrokeOutsideGlyph
+    );
+    assert_eq!(
+        kind("grid 4 6\nglyph A\nstroke"),
+        GridFontErrorKind::EmptyStroke
+    );
+    assert_eq!(
+        kind("grid 4 6\nglyph A\nstroke 0;0"),
+        GridFontErrorKind::BadPoint
+    );
+    assert_eq!(
+        GridFont::parse("grid 4 6\nglyph A\nstroke 1,x")
+            .unwrap_err()
+            .to_string(),
+        "line 3: expected a point as x,y"
+    );
+}
@@ -0,0 +1,122 @@
+use glam::{IVec2, Vec2};
+use vectorcade_shared::font::{
+    FontStyleId, GlyphPathCmd, JhfError, JhfErrorKind, JhfFont, JhfOptions, VectorFont, parse_jhf,
+};
+
+/// A space, then an A (apex at -12, feet on the baseline at 9) with
+/// its record wrapped onto a second line.
+const FONT: &str = "12345  1JZ\n  501  9I[RFJ[\n RRFZ[ RMTWT\n";
+
+fn error(src: &str) -> JhfError {
+    parse_jhf(src).unwrap_err()
+}
+
+#[test]
+fn records_decode_bounds_and_pen_up_strokes() {
+    let records = parse_jhf(FONT).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].number, 12345);
+    assert_eq!((records[0].left, records[0].right), (-8, 8));
+    assert!(records[0].strokes.is_empty());
+
+    let a = &records[1];
+    assert_eq!(a.number, 501);
+    assert_eq!((a.left, a.right), (-9, 9));
+    assert_eq!(
+        a.strokes,
+        vec![
+            vec![IVec2::new(0, -12), IVec2::new(-8, 9)],
+            vec![IVec2::new(0, -12), IVec2::new(8, 9)],
+            vec![IVec2::new(-5, 2), IVec2::new(5, 2)],
+        ]
+    );
+}
+
+#[test]
+fn blank_lines_and_crlf_are_tolerated() {
+    let src = "\r\n12345  1JZ\r\n\r\n  501  9I[RFJ[ RRFZ[ RMTWT  \r\n";
+    assert_eq!(parse_jhf(src).unwrap(), parse_jhf(FONT).unwrap());
+}
+
+#[test]
+fn errors_carry_line_numbers() {
+    assert_eq!(
+        error("12345  1JZ\n  5x1  9I[RF"),
+        JhfError {
+            line: 2,
+            kind: JhfErrorKind::BadNumber
+        }
+    );
+    assert_eq!(error("  501  0").kind, JhfErrorKind::BadVertexCount);
+    assert_eq!(error("  501").kind, JhfErrorKind::BadVertexCount);
+    assert_eq!(
+        error("12345  1JZ\n  501  9I[RF"),
+        JhfError {
+            line: 2,
+            kind: JhfErrorKind::Truncated {
+                expected: 9,
+                found: 2
+            }
+        }
+    );
+    assert_eq!(
+        error("12345  1JZ\n\n  501  2I[\tF"),
+        JhfError {
+            line: 3,
+            kind: JhfErrorKind::BadCoordinate { ch: '\t' }
+        }
+    );
+    assert_eq!(
+        error("  501  2I[RF\nXY"),
+        JhfError {
+            line: 2,
+            kind: JhfErrorKind::BadNumber
+        }
+    );
+    assert_eq!(error("12345  1JZXX").kind, JhfErrorKind::TrailingData);
+    assert_eq!(error("12345  1J\u{e9}").kind, JhfErrorKind::NotAscii);
+    assert_eq!(
+        error("12345  1JZ\n\n  501  2I[RF!").to_string(),
+        "line 3: unexpected data after last vertex"
+    );
+}
+
+#[test]
+fn font_maps_records_to_characters_in_font_units() {
+    let font = JhfFont::parse(FONT, FontStyleId::MIDWAY, JhfOptions::default()).unwrap();
+    assert_eq!(font.style_id(), FontStyleId::MIDWAY);
+    assert_eq!(font.len(), 2);
+    assert!(font.has_glyph(' ') && font.has_glyph('!'));
+    assert!(!font.has_glyph('A'));
+    assert!(font.glyph_paths(' ').is_empty());
+
+    let paths = font.glyph_paths('!');
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].cmds.len(), 6);
+    assert_eq!(
+        paths[0].cmds[0],
+        GlyphPathCmd::MoveTo(Vec2::new(9.0, 21.0) / 21.0)
+    );
+    assert_eq!(
+        paths[0].cmds[1],
+        GlyphPathCmd::LineTo(Vec2::new(1.0, 0.0) / 21.0)
+    );
+    assert_eq!(font.advance('!'), 18.0 / 21.0);
+    assert_eq!(font.advance('~'), 16.0 / 21.0);
+}
+
+#[test]
+fn options_choose_first_char_and_scale() {
+    let options = JhfOptions {
+        first_char: '@',
+        baseline: 0,
+        em: 10.0,
+    };
+    let font = JhfFont::parse(FONT, FontStyleId::ATARI, options).unwrap();
+    assert!(font.has_glyph('@') && font.has_glyph('A'));
+    assert_eq!(
+        font.glyph_paths('A')[0].cmds[0],
+        GlyphPathCmd::MoveTo(Vec2::new(0.9, 1.2))
+    );
+    assert_eq!(font.advance('A'), 1.8);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+// Kept apart from serde_tests.rs: filling the process-wide intern table
+// would reject layers deserialized by other tests in the same binary.
+
+use vectorcade_shared::draw::LayerId;
+
+fn from_json(name: &str, z: i32) -> Result<LayerId, serde_json::Error> {
+    serde_json::from_str(&format!(r#"{{"name":"{name}","z":{z}}}"#))
+}
+
+#[test]
+fn over_long_and_over_limit_names_are_rejected() {
+    let long = "x".repeat(LayerId::MAX_NAME_LEN + 1);
+    let err = from_json(&long, 0).unwrap_err();
+    assert!(err.to_string().contains("longer than"), "{err}");
+
+    for i in 0..LayerId::MAX_INTERNED_NAMES {
+        let name = format!("layer-{i}");
+        assert_eq!(from_json(&name, 0).unwrap().name, name);
+    }
+    // Known names still resolve; new ones are rejected.
+    assert_eq!(from_json("layer-7", 3).unwrap(), LayerId::new("layer-7", 3));
+    assert_eq!(from_json("hud", 100).unwrap(), LayerId::HUD);
+    let err = from_json("one-too-many", 5).unwrap_err();
+    assert!(err.to_string().contains("distinct layer names"), "{err}");
+}
@@ -0,0 +1,121 @@
+use glam::Vec2;
+use vectorcade_shared::draw::{
+    BlendMode, DrawCmd, LayerId, LayerSet, LayerStyle, Line2, Stroke, sort_layers,
+};
+use vectorcade_shared::{Rgba, translate2};
+
+fn line(x: f32) -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a: Vec2::new(x, 0.0),
+        b: Vec2::new(x, 1.0),
+        stroke: Stroke::default(),
+    })
+}
+
+fn layer(id: LayerId, body: &[DrawCmd]) -> Vec<DrawCmd> {
+    let mut cmds = vec![DrawCmd::BeginLayer(id)];
+    cmds.extend_from_slice(body);
+    cmds.push(DrawCmd::EndLayer);
+    cmds
+}
+
+fn sorted(cmds: &[DrawCmd], layers: &LayerSet) -> Vec<DrawCmd> {
+    let mut out = vec![line(9.0)];
+    sort_layers(cmds, &mut out, layers);
+    out
+}
+
+#[test]
+fn layers_are_stably_sorted_by_z() {
+    let second_hud = LayerId::new("score", LayerId::HUD.z);
+    let mut cmds = layer(LayerId::HUD, &[line(1.0)]);
+    cmds.extend(layer(LayerId::PLAYFIELD, &[line(2.0)]));
+    cmds.push(DrawCmd::Clear { color: Rgba::BLACK });
+    cmds.extend(layer(second_hud, &[line(3.0)]));
+    cmds.extend(layer(LayerId::BACKGROUND, &[line(4.0)]));
+
+    let mut expected = vec![DrawCmd::Clear { color: Rgba::BLACK }];
+    expected.extend(layer(LayerId::BACKGROUND, &[line(4.0)]));
+    expected.extend(layer(LayerId::PLAYFIELD, &[line(2.0)]));
+    expected.extend(layer(LayerId::HUD, &[line(1.0)]));
+    expected.extend(layer(second_hud, &[line(3.0)]));
+    assert_eq!(sorted(&cmds, &LayerSet::new()), expected);
+}
+
+#[test]
+fn nested_layers_sort_within_their_parent() {
+    let (low, high) = (LayerId::new("low", -5), LayerId::new("high", 5));
+    let mut body = layer(high, &[line(1.0)]);
+    body.push(line(2.0));
+    body.extend(layer(low, &[line(3.0)]));
+    let cmds = layer(LayerId::PLAYFIELD, &body);
+
+    let mut expected_body = vec![line(2.0)];
+    expected_body.extend(layer(low, &[line(3.0)]));
+    expected_body.extend(layer(high, &[line(1.0)]));
+    assert_eq!(
+        sorted(&cmds, &LayerSet::new()),
+        layer(LayerId::PLAYFIELD, &expected_body)
+    );
+}
+
+#[test]
+fn hidden_layers_are_dropped() {
+    let mut layers = LayerSet::new();
+    assert!(!layers.toggle_visible(LayerId::DEBUG));
+    assert!(!layers.is_visible(LayerId::DEBUG));
+    assert!(layers.is_visible(LayerId::HUD));
+
+    let mut cmds = layer(LayerId::DEBUG, &layer(LayerId::HUD, &[line(1.0)]));
+    cmds.extend(layer(LayerId::HUD, &[line(2.0)]));
+    assert_eq!(sorted(&cmds, &layers), layer(LayerId::HUD, &[line(2.0)]));
+
+    layers.set_visible(LayerId::DEBUG, true);
+    assert_eq!(sorted(&cmds, &layers).len(), cmds.len());
+}
+
+#[test]
+fn layer_blend_wraps_its_contents() {
+    let mut layers = LayerSet::new();
+    layers.style_mut(LayerId::PLAYFIELD).blend = Some(BlendMode::Additive);
+    let glow = Stroke::with_glow(Rgba::CYAN, 2.0, 0.5);
+    layers.set_style(
+        LayerId::HUD,
+        LayerStyle {
+            stroke: Some(glow),
+            ..LayerStyle::default()
+        },
+    );
+    assert_eq!(layers.style(LayerId::HUD).stroke, Some(glow));
+    assert_eq!(layers.style(LayerId::DEBUG), LayerStyle::default());
+
+    let cmds = layer(LayerId::PLAYFIELD, &[line(1.0)]);
+    assert_eq!(
+        sorted(&cmds, &layers),
+        [
+            DrawCmd::BeginLayer(LayerId::PLAYFIELD),
+            DrawCmd::PushBlend(BlendMode::Additive),
+            line(1.0),
+            DrawCmd::PopBlend,
+            DrawCmd::EndLayer,
+        ]
+    );
+}
+
+#[test]
+fn layers_inside_scopes_stay_in_place() {
+    let mut scoped = vec![DrawCmd::PushTransform(translate2(Vec2::X))];
+    scoped.extend(layer(LayerId::HUD, &[line(1.0)]));
+    scoped.push(DrawCmd::PopTransform);
+    let mut cmds = layer(LayerId::BACKGROUND, &[line(2.0)]);
+    cmds.extend_from_slice(&scoped);
+
+    // The scope counts as unlayered, so it comes first, HUD and all.
+    let mut expected = scoped;
+    expected.extend(layer(LayerId::BACKGROUND, &[line(2.0)]));
+    assert_eq!(sorted(&cmds, &LayerSet::new()), expected);
+
+    // An unclosed layer runs to the end of the list.
+    let open = [DrawCmd::BeginLayer(LayerId::HUD), line(1.0)];
+    assert_eq!(sorted(&open, &LayerSet::new()), open);
+}
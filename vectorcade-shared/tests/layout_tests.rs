@@ -0,0 +1,152 @@
+use glam::Vec2;
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{DrawCmd, DrawList, Stroke};
+use vectorcade_shared::font::{
+    Align, Anchor, FontStyleId, GlyphPath, GlyphPathCmd, TextStyle, VectorFont, layout_text,
+    measure_text, path_polylines,
+};
+
+/// Glyphs are closed 0.5 x 1 boxes one unit apart; spaces are half as wide.
+struct BoxFont;
+
+impl VectorFont for BoxFont {
+    fn style_id(&self) -> FontStyleId {
+        FontStyleId::DEFAULT
+    }
+
+    fn has_glyph(&self, _ch: char) -> bool {
+        true
+    }
+
+    fn glyph_paths(&self, ch: char) -> Vec<GlyphPath> {
+        if ch == ' ' {
+            return Vec::new();
+        }
+        vec![GlyphPath {
+            cmds: vec![
+                GlyphPathCmd::MoveTo(Vec2::ZERO),
+                GlyphPathCmd::LineTo(Vec2::new(0.5, 0.0)),
+                GlyphPathCmd::LineTo(Vec2::new(0.5, 1.0)),
+                GlyphPathCmd::LineTo(Vec2::new(0.0, 1.0)),
+                GlyphPathCmd::Close,
+            ],
+        }]
+    }
+
+    fn advance(&self, ch: char) -> f32 {
+        if ch == ' ' { 0.5 } else { 1.0 }
+    }
+}
+
+#[test]
+fn measure_sums_advances_and_spacing() {
+    let style = TextStyle::new(2.0);
+    assert_eq!(measure_text(&BoxFont, "ABC", &style), Vec2::new(6.0, 2.0));
+    let spaced = style.letter_spacing(0.25);
+    assert_eq!(measure_text(&BoxFont, "ABC", &spaced), Vec2::new(7.0, 2.0));
+    // Two lines: one em plus one line step.
+    let two = measure_text(&BoxFont, "AB\nC", &style.line_spacing(2.0));
+    assert_eq!(two, Vec2::new(4.0, 6.0));
+    assert_eq!(measure_text(&BoxFont, "", &style), Vec2::ZERO);
+}
+
+#[test]
+fn centered_middle_text_is_centered_on_the_origin() {
+    let origin = Vec2::new(0.25, -0.5);
+    let style = TextStyle::new(0.1)
+        .align(Align::Center)
+        .anchor(Anchor::Middle);
+    let layout = layout_text(&BoxFont, "GAME OVER", origin, &style);
+    assert_eq!(layout.lines, 1);
+    assert_eq!(layout.glyphs.len(), 9);
+    assert!(layout.bounds.center().distance(origin) < 1e-6);
+    assert!((layout.bounds.half_extents().x - 0.425).abs() < 1e-6);
+}
+
+#[test]
+fn lines_break_at_newlines_and_align() {
+    let style = TextStyle::new(1.0).align(Align::Right).anchor(Anchor::Top);
+    let layout = layout_text(&BoxFont, "A\nBB", Vec2::ZERO, &style);
+    assert_eq!(layout.lines, 2);
+    let origins: Vec<_> = layout
+        .glyphs
+        .iter()
+        .map(|g| (g.ch, g.line, g.origin))
+        .collect();
+    assert_eq!(
+        origins,
+        [
+            ('A', 0, Vec2::new(-1.0, -1.0)),
+            ('B', 1, Vec2::new(-2.0, -2.5)),
+            ('B', 1, Vec2::new(-1.0, -2.5)),
+        ]
+    );
+    assert_eq!(layout.bounds.min, Vec2::new(-2.0, -2.5));
+    assert_eq!(layout.bounds.max, Vec2::ZERO);
+}
+
+#[test]
+fn words_wrap_to_max_width() {
+    let style = TextStyle::new(1.0).wrap(5.0);
+    let layout = layout_text(&BoxFont, "AA BB  CC", Vec2::ZERO, &style);
+    let lines: Vec<String> = (0..layout.lines)
+        .map(|i| {
+            let line = layout.glyphs.iter().filter(|g| g.line == i);
+            line.map(|g| g.ch).collect()
+        })
+        .collect();
+    assert_eq!(lines, ["AA BB", "CC"]);
+    assert_eq!(layout.glyphs[5].origin, Vec2::new(0.0, -1.5));
+
+    // A word wider than the limit overflows on its own line.
+    let long = layout_text(&BoxFont, "A ABCDEFG B", Vec2::ZERO, &style);
+    assert_eq!(long.lines, 3);
+    assert_eq!(long.bounds.max.x, 7.0);
+}
+
+#[test]
+fn layout_becomes_polylines() {
+    let stroke = Stroke::new(Rgba::GREEN, 2.0);
+    let layout = layout_text(&BoxFont, "A B", Vec2::new(1.0, 1.0), &TextStyle::new(2.0));
+    let cmds = layout.polylines(stroke);
+    assert_eq!(cmds.len(), 2);
+    assert_eq!(
+        cmds[1],
+        DrawCmd::Polyline {
+            pts: vec![
+                Vec2::new(4.0, 1.0),
+                Vec2::new(5.0, 1.0),
+                Vec2::new(5.0, 3.0),
+                Vec2::new(4.0, 3.0),
+            ],
+            closed: true,
+            stroke,
+        }
+    );
+    let mut list = DrawList::new();
+    list.set_stroke(stroke);
+    list.text_layout(&layout);
+    assert_eq!(list.cmds(), cmds);
+}
+
+#[test]
+fn glyph_paths_split_into_sub_paths() {
+    let path = GlyphPath {
+        cmds: vec![
+            GlyphPathCmd::MoveTo(Vec2::ZERO),
+            GlyphPathCmd::LineTo(Vec2::X),
+            GlyphPathCmd::MoveTo(Vec2::Y),
+            GlyphPathCmd::MoveTo(Vec2::ONE),
+            GlyphPathCmd::LineTo(Vec2::X),
+            GlyphPathCmd::LineTo(Vec2::Y),
+            GlyphPathCmd::Close,
+        ],
+    };
+    assert_eq!(
+        path_polylines(&path),
+        [
+            (vec![Vec2::ZERO, Vec2::X], false),
+            (vec![Vec2::ONE, Vec2::X, Vec2::Y], true),
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+use glam::Vec2;
+use vectorcade_shared::draw::{
+    BlendMode, DrawCmd, DrawList, LayerId, LayerSet, Line2, Stroke, rect_wire, validate,
+};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::{Aabb, Rgba, translate2};
+
+fn green() -> Stroke {
+    Stroke::new(Rgba::GREEN, 2.0)
+}
+
+#[test]
+fn shapes_use_the_current_stroke() {
+    let mut list = DrawList::new();
+    list.set_stroke(green());
+    list.set_font_style(FontStyleId::ATARI);
+    list.line(Vec2::ZERO, Vec2::X);
+    list.rect(-Vec2::ONE, Vec2::ONE);
+    list.circle(Vec2::ZERO, 0.5);
+    list.text(Vec2::ZERO, "HI", 12.0);
+    assert_eq!(
+        list.cmds(),
+        [
+            DrawCmd::Line(Line2 {
+                a: Vec2::ZERO,
+                b: Vec2::X,
+                stroke: green(),
+            }),
+            rect_wire(-Vec2::ONE, Vec2::ONE, green()),
+            DrawCmd::Circle {
+                center: Vec2::ZERO,
+                radius: 0.5,
+                stroke: green(),
+            },
+            DrawCmd::Text {
+                pos: Vec2::ZERO,
+                text: "HI".to_string(),
+                size_px: 12.0,
+                color: Rgba::GREEN,
+                style: FontStyleId::ATARI,
+            },
+        ]
+    );
+}
+
+#[test]
+fn regular_polygons_and_stars_are_closed_rings() {
+    let mut list = DrawList::new();
+    list.regular_polygon(Vec2::ONE, 2.0, 4, 0.0);
+    list.star(Vec2::ZERO, 1.0, 0.5, 5, 0.0);
+    list.regular_polygon(Vec2::ZERO, 1.0, 2, 0.0);
+    list.star(Vec2::ZERO, 1.0, 0.5, 1, 0.0);
+    let [
+        DrawCmd::Polyline {
+            pts: square,
+            closed: true,
+            ..
+        },
+        DrawCmd::Polyline {
+            pts: star,
+            closed: true,
+            ..
+        },
+    ] = list.cmds()
+    else {
+        panic!("expected two closed polylines, got {:?}", list.cmds());
+    };
+    let expected = [(3.0, 1.0), (1.0, 3.0), (-1.0, 1.0), (1.0, -1.0)];
+    for (p, (x, y)) in square.iter().zip(expected) {
+        assert!(p.distance(Vec2::new(x, y)) < 1e-5, "{p}");
+    }
+    assert_eq!(star.len(), 10);
+    for (i, p) in star.iter().enumerate() {
+        let radius = if i % 2 == 0 { 1.0 } else { 0.5 };
+        assert!((p.length() - radius).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn scopes_pop_when_dropped() {
+    let mut list = DrawList::new();
+    {
+        let mut moved = list.transform(translate2(Vec2::X));
+        let mut clipped = moved.clip_rect(Aabb::from_min_max(-Vec2::ONE, Vec2::ONE));
+        let mut lit = clipped.blend(BlendMode::Additive);
+        lit.line(Vec2::ZERO, Vec2::Y);
+    }
+    let expected = [
+        DrawCmd::PushTransform(translate2(Vec2::X)),
+        DrawCmd::PushClipRect(Aabb::from_min_max(-Vec2::ONE, Vec2::ONE)),
+        DrawCmd::PushBlend(BlendMode::Additive),
+        DrawCmd::Line(Line2 {
+            a: Vec2::ZERO,
+            b: Vec2::Y,
+            stroke: Stroke::default(),
+        }),
+        DrawCmd::PopBlend,
+        DrawCmd::PopClip,
+        DrawCmd::PopTransform,
+    ];
+    assert_eq!(list.cmds(), expected);
+    assert!(validate(list.cmds()).is_empty());
+}
+
+#[test]
+fn scopes_restore_the_stroke() {
+    let mut layers = LayerSet::new();
+    layers.style_mut(LayerId::HUD).stroke = Some(green());
+    let mut list = DrawList::new();
+    {
+        let mut hud = list.styled_layer(LayerId::HUD, &layers);
+        assert_eq!(hud.stroke(), green());
+        let mut thin = hud.with_stroke(Stroke::new(Rgba::RED, 1.0));
+        thin.line(Vec2::ZERO, Vec2::X);
+        thin.set_stroke(Stroke::new(Rgba::BLUE, 1.0));
+    }
+    assert_eq!(list.stroke(), Stroke::default());
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.cmds()[2], DrawCmd::EndLayer);
+}
+
+#[test]
+fn capacity_is_reused_across_frames() {
+    let mut list = DrawList::with_capacity(64);
+    let mut out = Vec::new();
+    for _ in 0..3 {
+        out.clear();
+        for i in 0..32 {
+            list.line(Vec2::ZERO, Vec2::splat(i as f32));
+        }
+        list.append_to(&mut out);
+        assert!(list.is_empty());
+        assert_eq!(out.len(), 32);
+    }
+    let ptr = list.cmds().as_ptr();
+    list.line(Vec2::ZERO, Vec2::X);
+    list.clear();
+    list.line(Vec2::ZERO, Vec2::X);
+    assert_eq!(list.cmds().as_ptr(), ptr);
+    assert_eq!(list.into_cmds().capacity(), 64);
+}
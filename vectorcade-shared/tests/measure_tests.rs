@@ -0,0 +1,120 @@
+use glam::Vec2;
+use std::cell::Cell;
+use vectorcade_shared::Aabb;
+use vectorcade_shared::font::{
+    FontStyleId, GlyphMetrics, GlyphPath, GlyphPathCmd, TextMeasurer, VectorFont,
+};
+
+/// Glyphs are diagonal strokes from (0.1, -0.2) to (0.6, 0.8) one unit
+/// apart; spaces are blank and half as wide. Counts path requests.
+#[derive(Default)]
+struct SlashFont {
+    calls: Cell<usize>,
+}
+
+impl VectorFont for SlashFont {
+    fn style_id(&self) -> FontStyleId {
+        FontStyleId::ATARI
+    }
+
+    fn has_glyph(&self, _ch: char) -> bool {
+        true
+    }
+
+    fn glyph_paths(&self, ch: char) -> Vec<GlyphPath> {
+        self.calls.set(self.calls.get() + 1);
+        if ch == ' ' {
+            return Vec::new();
+        }
+        vec![GlyphPath {
+            cmds: vec![
+                GlyphPathCmd::MoveTo(Vec2::new(0.1, -0.2)),
+                GlyphPathCmd::LineTo(Vec2::new(0.6, 0.8)),
+                GlyphPathCmd::Close,
+            ],
+        }]
+    }
+
+    fn advance(&self, ch: char) -> f32 {
+        if ch == ' ' { 0.5 } else { 1.0 }
+    }
+}
+
+#[test]
+fn glyph_metrics_come_from_path_points() {
+    let font = SlashFont::default();
+    let slash = GlyphMetrics::of(&font, 'A');
+    assert_eq!(slash.advance, 1.0);
+    assert_eq!(
+        slash.ink,
+        Some(Aabb::from_min_max(
+            Vec2::new(0.1, -0.2),
+            Vec2::new(0.6, 0.8)
+        ))
+    );
+    assert_eq!(GlyphMetrics::of(&font, ' ').ink, None);
+}
+
+#[test]
+fn measures_advance_ink_and_carets() {
+    let font = SlashFont::default();
+    let mut m = TextMeasurer::new(&font);
+    let metrics = m.measure("A B", 10.0);
+    assert_eq!(metrics.advance, 25.0);
+    assert_eq!(metrics.carets, [0.0, 10.0, 15.0, 25.0]);
+    let ink = metrics.ink.unwrap();
+    assert!(ink.min.distance(Vec2::new(1.0, -2.0)) < 1e-5);
+    assert!(ink.max.distance(Vec2::new(21.0, 8.0)) < 1e-5);
+
+    assert_eq!(m.advance_width("A B", 10.0), 25.0);
+    assert_eq!(m.ink_bounds("A B", 10.0), metrics.ink);
+    assert_eq!(m.caret_positions("A B", 10.0), metrics.carets);
+    assert_eq!(m.ink_bounds("  ", 10.0), None);
+    assert_eq!(m.caret_positions("", 10.0), [0.0]);
+}
+
+#[test]
+fn glyphs_are_measured_once() {
+    let font = SlashFont::default();
+    let mut m = TextMeasurer::new(&font);
+    for _ in 0..10 {
+        let _ = m.measure("PLAYER 1 READY", 16.0);
+    }
+    // Distinct characters: P L A Y E R space 1 D.
+    assert_eq!(m.cached(), 9);
+    assert_eq!(font.calls.get(), 9);
+    m.clear();
+    let _ = m.advance_width("AA", 16.0);
+    assert_eq!(font.calls.get(), 10);
+}
+
+/// Blank glyphs two units wide, in the same style as `SlashFont`.
+struct WideFont;
+
+impl VectorFont for WideFont {
+    fn style_id(&self) -> FontStyleId {
+        FontStyleId::ATARI
+    }
+
+    fn has_glyph(&self, _ch: char) -> bool {
+        true
+    }
+
+    fn glyph_paths(&self, _ch: char) -> Vec<GlyphPath> {
+        Vec::new()
+    }
+
+    fn advance(&self, _ch: char) -> f32 {
+        2.0
+    }
+}
+
+#[test]
+fn fonts_sharing_a_style_keep_separate_metrics() {
+    let slash = SlashFont::default();
+    let mut a = TextMeasurer::new(&slash);
+    let mut b = TextMeasurer::new(&WideFont);
+    assert_eq!(a.advance_width("A", 10.0), 10.0);
+    assert_eq!(b.advance_width("A", 10.0), 20.0);
+    assert!(b.ink_bounds("A", 10.0).is_none());
+}
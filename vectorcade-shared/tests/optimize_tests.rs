@@ -0,0 +1,274 @@
+use glam::{Mat3, Vec2};
+use vectorcade_shared::draw::{
+    Dash, DrawCmd, LayerId, Line2, LineCap, LineJoin, OptimizeOptions, Stroke, optimize,
+};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::{Rgba, translate2};
+
+fn line(a: (f32, f32), b: (f32, f32), stroke: Stroke) -> DrawCmd {
+    DrawCmd::Line(Line2 {
+        a: Vec2::new(a.0, a.1),
+        b: Vec2::new(b.0, b.1),
+        stroke,
+    })
+}
+
+fn run(cmds: &[DrawCmd]) -> Vec<DrawCmd> {
+    let mut out = Vec::new();
+    optimize(cmds, &mut out, &OptimizeOptions::default());
+    out
+}
+
+#[test]
+fn chained_lines_merge_into_closed_polyline() {
+    let s = Stroke::new(Rgba::GREEN, 2.0);
+    let cmds = [
+        line((0.0, 0.0), (0.5, 0.0), s),
+        line((0.5, 0.0), (0.5, 0.5), s),
+        line((0.5, 0.5), (0.0, 0.5), s),
+        line((0.0, 0.5), (0.0, 0.0), s),
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(
+        out,
+        [DrawCmd::Polyline {
+            pts: vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(0.5, 0.0),
+                Vec2::new(0.5, 0.5),
+                Vec2::new(0.0, 0.5),
+            ],
+            closed: true,
+            stroke: s,
+        }]
+    );
+    assert_eq!(stats.lines_merged, 4);
+    assert_eq!(stats.saved(), 3);
+}
+
+#[test]
+fn different_strokes_and_gaps_break_chains() {
+    let a = Stroke::new(Rgba::GREEN, 2.0);
+    let b = Stroke::new(Rgba::RED, 2.0);
+    let cmds = [
+        line((0.0, 0.0), (0.1, 0.0), a),
+        line((0.1, 0.0), (0.2, 0.1), b),
+        line((0.3, 0.0), (0.4, 0.0), b),
+    ];
+    assert_eq!(run(&cmds), cmds);
+}
+
+#[test]
+fn dashed_lines_are_not_merged() {
+    // Each Line restarts its dash pattern; a merged polyline would not.
+    let s = Stroke::new(Rgba::GREEN, 2.0).dashed(Dash::new(3.0, 2.0, 0.0));
+    let cmds = [
+        line((0.0, 0.0), (0.5, 0.0), s),
+        line((0.5, 0.0), (0.5, 0.5), s),
+    ];
+    assert_eq!(run(&cmds), cmds);
+}
+
+#[test]
+fn mitered_and_butt_capped_lines_are_not_merged() {
+    // Merging would add join geometry and drop caps at the corner.
+    let round = Stroke::new(Rgba::GREEN, 2.0);
+    for s in [
+        round.join(LineJoin::Miter),
+        round.join(LineJoin::Bevel),
+        round.cap(LineCap::Butt),
+        round.cap(LineCap::Square),
+    ] {
+        let cmds = [
+            line((0.0, 0.0), (0.5, 0.0), s),
+            line((0.5, 0.0), (0.5, 0.5), s),
+        ];
+        assert_eq!(run(&cmds), cmds);
+    }
+}
+
+#[test]
+fn translucent_lines_are_not_merged() {
+    // Separate lines blend twice where they overlap; a polyline once.
+    let s = Stroke::new(Rgba::GREEN.with_a(0.5), 2.0);
+    let cmds = [
+        line((0.0, 0.0), (0.5, 0.0), s),
+        line((0.5, 0.0), (0.5, 0.5), s),
+    ];
+    assert_eq!(run(&cmds), cmds);
+}
+
+#[test]
+fn collinear_chain_collapses_to_single_line() {
+    let s = Stroke::default();
+    let cmds = [
+        line((-0.5, 0.0), (0.0, 0.0), s),
+        line((0.0, 0.0), (0.5, 0.0), s),
+    ];
+    assert_eq!(run(&cmds), [line((-0.5, 0.0), (0.5, 0.0), s)]);
+}
+
+#[test]
+fn identity_transforms_and_empty_blocks_are_dropped() {
+    let s = Stroke::default();
+    let cmds = [
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        line((0.0, 0.0), (0.1, 0.1), s),
+        DrawCmd::PopTransform,
+        DrawCmd::BeginLayer(LayerId::new("empty", 0)),
+        DrawCmd::PushTransform(translate2(Vec2::new(0.5, 0.0))),
+        DrawCmd::PopTransform,
+        DrawCmd::EndLayer,
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(out, [line((0.0, 0.0), (0.1, 0.1), s)]);
+    assert_eq!(stats.transforms_removed, 4);
+    assert_eq!(stats.layers_removed, 2);
+}
+
+#[test]
+fn degenerate_geometry_is_removed() {
+    let s = Stroke::default().cap(LineCap::Butt);
+    let cmds = [
+        line((0.2, 0.2), (0.2, 0.2), s),
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ZERO, Vec2::ZERO, Vec2::X * 0.5, Vec2::ZERO],
+            closed: true,
+            stroke: s,
+        },
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ONE * 0.1; 3],
+            closed: false,
+            stroke: s,
+        },
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(
+        out,
+        [DrawCmd::Polyline {
+            pts: vec![Vec2::ZERO, Vec2::X * 0.5],
+            closed: true,
+            stroke: s,
+        }]
+    );
+    assert_eq!(stats.degenerate_removed, 6);
+}
+
+#[test]
+fn round_and_square_capped_dots_are_kept() {
+    let round = Stroke::default();
+    let square = round.cap(LineCap::Square);
+    let cmds = [
+        line((0.2, 0.2), (0.2, 0.2), round),
+        line((0.4, 0.2), (0.4, 0.2), square),
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ONE * 0.1; 3],
+            closed: true,
+            stroke: round,
+        },
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(out[..2], cmds[..2]);
+    assert_eq!(
+        out[2],
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ONE * 0.1; 2],
+            closed: false,
+            stroke: round,
+        }
+    );
+    assert_eq!(stats.degenerate_removed, 1);
+
+    // A dot next to a chain is not swallowed by it.
+    let chain = [
+        line((0.0, 0.0), (0.5, 0.0), round),
+        line((0.5, 0.0), (0.5, 0.0), round),
+        line((0.5, 0.0), (0.5, 0.5), round),
+    ];
+    assert_eq!(run(&chain), chain);
+}
+
+#[test]
+fn offscreen_geometry_is_culled_through_transforms() {
+    let s = Stroke::default();
+    let cmds = [
+        line((1.5, 0.0), (2.0, 0.5), s),
+        DrawCmd::PushTransform(translate2(Vec2::new(3.0, 0.0))),
+        line((-2.5, 0.0), (-2.5, 0.5), s),
+        line((0.0, 0.0), (0.0, 0.5), s),
+        DrawCmd::PopTransform,
+    ];
+    let mut out = Vec::new();
+    let stats = optimize(&cmds, &mut out, &OptimizeOptions::default());
+    assert_eq!(
+        out,
+        [
+            DrawCmd::PushTransform(translate2(Vec2::new(3.0, 0.0))),
+            line((-2.5, 0.0), (-2.5, 0.5), s),
+            DrawCmd::PopTransform,
+        ]
+    );
+    assert_eq!(stats.culled, 2);
+
+    let keep_all = OptimizeOptions {
+        cull: false,
+        ..OptimizeOptions::default()
+    };
+    optimize(&cmds, &mut out, &keep_all);
+    assert_eq!(out.len(), cmds.len());
+}
+
+#[test]
+fn wide_strokes_reaching_the_viewport_are_kept() {
+    // On the default 800x600 screen, 20 px is 0.05 NDC across and
+    // 0.0667 NDC tall.
+    let wide = Stroke::new(Rgba::GREEN, 20.0);
+    let thin = Stroke::new(Rgba::GREEN, 2.0);
+    let edge = |s| line((1.02, -0.5), (1.02, 0.5), s);
+    assert_eq!(run(&[edge(wide)]), [edge(wide)]);
+    assert!(run(&[edge(thin)]).is_empty());
+
+    // Square caps reach further along the diagonal, miters further
+    // still.
+    let beyond = |s| line((1.03, -0.5), (1.03, 0.5), s);
+    assert!(run(&[beyond(wide)]).is_empty());
+    let squared = wide.cap(LineCap::Square);
+    assert_eq!(run(&[beyond(squared)]), [beyond(squared)]);
+    let far = |s| line((1.08, -0.5), (1.08, 0.5), s);
+    assert!(run(&[far(squared)]).is_empty());
+    let mitered = wide.join(LineJoin::Miter);
+    assert_eq!(run(&[far(mitered)]), [far(mitered)]);
+
+    let small = OptimizeOptions {
+        screen: ScreenInfo {
+            width_px: 200,
+            height_px: 100,
+            dpi_scale: 1.0,
+        },
+        ..OptimizeOptions::default()
+    };
+    let mut out = Vec::new();
+    optimize(&[far(wide)], &mut out, &small);
+    assert_eq!(out, [far(wide)]);
+}
+
+#[test]
+fn text_clear_and_unbalanced_pops_pass_through() {
+    let cmds = [
+        DrawCmd::Clear { color: Rgba::BLACK },
+        DrawCmd::PopTransform,
+        DrawCmd::Text {
+            pos: Vec2::new(5.0, 5.0),
+            text: "FAR AWAY".into(),
+            size_px: 16.0,
+            color: Rgba::WHITE,
+            style: FontStyleId::DEFAULT,
+        },
+    ];
+    assert_eq!(run(&cmds), cmds);
+}
@@ -0,0 +1,225 @@
+use vectorcade_shared::Xorshift64;
+use vectorcade_shared::draw::DrawCmd;
+use vectorcade_shared::game::{Game, GameCtx, GameMeta, GameRunner, RunnerConfig, SilentAudio};
+use vectorcade_shared::input::{Axis, Button, InputState, Key, NullInput, Pointer};
+use vectorcade_shared::replay::format::{FORMAT_VERSION, MAGIC};
+use vectorcade_shared::replay::{
+    InputFrame, InputRecorder, Replay, ReplayError, ReplayFile, ReplayInput,
+};
+
+/// Scripted input: holds Space on even ticks and moves right.
+struct Script {
+    tick: u32,
+}
+
+impl InputState for Script {
+    fn key(&self, k: Key) -> Button {
+        let held = k == Key::Space && self.tick.is_multiple_of(2);
+        Button {
+            is_down: held,
+            went_down: held,
+            went_up: k == Key::Space && !held,
+        }
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        if a == Axis::MoveX {
+            self.tick as f32 * 0.1
+        } else {
+            0.0
+        }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        Some(Pointer {
+            x_px: self.tick as f32,
+            y_px: 2.0,
+            is_down: false,
+        })
+    }
+}
+
+/// Accumulates a state hash from input and RNG.
+#[derive(Default)]
+struct Hasher {
+    state: u64,
+}
+
+impl Game for Hasher {
+    fn metadata(&self) -> GameMeta {
+        GameMeta {
+            name: "hasher",
+            preferred_aspect: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &mut GameCtx, _dt: f32) {
+        let mut h = self.state.rotate_left(7) ^ ctx.rng.next_u64();
+        if ctx.input.key(Key::Space).went_down {
+            h = h.wrapping_mul(31);
+        }
+        h ^= ctx.input.axis(Axis::MoveX).to_bits() as u64;
+        if let Some(p) = ctx.input.pointer() {
+            h ^= p.x_px.to_bits() as u64;
+        }
+        self.state = h;
+    }
+
+    fn render(&mut self, _ctx: &mut GameCtx, _out: &mut Vec<DrawCmd>) {}
+}
+
+fn new_runner(seed: u64) -> GameRunner<Hasher> {
+    GameRunner::new(
+        Hasher::default(),
+        Xorshift64::new(seed),
+        RunnerConfig::default(),
+    )
+}
+
+#[test]
+fn capture_round_trips_input() {
+    let script = Script { tick: 4 };
+    let frame = InputFrame::capture(&script);
+    for k in Key::ALL {
+        assert_eq!(frame.key(k), script.key(k));
+    }
+    for a in Axis::ALL {
+        assert_eq!(frame.axis(a), script.axis(a));
+    }
+    assert_eq!(frame.pointer(), script.pointer());
+}
+
+#[test]
+fn replay_reproduces_session() {
+    let seed = 99;
+    let mut live = new_runner(seed);
+    let mut recorder = InputRecorder::new(seed);
+    for tick in 0..120 {
+        let input = Script { tick };
+        recorder.record(&input);
+        live.step(&input, &SilentAudio);
+    }
+    let replay = recorder.finish();
+    assert_eq!(replay.len(), 120);
+
+    let mut played = GameRunner::new(Hasher::default(), replay.rng(), RunnerConfig::default());
+    let mut input = ReplayInput::new(&replay);
+    while !input.is_finished() {
+        played.step(&input, &SilentAudio);
+        input.advance();
+    }
+    assert_eq!(played.game().state, live.game().state);
+}
+
+#[test]
+fn different_seed_diverges() {
+    let mut a = new_runner(1);
+    let mut b = new_runner(2);
+    for tick in 0..10 {
+        a.step(&Script { tick }, &SilentAudio);
+        b.step(&Script { tick }, &SilentAudio);
+    }
+    assert_ne!(a.game().state, b.game().state);
+}
+
+#[test]
+fn playback_past_end_is_neutral() {
+    let mut recorder = InputRecorder::new(1);
+    recorder.record(&Script { tick: 0 });
+    let replay = recorder.finish();
+    let mut input = ReplayInput::new(&replay);
+    assert!(input.key(Key::Space).is_down);
+    input.advance();
+    assert!(input.is_finished());
+    for k in Key::ALL {
+        assert_eq!(input.key(k), NullInput.key(k));
+    }
+    assert_eq!(input.pointer(), None);
+}
+
+fn recorded(ticks: u32) -> Replay {
+    let mut recorder = InputRecorder::new(0xABCD);
+    for tick in 0..ticks {
+        recorder.record(&Script { tick: tick / 3 });
+    }
+    recorder.finish()
+}
+
+#[test]
+fn file_round_trips() {
+    let meta = Hasher::default().metadata();
+    let file = ReplayFile::new(&meta, 1.0 / 60.0, recorded(50));
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(&bytes[..4], &MAGIC);
+    assert_eq!(ReplayFile::from_bytes(&bytes), Ok(file));
+}
+
+#[test]
+fn idle_ticks_cost_one_byte() {
+    let mut recorder = InputRecorder::new(1);
+    for _ in 0..100 {
+        recorder.record(&NullInput);
+    }
+    let file = ReplayFile {
+        game: String::new(),
+        dt: 0.5,
+        replay: recorder.finish(),
+    };
+    assert_eq!(file.to_bytes().unwrap().len(), 24 + 100);
+}
+
+#[test]
+fn rejects_wrong_version() {
+    let file = ReplayFile {
+        game: "x".to_string(),
+        dt: 0.5,
+        replay: recorded(3),
+    };
+    let mut bytes = file.to_bytes().unwrap();
+    bytes[4] = FORMAT_VERSION as u8 + 1;
+    assert_eq!(
+        ReplayFile::from_bytes(&bytes),
+        Err(ReplayError::UnsupportedVersion {
+            found: FORMAT_VERSION + 1
+        })
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let file = ReplayFile {
+        game: "x".to_string(),
+        dt: 0.5,
+        replay: recorded(10),
+    };
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(ReplayFile::from_bytes(b"NOPE"), Err(ReplayError::BadMagic));
+    assert_eq!(
+        ReplayFile::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(ReplayError::Truncated)
+    );
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert_eq!(
+        ReplayFile::from_bytes(&extra),
+        Err(ReplayError::TrailingData)
+    );
+}
+
+#[test]
+fn refuses_to_encode_an_over_long_name() {
+    let len = usize::from(u16::MAX) + 1;
+    let file = ReplayFile {
+        game: "x".repeat(len),
+        dt: 0.5,
+        replay: recorded(1),
+    };
+    assert_eq!(file.to_bytes(), Err(ReplayError::NameTooLong { len }));
+
+    let file = ReplayFile {
+        game: "x".repeat(len - 1),
+        ..file
+    };
+    let bytes = file.to_bytes().unwrap();
+    assert_eq!(ReplayFile::from_bytes(&bytes), Ok(file));
+}
@@ -0,0 +1,195 @@
+use glam::Vec2;
+use vectorcade_shared::Rgba;
+use vectorcade_shared::Xorshift64;
+use vectorcade_shared::draw::{DrawCmd, Line2, Stroke};
+use vectorcade_shared::game::{Game, GameCtx, GameMeta, GameRunner, RunnerConfig, SilentAudio};
+use vectorcade_shared::input::{Axis, Button, InputState, Key, NullInput, Pointer};
+
+/// Counts updates and draws one line per update seen.
+#[derive(Default)]
+struct Counter {
+    updates: u32,
+    resets: u32,
+    last_now: f64,
+    unbalanced: bool,
+    presses: u32,
+    held: u32,
+}
+
+impl Game for Counter {
+    fn metadata(&self) -> GameMeta {
+        GameMeta {
+            name: "counter",
+            preferred_aspect: None,
+        }
+    }
+
+    fn reset(&mut self, _ctx: &mut GameCtx) {
+        self.resets += 1;
+        self.updates = 0;
+    }
+
+    fn update(&mut self, ctx: &mut GameCtx, _dt: f32) {
+        self.updates += 1;
+        self.last_now = ctx.now_s;
+        let space = ctx.input.key(Key::Space);
+        self.presses += u32::from(space.went_down);
+        self.held += u32::from(space.is_down);
+    }
+
+    fn render(&mut self, _ctx: &mut GameCtx, out: &mut Vec<DrawCmd>) {
+        out.push(DrawCmd::Clear { color: Rgba::BLACK });
+        for _ in 0..self.updates {
+            out.push(DrawCmd::Line(Line2 {
+                a: Vec2::ZERO,
+                b: Vec2::X,
+                stroke: Stroke::default(),
+            }));
+        }
+        if self.unbalanced {
+            out.push(DrawCmd::PopTransform);
+        }
+    }
+}
+
+/// Space pressed this frame.
+struct PressSpace;
+
+impl InputState for PressSpace {
+    fn key(&self, k: Key) -> Button {
+        if k == Key::Space {
+            Button {
+                is_down: true,
+                went_down: true,
+                went_up: false,
+            }
+        } else {
+            Button::UP
+        }
+    }
+
+    fn axis(&self, _a: Axis) -> f32 {
+        0.0
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        None
+    }
+}
+
+fn runner(max_steps: u32) -> GameRunner<Counter> {
+    let config = RunnerConfig::new(0.25, max_steps);
+    GameRunner::new(Counter::default(), Xorshift64::new(1), config)
+}
+
+#[test]
+fn runs_whole_steps_and_reports_alpha() {
+    let mut r = runner(10);
+    let frame = r.frame(0.6, &NullInput, &SilentAudio);
+    assert_eq!(frame.steps, 2);
+    assert!((frame.alpha - 0.4).abs() < 1e-4);
+    assert_eq!(frame.cmds.len(), 3);
+
+    let frame = r.frame(0.2, &NullInput, &SilentAudio);
+    assert_eq!(frame.steps, 1);
+    assert!((frame.alpha - 0.2).abs() < 1e-4);
+    assert_eq!(r.tick(), 3);
+    assert!((r.now_s() - 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn invalid_timesteps_fall_back_to_the_default() {
+    let default_dt = RunnerConfig::DEFAULT_FIXED_DT;
+    for dt in [0.0, -0.25, f32::NAN, f32::INFINITY] {
+        assert_eq!(RunnerConfig::new(dt, 5).fixed_dt, default_dt);
+    }
+
+    // Literal and deserialized configs bypass `new`; the runner fixes them.
+    let config = RunnerConfig {
+        fixed_dt: f32::NAN,
+        max_steps: 5,
+    };
+    let mut r = GameRunner::new(Counter::default(), Xorshift64::new(1), config);
+    assert_eq!(r.config().fixed_dt, default_dt);
+    for _ in 0..3 {
+        let frame = r.frame(0.06, &NullInput, &SilentAudio);
+        assert!(frame.alpha.is_finite() && frame.dropped_s.is_finite());
+    }
+    assert_eq!(r.tick(), 10);
+}
+
+#[test]
+fn zero_max_steps_still_advances() {
+    assert_eq!(RunnerConfig::new(0.25, 0).max_steps, 1);
+
+    let config = RunnerConfig {
+        fixed_dt: 0.25,
+        max_steps: 0,
+    };
+    let mut r = GameRunner::new(Counter::default(), Xorshift64::new(1), config);
+    let frame = r.frame(0.6, &NullInput, &SilentAudio);
+    assert_eq!(frame.steps, 1);
+    assert!((frame.dropped_s - 0.25).abs() < 1e-9);
+    assert_eq!(r.tick(), 1);
+}
+
+#[test]
+fn clamps_catch_up_steps() {
+    let mut r = runner(3);
+    let frame = r.frame(10.1, &NullInput, &SilentAudio);
+    assert_eq!(frame.steps, 3);
+    assert!((frame.dropped_s - 9.25).abs() < 1e-9);
+    assert!((frame.alpha - 0.4).abs() < 1e-4);
+    assert_eq!(r.game().updates, 3);
+}
+
+#[test]
+fn only_the_first_catch_up_step_sees_key_edges() {
+    let mut r = runner(10);
+    assert_eq!(r.frame(0.75, &PressSpace, &SilentAudio).steps, 3);
+    assert_eq!(r.game().presses, 1);
+    assert_eq!(r.game().held, 3);
+}
+
+#[test]
+fn ignores_invalid_elapsed_time() {
+    let mut r = runner(3);
+    assert_eq!(r.frame(-1.0, &NullInput, &SilentAudio).steps, 0);
+    assert_eq!(r.frame(f64::NAN, &NullInput, &SilentAudio).steps, 0);
+    assert_eq!(r.tick(), 0);
+}
+
+#[test]
+fn update_sees_time_before_step() {
+    let mut r = runner(10);
+    r.frame(1.0, &NullInput, &SilentAudio);
+    assert!((r.game().last_now - 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn reset_restarts_clock_and_game() {
+    let mut r = runner(10);
+    r.frame(1.0, &NullInput, &SilentAudio);
+    r.reset(&NullInput, &SilentAudio);
+    assert_eq!(r.tick(), 0);
+    assert_eq!(r.now_s(), 0.0);
+    assert_eq!(r.game().resets, 1);
+    assert_eq!(r.render(&NullInput, &SilentAudio).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "command 1: error: PopTransform")]
+fn validation_rejects_invalid_display_lists() {
+    let mut r = runner(10);
+    r.set_validate(true);
+    r.game_mut().unbalanced = true;
+    r.render(&NullInput, &SilentAudio);
+}
+
+#[test]
+fn validation_can_be_disabled() {
+    let mut r = runner(10);
+    r.set_validate(false);
+    r.game_mut().unbalanced = true;
+    assert_eq!(r.render(&NullInput, &SilentAudio).len(), 2);
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "serde")]
+
+use glam::{Vec2, Vec3};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use vectorcade_shared::draw::{DrawCmd, LayerId, Line2, Stroke, rect_wire};
+use vectorcade_shared::font::{FontStyleId, GlyphPath, GlyphPathCmd};
+use vectorcade_shared::game::ScreenInfo;
+use vectorcade_shared::input::{Button, Key, Pointer};
+use vectorcade_shared::projectile::{Projectile2D, Projectile3D};
+use vectorcade_shared::replay::{InputRecorder, Replay};
+use vectorcade_shared::{Rgba, rot2};
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    let back: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(&back, value);
+    back
+}
+
+#[test]
+fn display_list_round_trips() {
+    let cmds = vec![
+        DrawCmd::Clear { color: Rgba::BLACK },
+        DrawCmd::BeginLayer(LayerId::HUD),
+        DrawCmd::PushTransform(rot2(0.5)),
+        rect_wire(Vec2::splat(-0.5), Vec2::splat(0.5), Stroke::default()),
+        DrawCmd::Line(Line2 {
+            a: Vec2::ZERO,
+            b: Vec2::ONE,
+            stroke: Stroke::with_glow(Rgba::CYAN, 2.0, 0.7),
+        }),
+        DrawCmd::PopTransform,
+        DrawCmd::Text {
+            pos: Vec2::new(-0.9, 0.9),
+            text: "1UP".to_string(),
+            size_px: 12.0,
+            color: Rgba::WHITE,
+            style: FontStyleId::MIDWAY,
+        },
+        DrawCmd::EndLayer,
+    ];
+    round_trip(&cmds);
+}
+
+#[test]
+fn layer_names_are_interned() {
+    let json = r#"[{"BeginLayer":{"name":"debug","z":1}},{"BeginLayer":{"name":"debug","z":2}}]"#;
+    let cmds: Vec<DrawCmd> = serde_json::from_str(json).unwrap();
+    let [DrawCmd::BeginLayer(a), DrawCmd::BeginLayer(b)] = cmds.as_slice() else {
+        panic!("expected two layers");
+    };
+    assert_eq!(*a, LayerId::new("debug", 1));
+    assert!(std::ptr::eq(a.name, b.name));
+}
+
+#[test]
+fn glyph_paths_round_trip() {
+    round_trip(&GlyphPath {
+        cmds: vec![
+            GlyphPathCmd::MoveTo(Vec2::ZERO),
+            GlyphPathCmd::LineTo(Vec2::ONE),
+            GlyphPathCmd::Close,
+        ],
+    });
+    round_trip(&FontStyleId::ATARI);
+}
+
+#[test]
+fn input_types_round_trip() {
+    round_trip(&Key::Space);
+    round_trip(&Button {
+        is_down: true,
+        went_down: true,
+        went_up: false,
+    });
+    round_trip(&Pointer {
+        x_px: 10.0,
+        y_px: 20.0,
+        is_down: true,
+    });
+}
+
+#[test]
+fn game_state_round_trips() {
+    let screen = ScreenInfo::default();
+    let json = serde_json::to_string(&screen).unwrap();
+    let back: ScreenInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.width_px, screen.width_px);
+
+    round_trip(&Projectile2D::new(Vec2::ZERO, Vec2::X, 2.0));
+    round_trip(&Projectile3D::new(Vec3::ZERO, Vec3::Z, 5.0, 100.0));
+
+    let mut rec = InputRecorder::new(9);
+    rec.record(&vectorcade_shared::input::NullInput);
+    let replay: Replay = round_trip(&rec.finish());
+    assert_eq!(replay.seed, 9);
+}
@@ -0,0 +1,144 @@
+use glam::Vec2;
+use std::path::PathBuf;
+use vectorcade_shared::draw::{DrawCmd, LayerId, Line2, Stroke, rect_wire};
+use vectorcade_shared::font::FontStyleId;
+use vectorcade_shared::game::{Game, GameCtx, GameMeta};
+use vectorcade_shared::input::{Axis, Button, InputState, Key, Pointer};
+use vectorcade_shared::replay::{InputRecorder, Replay};
+use vectorcade_shared::snapshot::{GoldenError, capture, check_golden, compare_golden, to_text};
+use vectorcade_shared::{Rgba, translate2};
+
+/// Moves a paddle with `MoveX` and jitters a star with the RNG.
+#[derive(Default)]
+struct Paddle {
+    x: f32,
+    star: Vec2,
+}
+
+impl Game for Paddle {
+    fn metadata(&self) -> GameMeta {
+        GameMeta {
+            name: "paddle",
+            preferred_aspect: None,
+        }
+    }
+
+    fn reset(&mut self, ctx: &mut GameCtx) {
+        self.x = 0.0;
+        self.star = Vec2::new(ctx.rng.range_f32(-1.0, 1.0), 0.5);
+    }
+
+    fn update(&mut self, ctx: &mut GameCtx, dt: f32) {
+        self.x += ctx.input.axis(Axis::MoveX) * dt;
+        self.star.y -= ctx.rng.next_f32() * dt;
+    }
+
+    fn render(&mut self, _ctx: &mut GameCtx, out: &mut Vec<DrawCmd>) {
+        out.push(DrawCmd::Clear { color: Rgba::BLACK });
+        out.push(DrawCmd::BeginLayer(LayerId::PLAYFIELD));
+        out.push(DrawCmd::PushTransform(translate2(Vec2::new(self.x, -0.9))));
+        out.push(rect_wire(
+            Vec2::new(-0.1, -0.02),
+            Vec2::new(0.1, 0.02),
+            Stroke::with_glow(Rgba::GREEN, 2.0, 0.5),
+        ));
+        out.push(DrawCmd::PopTransform);
+        out.push(DrawCmd::Line(Line2 {
+            a: self.star,
+            b: self.star + Vec2::new(0.0, 0.01),
+            stroke: Stroke::default(),
+        }));
+        out.push(DrawCmd::EndLayer);
+        out.push(DrawCmd::Text {
+            pos: Vec2::new(-0.9, 0.9),
+            text: "SCORE \"0\"".to_string(),
+            size_px: 16.0,
+            color: Rgba::WHITE,
+            style: FontStyleId::ATARI,
+        });
+    }
+}
+
+struct Hold(f32);
+
+impl InputState for Hold {
+    fn key(&self, _k: Key) -> Button {
+        Button::UP
+    }
+
+    fn axis(&self, a: Axis) -> f32 {
+        if a == Axis::MoveX { self.0 } else { 0.0 }
+    }
+
+    fn pointer(&self) -> Option<Pointer> {
+        None
+    }
+}
+
+fn script() -> Replay {
+    let mut rec = InputRecorder::new(7);
+    for _ in 0..30 {
+        rec.record(&Hold(1.0));
+    }
+    rec.finish()
+}
+
+#[test]
+fn capture_is_deterministic() {
+    let a = to_text(&capture(Paddle::default(), &script(), 60));
+    let b = to_text(&capture(Paddle::default(), &script(), 60));
+    assert_eq!(a, b);
+    assert_eq!(a.lines().count(), 8);
+}
+
+#[test]
+fn text_format_is_stable() {
+    let cmds = capture(Paddle::default(), &script(), 60);
+    check_golden("tests/golden/paddle_60.txt", &to_text(&cmds)).unwrap();
+}
+
+/// Write `contents` to a fresh file in the system temp directory, so
+/// failing comparisons never touch the real golden files.
+fn temp_golden(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "vectorcade-golden-{}-{name}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn mismatch_reports_first_line() {
+    let golden = temp_golden("mismatch", "clear rgba(0 0 0 1)\nline\n");
+    let cmds = vec![DrawCmd::Clear { color: Rgba::BLACK }, DrawCmd::PopTransform];
+    let err = compare_golden(&golden, &to_text(&cmds)).unwrap_err();
+    std::fs::remove_file(&golden).unwrap();
+    let GoldenError::Mismatch(m) = err else {
+        panic!("expected a mismatch, got {err}");
+    };
+    assert_eq!(m.line, 2);
+    assert_eq!(m.expected.as_deref(), Some("line"));
+    assert_eq!(m.actual.as_deref(), Some("pop"));
+}
+
+#[test]
+fn missing_golden_is_reported_distinctly() {
+    let path = std::env::temp_dir().join("vectorcade-golden-does-not-exist.txt");
+    let err = compare_golden(&path, "").unwrap_err();
+    assert!(matches!(err, GoldenError::Missing { .. }));
+    assert!(err.to_string().contains("VECTORCADE_UPDATE_SNAPSHOTS=1"));
+}
+
+#[test]
+fn floats_are_rounded() {
+    let cmds = vec![DrawCmd::Line(Line2 {
+        a: Vec2::new(-0.000_01, 0.123_456),
+        b: Vec2::new(1.0, -2.5),
+        stroke: Stroke::new(Rgba::RED, 1.5),
+    })];
+    assert_eq!(
+        to_text(&cmds),
+        "line (0 0.1235) (1 -2.5) w 1.5 glow 0 rgba(1 0 0 1)\n"
+    );
+}
@@ -0,0 +1,194 @@
+use glam::Vec2;
+use std::f32::consts::PI;
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{
+    Dash, DiagnosticKind, DrawCmd, Line2, LineCap, LineJoin, MAX_DASHES, Severity, Stroke,
+    StrokeMesh, dash_path, expand_stroke, validate,
+};
+use vectorcade_shared::snapshot::to_text;
+
+fn area(mesh: &StrokeMesh) -> f32 {
+    mesh.triangles()
+        .map(|[a, b, c]| (b - a).perp_dot(c - a).abs() * 0.5)
+        .sum()
+}
+
+fn expand(pts: &[Vec2], closed: bool, stroke: Stroke) -> StrokeMesh {
+    let mut mesh = StrokeMesh::new();
+    expand_stroke(pts, closed, &stroke, &mut mesh);
+    mesh
+}
+
+fn lengths(parts: &[Vec<Vec2>]) -> Vec<f32> {
+    parts
+        .iter()
+        .map(|p| p.windows(2).map(|w| w[0].distance(w[1])).sum())
+        .collect()
+}
+
+#[test]
+fn dashes_follow_pattern_and_phase() {
+    let line = [Vec2::ZERO, Vec2::new(10.0, 0.0)];
+    let parts = dash_path(&line, false, &Dash::new(3.0, 1.0, 0.0));
+    assert_eq!(lengths(&parts), [3.0, 3.0, 2.0]);
+    assert_eq!(parts[1][0], Vec2::new(4.0, 0.0));
+
+    let parts = dash_path(&line, false, &Dash::new(3.0, 1.0, 2.0));
+    assert_eq!(lengths(&parts), [1.0, 3.0, 3.0]);
+
+    // A phase inside the gap starts with nothing drawn.
+    let parts = dash_path(&line, false, &Dash::new(3.0, 1.0, -1.0));
+    assert_eq!(parts[0][0], Vec2::new(1.0, 0.0));
+}
+
+#[test]
+fn dashes_continue_around_corners_and_closed_paths() {
+    let square = [
+        Vec2::ZERO,
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(0.0, 4.0),
+    ];
+    let parts = dash_path(&square[..3], false, &Dash::new(6.0, 1.0, 0.0));
+    assert_eq!(
+        parts[0],
+        [Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(4.0, 2.0)]
+    );
+
+    // 16 units of perimeter: dashes at 0..5, 6..11, 12..16 + 0..5 wrap.
+    let parts = dash_path(&square, true, &Dash::new(5.0, 1.0, 0.0));
+    assert_eq!(lengths(&parts), [5.0, 9.0]);
+    assert_eq!(*parts[1].last().unwrap(), Vec2::new(4.0, 1.0));
+}
+
+#[test]
+fn degenerate_patterns() {
+    let line = [Vec2::ZERO, Vec2::X];
+    assert_eq!(dash_path(&line, false, &Dash::new(1.0, 0.0, 0.5)), [line]);
+    assert!(dash_path(&line, false, &Dash::new(0.0, 1.0, 0.0)).is_empty());
+    assert!(dash_path(&line, false, &Dash::new(1.0, f32::NAN, 0.0)).is_empty());
+    assert!(dash_path(&[], false, &Dash::new(2.0, 2.0, 0.0)).is_empty());
+    assert!(dash_path(&[], true, &Dash::new(2.0, 2.0, 1.0)).is_empty());
+}
+
+#[test]
+fn empty_dashed_path_draws_nothing() {
+    let stroke = Stroke::new(Rgba::WHITE, 2.0).dashed(Dash::new(2.0, 2.0, 0.0));
+    assert!(expand(&[], false, stroke).indices.is_empty());
+}
+
+#[test]
+fn patterns_too_fine_for_the_path_draw_solid() {
+    let line = [Vec2::ZERO, Vec2::new(100_000.0, 0.0)];
+    // Sub-pixel dashes, and dashes that would need more than
+    // MAX_DASHES pieces, both return the whole path.
+    assert_eq!(dash_path(&line, false, &Dash::new(1e-3, 1e-3, 0.0)), [line]);
+    assert_eq!(dash_path(&line, false, &Dash::new(1.0, 1.0, 0.0)), [line]);
+    let short = [Vec2::ZERO, Vec2::new(MAX_DASHES as f32, 0.0)];
+    assert_eq!(
+        dash_path(&short, false, &Dash::new(1.0, 1.0, 0.0)).len(),
+        MAX_DASHES / 2
+    );
+    // A long gap does not rescue a dash finer than f32 steps can take.
+    let parts = dash_path(&line, false, &Dash::new(0.02, 1000.0, 0.0));
+    assert_eq!(parts, [line]);
+}
+
+#[test]
+fn caps_extend_the_ends() {
+    let line = [Vec2::ZERO, Vec2::new(10.0, 0.0)];
+    let base = Stroke::new(Rgba::WHITE, 2.0);
+    let butt = area(&expand(&line, false, base.cap(LineCap::Butt)));
+    let square = area(&expand(&line, false, base.cap(LineCap::Square)));
+    let round = area(&expand(&line, false, base));
+    assert!((butt - 20.0).abs() < 1e-4);
+    assert!((square - 24.0).abs() < 1e-4);
+    assert!(round > 20.0 + PI * 0.85 && round < 20.0 + PI);
+}
+
+#[test]
+fn joins_fill_the_outer_corner() {
+    let corner = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+    let base = Stroke::new(Rgba::WHITE, 2.0).cap(LineCap::Butt);
+    let segments = 40.0;
+    let bevel = area(&expand(&corner, false, base.join(LineJoin::Bevel))) - segments;
+    let round = area(&expand(&corner, false, base)) - segments;
+    let miter = area(&expand(&corner, false, base.join(LineJoin::Miter))) - segments;
+    assert!((bevel - 0.5).abs() < 1e-4);
+    assert!((miter - 1.0).abs() < 1e-4);
+    assert!(round > bevel && round < PI / 4.0 + 1e-4);
+}
+
+#[test]
+fn sharp_miters_fall_back_to_bevel() {
+    let spike = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(0.0, 1.0)];
+    let base = Stroke::new(Rgba::WHITE, 2.0).cap(LineCap::Butt);
+    let miter = expand(&spike, false, base.join(LineJoin::Miter));
+    let bevel = expand(&spike, false, base.join(LineJoin::Bevel));
+    assert_eq!(miter, bevel);
+}
+
+#[test]
+fn closed_paths_join_instead_of_capping() {
+    let tri = [Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(0.0, 10.0)];
+    let stroke = Stroke::new(Rgba::WHITE, 2.0).join(LineJoin::Bevel);
+    let closed = expand(&tri, true, stroke.cap(LineCap::Butt));
+    let closed_square = expand(&tri, true, stroke.cap(LineCap::Square));
+    assert_eq!(closed, closed_square);
+    assert_eq!(closed.indices.len() % 3, 0);
+}
+
+#[test]
+fn dots_and_empty_strokes() {
+    let p = [Vec2::new(5.0, 5.0)];
+    let stroke = Stroke::new(Rgba::WHITE, 4.0);
+    assert!((area(&expand(&p, false, stroke.cap(LineCap::Square))) - 16.0).abs() < 1e-4);
+    assert!(area(&expand(&p, false, stroke)) > 4.0 * PI * 0.85);
+    assert!(
+        expand(&p, false, stroke.cap(LineCap::Butt))
+            .indices
+            .is_empty()
+    );
+    let line = [Vec2::ZERO, Vec2::X];
+    assert!(
+        expand(&line, false, Stroke::new(Rgba::WHITE, 0.0))
+            .indices
+            .is_empty()
+    );
+}
+
+#[test]
+fn dashed_stroke_expands_each_dash() {
+    let line = [Vec2::ZERO, Vec2::new(10.0, 0.0)];
+    let stroke = Stroke::new(Rgba::WHITE, 2.0)
+        .cap(LineCap::Butt)
+        .dashed(Dash::new(2.0, 2.0, 0.0));
+    assert!((area(&expand(&line, false, stroke)) - 12.0).abs() < 1e-4);
+}
+
+#[test]
+fn styles_appear_in_snapshots_and_validation() {
+    let stroke = Stroke::new(Rgba::WHITE, 1.0)
+        .cap(LineCap::Square)
+        .join(LineJoin::Miter)
+        .dashed(Dash::new(4.0, 2.0, 1.0));
+    let line = |stroke| {
+        DrawCmd::Line(Line2 {
+            a: Vec2::ZERO,
+            b: Vec2::X,
+            stroke,
+        })
+    };
+    assert_eq!(
+        to_text(&[line(stroke)]),
+        "line (0 0) (1 0) w 1 glow 0 cap square join miter dash 4 2 phase 1 rgba(1 1 1 1)\n"
+    );
+    let bad = stroke.dashed(Dash::new(-1.0, 2.0, 0.0));
+    let kinds: Vec<_> = validate(&[line(bad)]).iter().map(|d| d.kind).collect();
+    assert_eq!(kinds, [DiagnosticKind::InvalidDash]);
+    let fine = stroke.dashed(Dash::new(1e-3, 1e-3, 0.0));
+    let diags = validate(&[line(fine)]);
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].kind, DiagnosticKind::DashTooFine);
+    assert_eq!(diags[0].severity, Severity::Warning);
+}
@@ -0,0 +1,149 @@
+use glam::{Mat3, Vec2};
+use vectorcade_shared::Rgba;
+use vectorcade_shared::draw::{
+    Diagnostic, DiagnosticKind, DrawCmd, LayerId, Line2, Severity, Stroke, StrokeVertex, rect_wire,
+    validate,
+};
+use vectorcade_shared::font::FontStyleId;
+
+fn kinds(cmds: &[DrawCmd]) -> Vec<(usize, DiagnosticKind)> {
+    validate(cmds).iter().map(|d| (d.index, d.kind)).collect()
+}
+
+#[test]
+fn well_formed_list_has_no_diagnostics() {
+    let cmds = [
+        DrawCmd::Clear { color: Rgba::BLACK },
+        DrawCmd::BeginLayer(LayerId::new("world", 0)),
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        rect_wire(Vec2::ZERO, Vec2::ONE, Stroke::default()),
+        DrawCmd::PopTransform,
+        DrawCmd::EndLayer,
+        DrawCmd::Text {
+            pos: Vec2::ZERO,
+            text: "OK".into(),
+            size_px: 12.0,
+            color: Rgba::WHITE,
+            style: FontStyleId::DEFAULT,
+        },
+    ];
+    assert!(validate(&cmds).is_empty());
+}
+
+#[test]
+fn unbalanced_stacks_are_reported_at_their_commands() {
+    let cmds = [
+        DrawCmd::PopTransform,
+        DrawCmd::BeginLayer(LayerId::new("a", 0)),
+        DrawCmd::PushTransform(Mat3::IDENTITY),
+        DrawCmd::EndLayer,
+        DrawCmd::EndLayer,
+    ];
+    assert_eq!(
+        kinds(&cmds),
+        [
+            (0, DiagnosticKind::UnbalancedPop),
+            (2, DiagnosticKind::UnclosedPush),
+            (4, DiagnosticKind::UnbalancedEndLayer),
+        ]
+    );
+}
+
+#[test]
+fn geometry_and_style_problems_are_reported() {
+    let bad = Stroke::new(Rgba(1.0, 1.0, 1.0, 1.5), -2.0);
+    let cmds = [
+        DrawCmd::Polyline {
+            pts: vec![Vec2::ZERO],
+            closed: false,
+            stroke: Stroke::default(),
+        },
+        DrawCmd::Line(Line2 {
+            a: Vec2::new(f32::NAN, 0.0),
+            b: Vec2::ZERO,
+            stroke: bad,
+        }),
+        DrawCmd::PushTransform(Mat3::from_diagonal(glam::Vec3::splat(f32::INFINITY))),
+        DrawCmd::PopTransform,
+    ];
+    assert_eq!(
+        kinds(&cmds),
+        [
+            (0, DiagnosticKind::TooFewPoints { count: 1 }),
+            (1, DiagnosticKind::NonFinite),
+            (1, DiagnosticKind::NegativeWidth { width_px: -2.0 }),
+            (1, DiagnosticKind::AlphaOutOfRange { alpha: 1.5 }),
+            (2, DiagnosticKind::NonFinite),
+        ]
+    );
+}
+
+#[test]
+fn severities_and_messages() {
+    let cmds = [
+        DrawCmd::BeginLayer(LayerId::new("open", 0)),
+        DrawCmd::PopTransform,
+    ];
+    let diags = validate(&cmds);
+    assert_eq!(
+        diags,
+        [
+            Diagnostic {
+                index: 0,
+                severity: Severity::Warning,
+                kind: DiagnosticKind::UnclosedLayer,
+            },
+            Diagnostic {
+                index: 1,
+                severity: Severity::Error,
+                kind: DiagnosticKind::UnbalancedPop,
+            },
+        ]
+    );
+    assert_eq!(
+        diags[1].to_string(),
+        "command 1: error: PopTransform without matching PushTransform"
+    );
+    assert!(Severity::Error > Severity::Warning);
+}
+
+#[test]
+fn gradient_polyline_reports_each_kind_once() {
+    let bad = |x: f32, width_px: f32| {
+        StrokeVertex::new(Vec2::new(x, 0.0), Rgba(1.0, 1.0, 1.0, 2.0), width_px)
+    };
+    let cmds = [DrawCmd::GradientPolyline {
+        verts: vec![bad(f32::NAN, -1.0), bad(1.0, -2.0), bad(f32::NAN, f32::NAN)],
+        closed: false,
+        glow: 0.0,
+    }];
+    assert_eq!(
+        kinds(&cmds),
+        [
+            (0, DiagnosticKind::NonFinite),
+            (0, DiagnosticKind::NegativeWidth { width_px: -1.0 }),
+            (0, DiagnosticKind::AlphaOutOfRange { alpha: 2.0 }),
+        ]
+    );
+}
+
+#[test]
+fn arc_angles_are_checked_separately() {
+    let arc = |start_angle, end_angle| DrawCmd::Arc {
+        center: Vec2::ZERO,
+        radius: 1.0,
+        start_angle,
+        end_angle,
+        stroke: Stroke::default(),
+    };
+    // Finite angles whose sum overflows are fine.
+    assert!(validate(&[arc(f32::MAX, f32::MAX)]).is_empty());
+    assert_eq!(
+        kinds(&[arc(0.0, f32::INFINITY)]),
+        [(0, DiagnosticKind::NonFinite)]
+    );
+    assert_eq!(
+        kinds(&[arc(f32::NEG_INFINITY, 0.0)]),
+        [(0, DiagnosticKind::NonFinite)]
+    );
+}